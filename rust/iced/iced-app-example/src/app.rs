@@ -1,13 +1,36 @@
 use iced::time::{self, milliseconds};
-use iced::widget::{button, column, container, row, scrollable, stack, text, Column};
-use iced::{Color, Element, Point, Subscription, Theme};
+use iced::{event, window};
+use iced::widget::{button, container, float, operation, row, scrollable, stack, text, themer, Column};
+use iced::{Background, Color, Element, Subscription, Task, Theme, Vector};
 use std::time::Instant;
 
-use crate::components::{config_menu, menu};
-use crate::message::{BrowserOption, Message, Role, ViewMode};
-use crate::utils::animation::{AnimationDirection, get_animation_progress};
-use crate::widgets::{BarChart, BarData};
-use crate::views::{browser_form, chart, form, summary};
+use crate::cli::Options;
+use crate::dataset::{self, Dataset};
+use crate::command_palette::{self, Command, CommandPalette};
+use crate::components::{
+    command_palette as palette_overlay, config_menu, debugger as debugger_overlay, menu, modal, snackbar,
+};
+use crate::debugger::{Debugger, Frame};
+use crate::history::{self, History, Snapshot, UndoKind, DEFAULT_HISTORY_LIMIT};
+use crate::keymap::{self, Action, Keymap};
+use crate::message::{BrowserOption, Message, Role, ViewTransition, WidgetAnimation};
+use crate::persistence::{self, SavedState, Storage, AUTOSAVE_DEBOUNCE_MS};
+use crate::router::{self, Route, Router};
+use crate::session::{self, Recorder, Replay, SessionEntry};
+use crate::settings::{self, Settings, SettingsError};
+use crate::styles::skin::Skin;
+use crate::styles::theme::{self, CustomTheme, ThemeChoice};
+use crate::theme_editor::{self, ThemeEditor};
+use crate::utils::animation::{Animation, Curve, Direction, Easing};
+use crate::utils::clock::Clock;
+use crate::widgets::{inert, BarChart, Chart, ChartKind, ExportSize, LineChart, PieChart, ScatterChart, Series};
+use crate::views::{self, Section, ViewId};
+
+/// How long the "Undo" snackbar stays up after resetting all data
+const RESET_UNDO_WINDOW_MS: f32 = 5000.0;
+
+/// Scrollable around the current view, for jumping to form fields
+const CONTENT_ID: &str = "content";
 
 pub struct App {
     pub value: i64,
@@ -17,13 +40,30 @@ pub struct App {
     pub experience: f32,
     pub selected_role: Option<Role>,
     pub selected_theme: Theme,
+    /// Widget styles, picked independently of the color theme
+    pub skin: Skin,
     pub chart: BarChart,
-    pub current_view: ViewMode,
-    pub previous_view: ViewMode,
-    pub transition_start: Option<Instant>,
+    // The same data as the other chart types, picked in the Chart view
+    pub chart_kind: ChartKind,
+    pub line_chart: LineChart,
+    pub pie_chart: PieChart,
+    pub scatter_chart: ScatterChart,
+    // Chart export options and the outcome of the last export
+    pub chart_export_size: ExportSize,
+    pub chart_export_transparent: bool,
+    pub chart_status: Option<String>,
+    // Data file shown instead of the form values
+    pub chart_import_path: String,
+    pub chart_dataset: Option<Dataset>,
+    pub current_view: ViewId,
+    pub previous_view: ViewId,
+    /// Section of the current view last navigated to, so Back can return there
+    pub current_section: Option<Section>,
+    pub router: Router,
+    pub view_transition: Animation,
+    pub transition_style: ViewTransition,
     pub second_button_visible: bool,
-    pub button_animation_start: Option<Instant>,
-    pub button_animation_direction: AnimationDirection,
+    pub second_button_animation: Animation,
     // Browser form fields
     pub browser_text: String,
     pub browser_password: String,
@@ -32,219 +72,570 @@ pub struct App {
     pub browser_selected_option: Option<BrowserOption>,
     // Config menu state
     pub config_menu_open: bool,
-    pub config_menu_animation_start: Option<Instant>,
+    pub config_menu_animation: Animation,
+    pub config_status: Option<String>,
+    // Reset confirmation and undo window
+    pub reset_confirm_open: bool,
+    pub reset_undo: Option<(Box<App>, Instant)>,
+    // Opt-in persistence across launches
+    pub autosave_enabled: bool,
+    pub autosave_pending: Option<Instant>,
+    pub history: History<Snapshot>,
+    /// Time of the message currently being handled, shared by all animations
+    pub now: Instant,
+    // Session recording and replay
+    pub clock: Clock,
+    pub recorder: Option<Recorder>,
+    pub replay: Option<Replay>,
+    /// Where saves and exports go; a sandbox once a replay has started
+    pub storage: Storage,
+    pub debugger: Debugger,
+    pub keymap: Keymap,
+    pub command_palette: CommandPalette,
+    // User themes and the one being edited
+    pub custom_themes: Vec<CustomTheme>,
+    pub theme_editor: ThemeEditor,
 }
 
 impl App {
-    pub fn new() -> (Self, iced::Task<Message>) {
-        (Self::default(), iced::Task::none())
-    }
+    pub fn new(options: Options) -> (Self, Task<Message>) {
+        let mut app = Self::default();
 
-    pub fn update(&mut self, message: Message) {
-        match message {
-            Message::Increment => {
-                self.value += 1;
-            }
-            Message::Decrement => {
-                self.value -= 1;
-            }
-            Message::NameChanged(name) => {
-                self.name = name;
+        // Loaded first so a restored custom theme can be found
+        match theme_editor::load() {
+            Ok(themes) => app.custom_themes = themes,
+            Err(error) => app.config_status = Some(format!("Themes: {error}")),
+        }
+
+        // A replay starts from a clean slate so it ends where the recording did
+        if let Some(path) = &options.replay {
+            match session::load(path) {
+                Ok(entries) => {
+                    if let Err(error) = app.start_replay(entries, Instant::now()) {
+                        app.config_status = Some(format!("Could not set up the replay sandbox: {error}"));
+                    }
+                }
+                Err(error) => app.config_status = Some(format!("Could not load session: {error}")),
             }
-            Message::EmailChanged(email) => {
-                self.email = email;
+        } else {
+            app.restore(persistence::load());
+        }
+
+        // A route from the command line wins over the restored view and
+        // opens without a transition
+        let open_route = match options.route {
+            Some(route) if app.replay.is_none() => {
+                app.current_view = route.view;
+                app.previous_view = route.view;
+                app.current_section = route.section;
+                scroll_to(route.section)
             }
-            Message::AgeChanged(age) => {
-                self.age = age;
+            _ => Task::none(),
+        };
+        app.update_chart();
+
+        if let Some(path) = &options.record {
+            match Recorder::create(path, Instant::now()) {
+                Ok(recorder) => app.recorder = Some(recorder),
+                Err(error) => {
+                    app.config_status = Some(format!("Could not start recording: {error}"));
+                }
             }
-            Message::ExperienceChanged(experience) => {
-                self.experience = experience;
+        }
+
+        // A broken keymap leaves the defaults for anything it got wrong
+        let (keymap, errors) = Keymap::load();
+        app.keymap = keymap;
+        if !errors.is_empty() {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            app.config_status = Some(format!("Keymap: {}", errors.join("; ")));
+        }
+
+        (app, open_route)
+    }
+
+    /// Play `entries` back from `now`, writing into a sandbox from here on:
+    /// the replayed saves and exports must not overwrite the user's files,
+    /// and neither may autosaves of the replayed state after it finishes
+    pub fn start_replay(&mut self, entries: Vec<SessionEntry>, now: Instant) -> std::io::Result<()> {
+        self.storage = Storage::sandbox()?;
+        self.replay = Some(Replay::new(entries, now));
+        Ok(())
+    }
+
+    /// Restore the last session; a broken file must never prevent startup
+    pub fn restore(&mut self, saved: Result<Option<SavedState>, SettingsError>) {
+        match saved {
+            Ok(Some(state)) if state.autosave => {
+                state.apply(self);
+                self.update_chart();
             }
-            Message::RoleSelected(role) => {
-                self.selected_role = Some(role);
+            Ok(_) => {}
+            Err(error) => {
+                self.config_status = Some(format!("Could not restore last session: {error}"));
             }
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        // Scrubbed back in time - timers stay frozen until resumed
+        if self.debugger.is_paused() && matches!(message, Message::Tick(_)) {
+            return Task::none();
+        }
+        // Snapshots are only worth taking while the overlay can show them
+        if !self.debugger.open || !Debugger::observes(&message) {
+            return self.handle(message);
+        }
+
+        let before = Frame::from_app(self);
+        let logged = message.clone();
+        let task = self.handle(message);
+        self.debugger.log(&logged, before, Frame::from_app(self));
+        task
+    }
+
+    fn handle(&mut self, message: Message) -> Task<Message> {
+        self.now = match message {
+            Message::Tick(now) => now,
+            _ => self.clock.now(),
+        };
+
+        if let Some(recorder) = &mut self.recorder
+            && let Err(error) = recorder.record(&message, self.now)
+        {
+            self.config_status = Some(format!("Recording stopped: {error}"));
+            self.recorder = None;
+        }
+
+        // Anything but ticks and hover noise counts as a change worth saving
+        if self.autosave_enabled
+            && !matches!(
+                message,
+                Message::Tick(_)
+                    | Message::ReplayTick(_)
+                    | Message::ConfigMenuInteraction
+                    | Message::DebuggerToggled
+                    | Message::WindowCloseRequested(_)
+            )
+        {
+            self.autosave_pending = Some(self.now);
+        }
+
+        // Remember the state before anything undoable happens
+        let recorded = (history::undo_kind(&message) != UndoKind::Skip)
+            .then(|| (Snapshot::from_app(self), message.clone()));
+
+        let mut task = Task::none();
+
+        match message {
             Message::ThemeSelected(theme) => {
                 self.selected_theme = theme.to_theme();
             }
-            Message::ViewChanged(view) => {
-                if self.current_view != view {
-                    self.previous_view = self.current_view;
-                    self.current_view = view;
-                    self.transition_start = Some(Instant::now());
+            Message::CustomThemeSelected(name) => {
+                if let Some(theme) = ThemeChoice::Custom(name).to_theme(&self.custom_themes) {
+                    self.selected_theme = theme;
                 }
             }
-            Message::Tick(_now) => {
-                // Animation tick - will trigger redraws during transition
-                // Also check if button animation is complete
-                if let Some(start) = self.button_animation_start {
-                    let elapsed = start.elapsed().as_millis() as f32;
-                    let duration = 300.0; // 300ms animation
-                    if elapsed >= duration {
-                        // Animation complete
-                        match self.button_animation_direction {
-                            AnimationDirection::FadeIn => {
-                                self.button_animation_start = None;
-                                self.button_animation_direction = AnimationDirection::None;
-                            }
-                            AnimationDirection::FadeOut => {
-                                self.second_button_visible = false;
-                                self.button_animation_start = None;
-                                self.button_animation_direction = AnimationDirection::None;
-                            }
-                            AnimationDirection::None => {}
-                        }
-                    }
+            Message::ViewChanged(view) => {
+                return self.navigate(Route::from(view));
+            }
+            Message::Navigate(route) => {
+                return self.navigate(route);
+            }
+            Message::NavigateBack => {
+                if let Some(route) = self.router.back(self.route()) {
+                    return self.show_route(route);
                 }
-                
-                // Check if config menu animation is complete
-                if let Some(start) = self.config_menu_animation_start {
-                    let elapsed = start.elapsed().as_millis() as f32;
-                    let duration = 300.0; // 300ms animation
-                    if elapsed >= duration {
-                        self.config_menu_animation_start = None;
-                    }
+            }
+            Message::NavigateForward => {
+                if let Some(route) = self.router.forward(self.route()) {
+                    return self.show_route(route);
                 }
             }
-            Message::ShowSecondButton => {
-                self.second_button_visible = true;
-                self.button_animation_start = Some(Instant::now());
-                self.button_animation_direction = AnimationDirection::FadeIn;
+            Message::TransitionSelected(transition) => {
+                self.transition_style = transition;
+            }
+            Message::CurveSelected(animation, curve) => {
+                self.animation_mut(animation).set_curve(curve);
+            }
+            Message::SkinSelected(skin) => {
+                self.skin = skin;
+            }
+            Message::Tick(now) => {
+                // Settle finished animations and collect their completion messages
+                let completed: Vec<Message> = [
+                    &mut self.view_transition,
+                    &mut self.second_button_animation,
+                    &mut self.config_menu_animation,
+                ]
+                .into_iter()
+                .filter_map(|animation| animation.tick(now))
+                .collect();
+                self.chart.tick(now);
+
+                // Debounced autosave
+                if let Some(changed_at) = self.autosave_pending
+                    && now.saturating_duration_since(changed_at).as_millis() as f32 >= AUTOSAVE_DEBOUNCE_MS
+                {
+                    self.save_state();
+                }
+
+                // Drop the reset snapshot once the undo window has passed
+                if let Some((_, reset_at)) = &self.reset_undo
+                    && now.saturating_duration_since(*reset_at).as_millis() as f32 >= RESET_UNDO_WINDOW_MS
+                {
+                    self.reset_undo = None;
+                }
+
+                return Task::batch(completed.into_iter().map(Task::done));
             }
-            Message::HideSecondButton => {
-                self.button_animation_start = Some(Instant::now());
-                self.button_animation_direction = AnimationDirection::FadeOut;
+            Message::ShortcutPressed(action) => {
+                // Dispatched as its own message so the session log and undo
+                // history see what the shortcut did
+                if let Some(message) = self.action_message(action) {
+                    return Task::done(message);
+                }
             }
-            Message::BrowserTextChanged(text) => {
-                self.browser_text = text;
+            Message::CommandPaletteToggled => {
+                if self.command_palette.open {
+                    self.command_palette.open = false;
+                } else {
+                    self.command_palette.show();
+                    return operation::focus(palette_overlay::INPUT_ID);
+                }
             }
-            Message::BrowserPasswordChanged(password) => {
-                self.browser_password = password;
+            Message::CommandPaletteClosed => {
+                self.command_palette.open = false;
             }
-            Message::BrowserSliderChanged(value) => {
-                self.browser_slider_value = value;
+            Message::CommandPaletteQueryChanged(query) => {
+                self.command_palette.set_query(query);
+                return operation::snap_to(palette_overlay::RESULTS_ID, operation::RelativeOffset::START);
             }
-            Message::BrowserCheckboxToggled(checked) => {
-                self.browser_checkbox = checked;
+            Message::CommandPaletteMoved(offset) => {
+                self.command_palette.move_selection(offset);
+                // Scroll in proportion so the highlight stays in view
+                let last = self.command_palette.matches().len().saturating_sub(1).max(1);
+                let y = self.command_palette.selected as f32 / last as f32;
+                return operation::snap_to(palette_overlay::RESULTS_ID, operation::RelativeOffset { x: 0.0, y });
             }
-            Message::BrowserOptionSelected(option) => {
-                self.browser_selected_option = Some(option);
+            Message::CommandPaletteSubmitted => {
+                if let Some(command) = self.command_palette.selected_command() {
+                    return self.run_command(command);
+                }
             }
-            Message::BrowserFormSubmit => {
-                // Handle form submission (e.g., log values, validate, etc.)
-                println!("Form submitted!");
-                println!("Text: {}", self.browser_text);
-                println!("Slider: {}", self.browser_slider_value);
-                println!("Checkbox: {}", self.browser_checkbox);
+            Message::CommandPaletteRun(command) => {
+                return self.run_command(command);
             }
             Message::ToggleConfigMenu => {
                 self.config_menu_open = !self.config_menu_open;
-                self.config_menu_animation_start = Some(Instant::now());
+                let direction = if self.config_menu_open {
+                    Direction::Forward
+                } else {
+                    Direction::Reverse
+                };
+                self.config_menu_animation.play(direction, self.now, None);
             }
             Message::ConfigResetData => {
-                // Handle reset data action
-                println!("Reset All Data clicked");
+                self.reset_confirm_open = true;
+            }
+            Message::ConfigResetConfirm => {
+                self.reset_confirm_open = false;
+                let mut previous = Box::new(App::default());
+                self.swap_user_data(&mut previous);
+                self.reset_undo = Some((previous, self.now));
+            }
+            Message::ConfigResetCancel => {
+                self.reset_confirm_open = false;
+            }
+            Message::ConfigResetUndo => {
+                if let Some((mut previous, _)) = self.reset_undo.take() {
+                    self.swap_user_data(&mut previous);
+                }
             }
             Message::ConfigExportSettings => {
-                // Handle export settings action
-                println!("Export Settings clicked");
+                let path = self.storage.work_file(settings::SETTINGS_FILE);
+                self.config_status = Some(match Settings::from_app(self).save(&path) {
+                    Ok(()) => format!("Exported to {}", path.display()),
+                    Err(error) => format!("Export failed: {error}"),
+                });
             }
             Message::ConfigImportSettings => {
-                // Handle import settings action
-                println!("Import Settings clicked");
+                let path = settings::settings_path();
+                self.config_status = Some(match Settings::load(&path) {
+                    Ok(imported) => {
+                        imported.apply(self);
+                        format!("Imported from {}", path.display())
+                    }
+                    Err(error) => format!("Import failed: {error}"),
+                });
             }
             Message::ConfigMenuInteraction => {
                 // Dummy handler to block click-through - does nothing
             }
+            Message::ConfigAutosaveToggled(enabled) => {
+                self.autosave_enabled = enabled;
+                // Save right away so turning it off also sticks across launches
+                self.save_state();
+            }
+            Message::WindowCloseRequested(id) => {
+                if self.autosave_enabled {
+                    self.save_state();
+                }
+                return window::close(id);
+            }
+            Message::Undo => {
+                if let Some(state) = self.history.undo(Snapshot::from_app(self)) {
+                    state.apply(self);
+                }
+            }
+            Message::Redo => {
+                if let Some(state) = self.history.redo(Snapshot::from_app(self), self.now) {
+                    state.apply(self);
+                }
+            }
+            Message::HistoryLimitChanged(limit) => {
+                self.history.set_limit(limit as usize);
+            }
+            Message::ReplayTick(now) => {
+                let due = match &mut self.replay {
+                    Some(replay) => replay.due(now),
+                    None => Vec::new(),
+                };
+
+                session::play(self, due);
+
+                if self.replay.as_ref().is_some_and(Replay::is_finished) {
+                    self.replay = None;
+                    self.clock = Clock::System;
+                    self.config_status = Some("Session replay finished".to_string());
+                }
+                return Task::none();
+            }
+            Message::DebuggerToggled => {
+                self.debugger.open = !self.debugger.open;
+                // Closing the overlay goes back to running live
+                if !self.debugger.open {
+                    self.debugger.resume();
+                }
+            }
+            Message::DebuggerScrubbed(index) => {
+                if let Some(frame) = self.debugger.scrub(index) {
+                    frame.apply(self);
+                }
+            }
+            Message::DebuggerResume => {
+                self.debugger.resume();
+            }
+            // Anything else comes from the widgets of one of the views
+            message => task = views::update(self, message),
+        }
+
+        // Only messages that actually changed something become undo steps
+        if let Some((before, message)) = recorded
+            && before != Snapshot::from_app(self)
+        {
+            self.history.record(before, &message, self.now);
         }
 
         // Update chart data whenever values change
         self.update_chart();
+
+        task
+    }
+
+    fn save_state(&mut self) {
+        self.autosave_pending = None;
+        if let Err(error) = persistence::save(&self.storage, &SavedState::from_app(self)) {
+            self.config_status = Some(format!("Autosave failed: {error}"));
+        }
+    }
+
+    pub fn animation(&self, animation: WidgetAnimation) -> &Animation {
+        match animation {
+            WidgetAnimation::ViewTransition => &self.view_transition,
+            WidgetAnimation::ButtonFade => &self.second_button_animation,
+            WidgetAnimation::DrawerSlide => &self.config_menu_animation,
+        }
+    }
+
+    fn animation_mut(&mut self, animation: WidgetAnimation) -> &mut Animation {
+        match animation {
+            WidgetAnimation::ViewTransition => &mut self.view_transition,
+            WidgetAnimation::ButtonFade => &mut self.second_button_animation,
+            WidgetAnimation::DrawerSlide => &mut self.config_menu_animation,
+        }
+    }
+
+    /// Copy of the chart shown for the selected chart type, to render in the
+    /// background
+    pub fn current_chart_snapshot(&self) -> Box<dyn Chart + Send> {
+        match self.chart_kind {
+            ChartKind::Bar => Box::new(self.chart.clone()),
+            ChartKind::Line | ChartKind::Area => Box::new(self.line_chart.clone()),
+            ChartKind::Pie | ChartKind::Donut => Box::new(self.pie_chart.clone()),
+            ChartKind::Scatter => Box::new(self.scatter_chart.clone()),
+        }
+    }
+
+    /// Where the app is now, for Back and Forward to return to
+    pub fn route(&self) -> Route {
+        Route {
+            view: self.current_view,
+            // Views changed without navigating leave another view's section behind
+            section: self
+                .current_section
+                .filter(|section| views::get(self.current_view).sections.contains(section)),
+        }
+    }
+
+    /// Show `route`, remembering the current one so Back returns to it
+    fn navigate(&mut self, route: Route) -> Task<Message> {
+        if route.view != self.current_view {
+            self.router.visit(self.route());
+            return self.show_route(route);
+        }
+        // Within the same view only a new section is worth remembering
+        if route.section.is_some() {
+            self.current_section = route.section;
+        }
+        scroll_to(route.section)
+    }
+
+    /// Switch to `route` without touching the Back and Forward stacks
+    fn show_route(&mut self, route: Route) -> Task<Message> {
+        self.show_view(route.view);
+        self.current_section = route.section;
+        scroll_to(route.section)
+    }
+
+    /// Switch to `view` with the selected transition
+    fn show_view(&mut self, view: ViewId) {
+        if self.current_view == view {
+            return;
+        }
+
+        let progress = self.view_transition.linear_progress(self.now);
+        let in_flight = self.view_transition.is_active();
+
+        if in_flight && view == self.previous_view {
+            // Heading back mid-transition: run the same transition
+            // in reverse from where it is instead of popping
+            self.previous_view = self.current_view;
+            self.current_view = view;
+            self.view_transition.restart_from(1.0 - progress, self.now);
+        } else {
+            // Leave from whichever view is currently the more visible one
+            if !in_flight || progress >= 0.5 {
+                self.previous_view = self.current_view;
+            }
+            self.current_view = view;
+            self.view_transition.restart(self.now);
+        }
+    }
+
+    /// Close the command palette and dispatch `command` as its own message
+    fn run_command(&mut self, command: Command) -> Task<Message> {
+        self.command_palette.open = false;
+        Task::done(command.message())
+    }
+
+    /// Message performing `action` in the current state, if it applies
+    fn action_message(&self, action: Action) -> Option<Message> {
+        Some(match action {
+            Action::ShowView(view) => Message::ViewChanged(view),
+            Action::Back => Message::NavigateBack,
+            Action::Forward => Message::NavigateForward,
+            Action::CommandPalette => Message::CommandPaletteToggled,
+            Action::Close if self.command_palette.open => Message::CommandPaletteClosed,
+            Action::Close if self.reset_confirm_open => Message::ConfigResetCancel,
+            Action::Close if self.config_menu_open => Message::ToggleConfigMenu,
+            Action::Close => return None,
+            Action::OpenSettings if self.config_menu_open => return None,
+            Action::OpenSettings => Message::ToggleConfigMenu,
+            Action::ExportSettings => Message::ConfigExportSettings,
+            Action::Undo => Message::Undo,
+            Action::Redo => Message::Redo,
+            Action::ToggleDebugger => Message::DebuggerToggled,
+        })
     }
 
     fn update_chart(&mut self) {
-        let chart_data = vec![
-            BarData {
-                label: "Counter".to_string(),
-                value: self.value.abs() as f32,
-                color: Color::from_rgb(0.2, 0.6, 0.9),
-            },
-            BarData {
-                label: "Age".to_string(),
-                value: self.age as f32,
-                color: Color::from_rgb(0.9, 0.4, 0.2),
-            },
-            BarData {
-                label: "Experience".to_string(),
-                value: self.experience,
-                color: Color::from_rgb(0.3, 0.8, 0.3),
-            },
-        ];
-        self.chart.update_data(chart_data);
+        let (labels, series, colors) = match &self.chart_dataset {
+            Some(dataset) => {
+                let bars = dataset.bars();
+                let series = vec![Series {
+                    name: dataset.mapping().value.clone(),
+                    color: Color::from_rgb(0.2, 0.6, 0.9),
+                    values: bars.values.clone(),
+                }];
+                (bars.labels.clone(), series, bars.colors.clone())
+            }
+            None => {
+                let labels = vec!["Counter".to_string(), "Age".to_string(), "Experience".to_string()];
+                let series = vec![Series {
+                    name: "Value".to_string(),
+                    color: Color::from_rgb(0.2, 0.6, 0.9),
+                    values: vec![self.value as f32, self.age as f32, self.experience],
+                }];
+                (labels, series, Vec::new())
+            }
+        };
+        self.line_chart.update_data(labels.clone(), series.clone(), self.now);
+        self.pie_chart.update_data(labels.clone(), series.clone(), self.now);
+        self.scatter_chart.update_data(labels.clone(), series.clone(), self.now);
+        self.chart.update_data(labels, series, self.now);
+        self.chart.set_bar_colors(&colors);
+    }
+
+    /// Exchange all user-entered data with `other`, leaving theme, view and
+    /// UI state untouched. Used by "Reset All Data" and its undo.
+    fn swap_user_data(&mut self, other: &mut App) {
+        std::mem::swap(&mut self.value, &mut other.value);
+        std::mem::swap(&mut self.name, &mut other.name);
+        std::mem::swap(&mut self.email, &mut other.email);
+        std::mem::swap(&mut self.age, &mut other.age);
+        std::mem::swap(&mut self.experience, &mut other.experience);
+        std::mem::swap(&mut self.selected_role, &mut other.selected_role);
+        std::mem::swap(&mut self.browser_text, &mut other.browser_text);
+        std::mem::swap(&mut self.browser_password, &mut other.browser_password);
+        std::mem::swap(&mut self.browser_slider_value, &mut other.browser_slider_value);
+        std::mem::swap(&mut self.browser_checkbox, &mut other.browser_checkbox);
+        std::mem::swap(&mut self.browser_selected_option, &mut other.browser_selected_option);
     }
 
     pub fn view(&self) -> iced::Element<'_, Message> {
-        let menu = menu::create_menu(self.current_view);
+        let menu = menu::create_menu(self.current_view, &self.router, self.skin);
         let content = self.create_content_with_transition();
         
         // Config menu toggle button (hamburger icon)
         let toggle_button = button(text("☰").size(24))
             .on_press(Message::ToggleConfigMenu)
             .padding(10)
-            .style(|_theme: &Theme, _status| {
-                button::Style {
-                    background: Some(iced::Background::Color(Color::from_rgba(0.2, 0.2, 0.2, 0.8))),
-                    border: iced::Border {
-                        color: Color::from_rgb(0.4, 0.4, 0.4),
-                        width: 1.0,
-                        radius: 4.0.into(),
-                    },
-                    text_color: Color::WHITE,
-                    shadow: iced::Shadow {
-                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.3),
-                        offset: iced::Vector::new(0.0, 2.0),
-                        blur_radius: 4.0,
-                    },
-                    snap: false,
-                }
-            });
+            .style(self.skin.secondary_button());
 
         // Main content
         let main_view = row![
             container(menu).padding(10),
-            container(scrollable(content)).padding(20).width(iced::Fill),
+            container(scrollable(content).id(CONTENT_ID)).padding(20).width(iced::Fill),
         ];
         
         // Config menu (sliding from left)
         let config_menu_widget = config_menu::create_config_menu(
-            self.config_menu_open,
-            self.config_menu_animation_start,
-        );
-        
-        let offset = config_menu::get_menu_offset(
-            self.config_menu_open,
-            self.config_menu_animation_start,
+            self.config_status.clone(),
+            self.autosave_enabled,
+            self.transition_style,
+            self.skin,
+            WidgetAnimation::ALL.map(|animation| self.animation(animation).current_curve()),
+            self.history.limit(),
+            self.debugger.open,
         );
         
-        // Calculate if menu should be visible
-        let menu_visible = self.config_menu_open || self.config_menu_animation_start.is_some();
-        
-        // Always show menu in stack, but position it off-screen when closed
-        let progress = get_animation_progress(self.config_menu_animation_start, 300.0);
-        
         // Calculate visible width for sliding effect (0 to 250)
-        // Menu stays at 250px but we clip it
-        let visible_width = if self.config_menu_open {
-            250.0 * progress // Show from 0 to 250
-        } else {
-            if self.config_menu_animation_start.is_some() {
-                250.0 * (1.0 - progress) // Hide from 250 to 0
-            } else {
-                0.0 // Start hidden
-            }
-        };
+        // Menu stays at 250px but we clip it; progress runs back to 0 when closing
+        let visible_width = 250.0 * self.config_menu_animation.progress(self.now);
         
         // Create layout with config menu overlay
-        {
+        let mut layers = {
             // Use stack to overlay config menu
             // Stack layers elements on top of each other without affecting layout
             stack![
@@ -319,96 +710,167 @@ impl App {
                         }
                     }),
             ]
-            .into()
+        };
+
+        // Undo snackbar after "Reset All Data"
+        if let Some((_, reset_at)) = &self.reset_undo {
+            let elapsed = self.now.saturating_duration_since(*reset_at).as_millis() as f32;
+            let remaining = (RESET_UNDO_WINDOW_MS - elapsed) / 1000.0;
+            layers = layers.push(snackbar::create_snackbar(
+                format!("All data reset ({:.0}s)", remaining.max(0.0).ceil()),
+                "Undo",
+                Message::ConfigResetUndo,
+                self.skin,
+            ));
         }
+
+        if self.command_palette.open {
+            layers = layers.push(palette_overlay::create_command_palette(&self.command_palette, self.skin));
+        }
+
+        // Reset confirmation sits above everything else
+        if self.reset_confirm_open {
+            layers = layers.push(modal::create_reset_confirmation(self.skin));
+        }
+
+        // Developer overlay on top of everything
+        if self.debugger.open {
+            layers = layers.push(debugger_overlay::create_debugger_overlay(&self.debugger, self.skin));
+        }
+
+        layers.into()
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        // Subscribe to time updates during transitions, button animations, or menu animation
-        let needs_animation = (self.transition_start.is_some()
-            && self.get_transition_progress() < 1.0)
-            || self.button_animation_start.is_some()
-            || self.config_menu_animation_start.is_some();
+        // The session log carries its own ticks, so live ones are suppressed
+        if self.replay.is_some() {
+            return Subscription::batch([
+                time::every(milliseconds(16)).map(|_| Message::ReplayTick(Instant::now())),
+                window::close_requests().map(Message::WindowCloseRequested),
+            ]);
+        }
+
+        // Subscribe to time updates only while an animation is running
+        let needs_animation = (self.view_transition.is_active()
+            || self.second_button_animation.is_active()
+            || self.config_menu_animation.is_active()
+            || self.chart.is_animating())
+            && !self.debugger.is_paused();
 
-        if needs_animation {
+        let animation = if needs_animation {
             time::every(milliseconds(16)).map(|_| Message::Tick(Instant::now()))
         } else {
             Subscription::none()
-        }
+        };
+
+        // Slower tick to count down and expire the reset undo window
+        let undo_countdown = if self.reset_undo.is_some() {
+            time::every(milliseconds(250)).map(|_| Message::Tick(Instant::now()))
+        } else {
+            Subscription::none()
+        };
+
+        let palette_keys = if self.command_palette.open {
+            event::listen_with(command_palette::arrow_key)
+        } else {
+            Subscription::none()
+        };
+
+        // Poll for the end of the autosave debounce period
+        let autosave = if self.autosave_pending.is_some() {
+            time::every(milliseconds(250)).map(|_| Message::Tick(Instant::now()))
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([
+            animation,
+            undo_countdown,
+            autosave,
+            palette_keys,
+            window::close_requests().map(Message::WindowCloseRequested),
+            event::listen_with(router::mouse_button),
+            event::listen_with(keymap::key_press)
+                .with(self.keymap.clone())
+                .filter_map(|(keymap, press)| {
+                    keymap.action(&press.key, press.modifiers).map(Message::ShortcutPressed)
+                }),
+        ])
     }
 
     pub fn theme(&self) -> Theme {
         self.selected_theme.clone()
     }
 
-    fn get_transition_progress(&self) -> f32 {
-        get_animation_progress(self.transition_start, 300.0)
-    }
+    fn create_content_with_transition(&self) -> Element<'_, Message> {
+        let progress = self.view_transition.progress(self.now);
 
-    fn create_content_with_transition(&self) -> Column<'_, Message> {
-        let progress = self.get_transition_progress();
-        let content = self.create_content();
-
-        // Apply opacity fade during transition
-        if progress < 1.0 {
-            // Wrap content in a container with opacity styling
-            let alpha = progress;
-            column![container(content).style(move |theme: &iced::Theme| {
-                let palette = theme.palette();
-                container::Style {
-                    text_color: Some(Color::from_rgba(
-                        palette.text.r,
-                        palette.text.g,
-                        palette.text.b,
-                        alpha,
-                    )),
-                    background: Some(iced::Background::Color(Color::from_rgba(
-                        palette.background.r,
-                        palette.background.g,
-                        palette.background.b,
-                        alpha,
-                    ))),
-                    border: iced::Border::default(),
-                    shadow: iced::Shadow::default(),
-                    snap: false,
-                }
-            })]
-        } else {
-            content
+        if progress >= 1.0 || self.previous_view == self.current_view {
+            return self.create_content(self.current_view).into();
         }
-    }
 
-    fn create_content(&self) -> Column<'_, Message> {
-        match self.current_view {
-            ViewMode::Form => form::create_form_view(self),
-            ViewMode::Chart => chart::create_chart_view(self),
-            ViewMode::Summary => summary::create_summary_view(self),
-            ViewMode::BrowserForm => browser_form::create_browser_form_view(self),
+        // Only the incoming view takes input while both are on screen
+        let outgoing = inert(self.create_content(self.previous_view));
+        let incoming = self.create_content(self.current_view);
+        let theme = self.theme();
+
+        match self.transition_style {
+            ViewTransition::CrossFade => stack![
+                faded(&theme, outgoing, 1.0 - progress),
+                faded(&theme, incoming, progress),
+            ]
+            .into(),
+            ViewTransition::Slide => {
+                // Views further down the menu come in from the right
+                let sign = if views::get(self.current_view).order > views::get(self.previous_view).order {
+                    1.0
+                } else {
+                    -1.0
+                };
+
+                stack![
+                    float(outgoing).translate(move |bounds, _viewport| {
+                        Vector::new(-sign * bounds.width * progress, 0.0)
+                    }),
+                    float(incoming).translate(move |bounds, _viewport| {
+                        Vector::new(sign * bounds.width * (1.0 - progress), 0.0)
+                    }),
+                ]
+                .into()
+            }
+            ViewTransition::Zoom => stack![
+                faded(&theme, outgoing, 1.0 - progress),
+                // Float only scales above 1.0, so the new view zooms down into place
+                float(faded(&theme, incoming, progress)).scale(1.0 + 0.1 * (1.0 - progress)),
+            ]
+            .into(),
         }
     }
+
+    fn create_content(&self, view: ViewId) -> Column<'_, Message> {
+        (views::get(view).view)(self)
+    }
+}
+
+/// Scroll the content to `section`, or leave it where it is
+fn scroll_to(section: Option<Section>) -> Task<Message> {
+    match section {
+        Some(section) => router::scroll_to(CONTENT_ID, section),
+        None => Task::none(),
+    }
+}
+
+/// `content` drawn at `alpha` opacity, its widgets and charts included
+fn faded<'a>(theme: &Theme, content: impl Into<Element<'a, Message>>, alpha: f32) -> Element<'a, Message> {
+    themer(Some(theme::faded(theme, alpha)), content)
+        .text_color(|theme| theme.palette().text)
+        .background(|theme| Background::Color(theme.palette().background))
+        .into()
 }
 
 impl Default for App {
     fn default() -> Self {
-        let chart_data = vec![
-            BarData {
-                label: "Counter".to_string(),
-                value: 0.0,
-                color: Color::from_rgb(0.2, 0.6, 0.9),
-            },
-            BarData {
-                label: "Age".to_string(),
-                value: 0.0,
-                color: Color::from_rgb(0.9, 0.4, 0.2),
-            },
-            BarData {
-                label: "Experience".to_string(),
-                value: 0.0,
-                color: Color::from_rgb(0.3, 0.8, 0.3),
-            },
-        ];
-
-        Self {
+        let mut app = Self {
             value: 0,
             name: String::new(),
             email: String::new(),
@@ -416,20 +878,52 @@ impl Default for App {
             experience: 0.0,
             selected_role: None,
             selected_theme: Theme::Light,
-            chart: BarChart::new(chart_data),
-            current_view: ViewMode::Form,
-            previous_view: ViewMode::Form,
-            transition_start: None,
+            skin: Skin::default(),
+            chart: BarChart::new(Vec::new(), Vec::new()).y_axis_title("Value"),
+            chart_kind: ChartKind::default(),
+            line_chart: LineChart::new(Vec::new(), Vec::new()).y_axis_title("Value"),
+            pie_chart: PieChart::new(Vec::new(), Vec::new()),
+            scatter_chart: ScatterChart::new(Vec::new(), Vec::new()),
+            chart_export_size: ExportSize::default(),
+            chart_export_transparent: false,
+            chart_status: None,
+            chart_import_path: dataset::DATA_FILE.to_string(),
+            chart_dataset: None,
+            current_view: views::first(),
+            previous_view: views::first(),
+            current_section: None,
+            router: Router::default(),
+            // Resting at the end so the first view shows without a fade
+            view_transition: Animation::default().easing(Easing::CubicOut).completed(),
+            transition_style: ViewTransition::default(),
             second_button_visible: false,
-            button_animation_start: None,
-            button_animation_direction: AnimationDirection::None,
+            second_button_animation: Animation::default().easing(Easing::QuadInOut),
             browser_text: String::new(),
             browser_password: String::new(),
             browser_slider_value: 50.0,
             browser_checkbox: false,
             browser_selected_option: None,
             config_menu_open: false,
-            config_menu_animation_start: None,
-        }
+            // The drawer can be flicked back mid-slide, so it keeps its momentum
+            config_menu_animation: Animation::default().curve(Curve::Spring),
+            config_status: None,
+            reset_confirm_open: false,
+            reset_undo: None,
+            autosave_enabled: false,
+            autosave_pending: None,
+            history: History::new(DEFAULT_HISTORY_LIMIT),
+            now: Instant::now(),
+            clock: Clock::default(),
+            recorder: None,
+            replay: None,
+            storage: Storage::system(),
+            debugger: Debugger::default(),
+            keymap: Keymap::default(),
+            command_palette: CommandPalette::default(),
+            custom_themes: Vec::new(),
+            theme_editor: ThemeEditor::default(),
+        };
+        app.update_chart();
+        app
     }
 }
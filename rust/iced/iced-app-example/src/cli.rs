@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use crate::router::Route;
+
+pub const USAGE: &str = "\
+Usage: icedapp [OPTIONS] [ROUTE]
+
+Arguments:
+  [ROUTE]           View to open, like /chart or /form#settings
+
+Options:
+  --record <FILE>   Record every message to a session log
+  --replay <FILE>   Play a session log back through the app
+  -h, --help        Print this help";
+
+/// Command line options
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub route: Option<Route>,
+    /// Print [`USAGE`] and exit instead of starting the app
+    pub help: bool,
+}
+
+impl Options {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--record" => options.record = Some(path_value(&arg, args.next())?),
+                "--replay" => options.replay = Some(path_value(&arg, args.next())?),
+                "-h" | "--help" => {
+                    options.help = true;
+                    return Ok(options);
+                }
+                other if other.starts_with('-') || options.route.is_some() => {
+                    return Err(format!("unknown argument `{other}`\n\n{USAGE}"));
+                }
+                route => {
+                    let route = route.parse().map_err(|error| format!("{error}\n\n{USAGE}"))?;
+                    options.route = Some(route);
+                }
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn path_value(flag: &str, value: Option<String>) -> Result<PathBuf, String> {
+    value
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("`{flag}` needs a file path\n\n{USAGE}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::{chart, form};
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn route_opens_the_app_on_a_view() {
+        let options = parse(&["--record", "session.jsonl", "/form#settings"]).unwrap();
+        assert_eq!(options.record, Some(PathBuf::from("session.jsonl")));
+        assert_eq!(options.route, Some(Route::section(form::VIEW.id, form::SETTINGS)));
+
+        assert_eq!(parse(&["/chart"]).unwrap().route, Some(Route::from(chart::VIEW.id)));
+        assert_eq!(parse(&[]).unwrap().route, None);
+    }
+
+    #[test]
+    fn bad_routes_and_extra_arguments_are_errors() {
+        assert!(parse(&["/nowhere"]).unwrap_err().starts_with("no view at `/nowhere`"));
+        assert!(parse(&["/chart", "/summary"]).unwrap_err().starts_with("unknown argument `/summary`"));
+        assert!(parse(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn help_is_not_an_error() {
+        assert!(parse(&["--help"]).unwrap().help);
+        assert!(parse(&["/chart", "-h", "--verbose"]).unwrap().help);
+        assert!(!parse(&["/chart"]).unwrap().help);
+    }
+}
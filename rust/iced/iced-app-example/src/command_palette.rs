@@ -0,0 +1,230 @@
+use iced::keyboard::{self, key, Key};
+use iced::{event, window};
+use serde::{Deserialize, Serialize};
+
+use crate::message::Message;
+use crate::styles::skin::Skin;
+use crate::styles::theme::ThemeType;
+use crate::views::{self, ViewId};
+
+/// Anything the command palette can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Command {
+    View(ViewId),
+    Theme(ThemeType),
+    Skin(Skin),
+    ResetData,
+    ExportSettings,
+    ImportSettings,
+}
+
+impl Command {
+    /// Every command, in the order shown before anything is typed
+    pub fn all() -> impl Iterator<Item = Command> {
+        views::all()
+            .map(|view| Command::View(view.id))
+            .chain([Command::ResetData, Command::ExportSettings, Command::ImportSettings])
+            .chain(ThemeType::ALL.into_iter().map(Command::Theme))
+            .chain(Skin::ALL.into_iter().map(Command::Skin))
+    }
+
+    pub fn message(self) -> Message {
+        match self {
+            Command::View(view) => Message::ViewChanged(view),
+            Command::Theme(theme) => Message::ThemeSelected(theme),
+            Command::Skin(skin) => Message::SkinSelected(skin),
+            Command::ResetData => Message::ConfigResetData,
+            Command::ExportSettings => Message::ConfigExportSettings,
+            Command::ImportSettings => Message::ConfigImportSettings,
+        }
+    }
+}
+
+impl std::fmt::Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::View(view) => write!(f, "Go to {}", views::get(*view).title),
+            Command::Theme(theme) => write!(f, "Theme: {theme}"),
+            Command::Skin(skin) => write!(f, "Skin: {skin}"),
+            Command::ResetData => write!(f, "Reset all data"),
+            Command::ExportSettings => write!(f, "Export settings"),
+            Command::ImportSettings => write!(f, "Import settings"),
+        }
+    }
+}
+
+/// Query and highlighted entry of the command palette overlay
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    pub query: String,
+    /// Index into [`CommandPalette::matches`]
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    /// Open with an empty query
+    pub fn show(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.selected = 0;
+    }
+
+    /// Commands matching the query, best match first
+    pub fn matches(&self) -> Vec<Command> {
+        let mut scored: Vec<(i32, Command)> = Command::all()
+            .filter_map(|command| Some((fuzzy_score(&self.query, &command.to_string())?, command)))
+            .collect();
+        // Stable, so equal scores keep their listing order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, command)| command).collect()
+    }
+
+    /// Move the highlight by `offset` entries, wrapping around
+    pub fn move_selection(&mut self, offset: i32) {
+        let count = self.matches().len() as i32;
+        if count > 0 {
+            self.selected = (self.selected as i32 + offset).rem_euclid(count) as usize;
+        }
+    }
+
+    pub fn selected_command(&self) -> Option<Command> {
+        self.matches().get(self.selected).copied()
+    }
+}
+
+/// Arrow keys move the highlight while the palette is open
+pub fn arrow_key(event: iced::Event, _status: event::Status, _window: window::Id) -> Option<Message> {
+    let iced::Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) = event else {
+        return None;
+    };
+    match key {
+        Key::Named(key::Named::ArrowUp) => Some(Message::CommandPaletteMoved(-1)),
+        Key::Named(key::Named::ArrowDown) => Some(Message::CommandPaletteMoved(1)),
+        _ => None,
+    }
+}
+
+/// How well `query` matches `text` as a case-insensitive subsequence, higher
+/// being better; `None` if some query character is missing
+///
+/// Matches right after each other or at the start of a word score extra,
+/// and skipping over characters costs a little.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for c in query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let index = position + text[position..].iter().position(|t| *t == c)?;
+
+        score += 1;
+        if index > 0 && previous == Some(index - 1) {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (index - position).min(3) as i32;
+
+        previous = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::{chart, form};
+
+    #[test]
+    fn query_characters_must_appear_in_order() {
+        assert!(fuzzy_score("drc", "Theme: Dracula").is_some());
+        assert!(fuzzy_score("DRC", "Theme: Dracula").is_some());
+        assert!(fuzzy_score("cdr", "Theme: Dracula").is_none());
+        assert!(fuzzy_score("x", "Theme: Dracula").is_none());
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(fuzzy_score("", "Anything"), Some(0));
+        assert_eq!(fuzzy_score("  ", "Anything"), Some(0));
+    }
+
+    #[test]
+    fn word_starts_and_runs_score_higher() {
+        let run = fuzzy_score("nord", "Theme: Nord").unwrap();
+        let scattered = fuzzy_score("nord", "Neon roads").unwrap();
+        assert!(run > scattered);
+
+        // "tn" picks out Tokyo Night's word starts
+        let starts = fuzzy_score("tn", "Tokyo Night").unwrap();
+        let inner = fuzzy_score("tn", "Catppuccin").unwrap();
+        assert!(starts > inner);
+    }
+
+    #[test]
+    fn palette_lists_views_actions_and_every_theme() {
+        let palette = CommandPalette::default();
+        let commands = palette.matches();
+
+        assert_eq!(commands.len(), views::all().count() + 3 + ThemeType::ALL.len() + Skin::ALL.len());
+        assert_eq!(commands[0], Command::View(form::VIEW.id));
+        assert!(commands.contains(&Command::Theme(ThemeType::Ferra)));
+        assert!(commands.contains(&Command::Skin(Skin::PlainHtml)));
+    }
+
+    #[test]
+    fn best_match_comes_first() {
+        let mut palette = CommandPalette::default();
+        palette.set_query("gruv dark".to_string());
+
+        assert_eq!(palette.selected_command(), Some(Command::Theme(ThemeType::GruvboxDark)));
+
+        palette.set_query("export".to_string());
+        assert_eq!(palette.selected_command(), Some(Command::ExportSettings));
+    }
+
+    #[test]
+    fn selection_wraps_and_resets_on_typing() {
+        let mut palette = CommandPalette::default();
+        palette.set_query("go to".to_string());
+        assert_eq!(palette.matches().len(), views::all().count());
+
+        // Up from the first entry wraps to the last view
+        let last = views::all().last().unwrap().id;
+        palette.move_selection(-1);
+        assert_eq!(palette.selected_command(), Some(Command::View(last)));
+        palette.move_selection(1);
+        assert_eq!(palette.selected, 0);
+
+        palette.move_selection(2);
+        palette.set_query("go to c".to_string());
+        assert_eq!(palette.selected, 0);
+    }
+
+    #[test]
+    fn commands_dispatch_their_messages() {
+        assert!(matches!(
+            Command::Theme(ThemeType::Nord).message(),
+            Message::ThemeSelected(ThemeType::Nord)
+        ));
+        assert!(matches!(
+            Command::View(chart::VIEW.id).message(),
+            Message::ViewChanged(view) if view == chart::VIEW.id
+        ));
+        assert!(matches!(Command::ResetData.message(), Message::ConfigResetData));
+    }
+}
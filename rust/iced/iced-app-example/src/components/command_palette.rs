@@ -0,0 +1,53 @@
+use iced::widget::{button, column, container, scrollable, text, text_input};
+use iced::Element;
+
+use crate::command_palette::CommandPalette;
+use crate::components::modal::create_modal;
+use crate::message::Message;
+use crate::styles::skin::Skin;
+
+/// Search field of the palette, focused when it opens
+pub const INPUT_ID: &str = "command-palette-input";
+
+/// Result list, scrolled to keep the highlighted entry in view
+pub const RESULTS_ID: &str = "command-palette-results";
+
+/// Search field over a list of matching commands; Enter or a click runs one
+pub fn create_command_palette(palette: &CommandPalette, skin: Skin) -> Element<'_, Message> {
+    let matches = palette.matches();
+
+    let results: Element<'_, Message> = if matches.is_empty() {
+        text("No matching commands").size(13).into()
+    } else {
+        column(matches.into_iter().enumerate().map(|(index, command)| {
+            let is_selected = index == palette.selected;
+            button(text(command.to_string()).size(14))
+                .on_press(Message::CommandPaletteRun(command))
+                .padding([6, 10])
+                .width(iced::Fill)
+                .style(skin.list_item(is_selected))
+                .into()
+        }))
+        .spacing(2)
+        .into()
+    };
+
+    let dialog = container(
+        column![
+            text_input("Type a view, theme or action…", &palette.query)
+                .id(INPUT_ID)
+                .on_input(Message::CommandPaletteQueryChanged)
+                .on_submit(Message::CommandPaletteSubmitted)
+                .padding(8)
+                .style(skin.input()),
+            scrollable(results).id(RESULTS_ID).height(iced::Length::Shrink),
+        ]
+        .spacing(10),
+    )
+    .padding(12)
+    .width(420)
+    .max_height(420)
+    .style(skin.popup());
+
+    create_modal(dialog.into(), Message::CommandPaletteClosed)
+}
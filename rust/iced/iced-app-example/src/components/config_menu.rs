@@ -1,13 +1,18 @@
-use iced::widget::{button, column, container, mouse_area, text, Column};
-use iced::{Background, Border, Color, Element, Shadow};
-use std::time::Instant;
+use iced::widget::{button, checkbox, column, container, mouse_area, pick_list, slider, text};
+use iced::Element;
 
-use crate::message::Message;
-use crate::utils::animation::get_animation_progress;
+use crate::message::{Message, ViewTransition, WidgetAnimation};
+use crate::styles::skin::Skin;
+use crate::utils::animation::Curve;
 
 pub fn create_config_menu(
-    is_open: bool,
-    animation_start: Option<Instant>,
+    status: Option<String>,
+    autosave_enabled: bool,
+    transition: ViewTransition,
+    skin: Skin,
+    curves: [Curve; 3],
+    history_limit: usize,
+    debugger_open: bool,
 ) -> Element<'static, Message> {
     // Menu content with rounded corners and limited height
     let menu_content = column![
@@ -18,16 +23,7 @@ pub fn create_config_menu(
                 .wrapping(iced::widget::text::Wrapping::None) // No text wrapping
         )
         .padding(15)
-        .width(250) // Fixed width to prevent shrinking
-        .style(|_theme| {
-            container::Style {
-                background: None,
-                text_color: Some(Color::WHITE),
-                border: Border::default(),
-                shadow: Shadow::default(),
-                snap: false,
-            }
-        }),
+        .width(250), // Fixed width to prevent shrinking
         
         container(
             column![
@@ -42,7 +38,8 @@ pub fn create_config_menu(
                 )
                     .on_press(Message::ConfigResetData)
                     .padding(10)
-                    .width(iced::Fill),
+                    .width(iced::Fill)
+                    .style(skin.danger_button()),
                     
                 container(text("")).height(5),
                 
@@ -52,7 +49,8 @@ pub fn create_config_menu(
                 )
                     .on_press(Message::ConfigExportSettings)
                     .padding(10)
-                    .width(iced::Fill),
+                    .width(iced::Fill)
+                    .style(skin.button()),
                     
                 container(text("")).height(5),
                 
@@ -62,7 +60,56 @@ pub fn create_config_menu(
                 )
                     .on_press(Message::ConfigImportSettings)
                     .padding(10)
+                    .width(iced::Fill)
+                    .style(skin.button()),
+
+                container(text("")).height(5),
+
+                checkbox("Autosave state", autosave_enabled)
+                    .on_toggle(Message::ConfigAutosaveToggled),
+
+                text("View transition")
+                    .size(12)
+                    .wrapping(iced::widget::text::Wrapping::None),
+                pick_list(
+                    &ViewTransition::ALL[..],
+                    Some(transition),
+                    Message::TransitionSelected
+                )
+                .width(iced::Fill),
+
+                text("Skin")
+                    .size(12)
+                    .wrapping(iced::widget::text::Wrapping::None),
+                pick_list(&Skin::ALL[..], Some(skin), Message::SkinSelected)
                     .width(iced::Fill),
+
+                // Curve of each animation, in WidgetAnimation::ALL order
+                column(WidgetAnimation::ALL.into_iter().zip(curves).map(|(animation, curve)| {
+                    column![
+                        text(format!("{animation} curve"))
+                            .size(12)
+                            .wrapping(iced::widget::text::Wrapping::None),
+                        pick_list(Curve::all(), Some(curve), move |curve| {
+                            Message::CurveSelected(animation, curve)
+                        })
+                        .width(iced::Fill),
+                    ]
+                    .spacing(5)
+                    .into()
+                }))
+                .spacing(5),
+
+                text(format!("Undo history: {history_limit} steps"))
+                    .size(12)
+                    .wrapping(iced::widget::text::Wrapping::None),
+                slider(5..=200, history_limit.min(200) as u8, Message::HistoryLimitChanged),
+
+                checkbox("Time-travel debugger (F12)", debugger_open)
+                    .on_toggle(|_| Message::DebuggerToggled),
+
+                // Result of the last export/import, including errors
+                text(status.unwrap_or_default()).size(11),
                     
                 container(text("")).height(20),
                 
@@ -80,40 +127,15 @@ pub fn create_config_menu(
             .spacing(5)
         )
         .padding(15)
-        .width(250) // Fixed width to prevent shrinking
-        .style(|_theme| {
-            container::Style {
-                background: None,
-                text_color: Some(Color::from_rgb(0.9, 0.9, 0.9)),
-                border: Border::default(),
-                shadow: Shadow::default(),
-                snap: false,
-            }
-        }),
+        .width(250), // Fixed width to prevent shrinking
     ]
     .width(250);
     
     let menu_column = column![
         container(menu_content)
-            .style(move |_theme| {
-                container::Style {
-                    background: Some(Background::Color(Color::from_rgba(0.2, 0.2, 0.2, 0.75))),
-                    text_color: Some(Color::WHITE),
-                    border: Border {
-                        color: Color::from_rgba(0.3, 0.3, 0.3, 0.6),
-                        width: 1.0,
-                        radius: 8.0.into(), // Rounded corners
-                    },
-                    shadow: Shadow {
-                        color: Color::from_rgba(0.0, 0.0, 0.0, 0.5),
-                        offset: iced::Vector::new(4.0, 0.0),
-                        blur_radius: 10.0,
-                    },
-                    snap: false,
-                }
-            })
+            .style(skin.popup())
             .width(250)
-            .height(500) // Limited height, not full screen
+            .height(iced::Shrink) // Only as tall as its content, not full screen
     ];
     
     // Wrap with mouse_area to capture all mouse events and prevent click-through
@@ -123,17 +145,3 @@ pub fn create_config_menu(
         .on_move(|_| Message::ConfigMenuInteraction)
         .into()
 }
-
-pub fn get_menu_offset(is_open: bool, animation_start: Option<Instant>) -> f32 {
-    let progress = get_animation_progress(animation_start, 300.0);
-    
-    if is_open {
-        -250.0 * (1.0 - progress) // Slide in from left
-    } else {
-        if animation_start.is_some() {
-            -250.0 * progress // Slide out to left
-        } else {
-            -250.0 // Hidden by default
-        }
-    }
-}
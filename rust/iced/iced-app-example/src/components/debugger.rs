@@ -0,0 +1,84 @@
+use iced::widget::{button, column, container, opaque, row, scrollable, space, text};
+use iced::{Center, Element, Font};
+
+use crate::debugger::Debugger;
+use crate::message::Message;
+use crate::styles::skin::Skin;
+
+/// Developer overlay listing recent messages with the fields each changed
+pub fn create_debugger_overlay(debugger: &Debugger, skin: Skin) -> Element<'_, Message> {
+    let selected = debugger.selected();
+
+    let mut list = column![].spacing(2);
+    for (index, entry) in debugger.entries().enumerate() {
+        let is_selected = selected == Some(index);
+        let label = if entry.count > 1 {
+            format!("{:>3} {} ×{}", index + 1, entry.label, entry.count)
+        } else {
+            format!("{:>3} {}", index + 1, entry.label)
+        };
+
+        list = list.push(
+            button(text(label).size(12).font(Font::MONOSPACE))
+                .on_press(Message::DebuggerScrubbed(index))
+                .padding([2, 6])
+                .width(iced::Fill)
+                .style(skin.list_item(is_selected)),
+        );
+
+        // Diff of the selected message
+        if is_selected {
+            let changes = if entry.changes.is_empty() {
+                column![text("      no changes").size(11).font(Font::MONOSPACE)]
+            } else {
+                column(entry.changes.iter().map(|change| {
+                    text(format!("      {}: {} → {}", change.name, change.before, change.after))
+                        .size(11)
+                        .font(Font::MONOSPACE)
+                        .style(text::primary)
+                        .into()
+                }))
+            };
+            list = list.push(changes);
+        }
+    }
+
+    let header = row![
+        text(if debugger.is_paused() { "Time travel (paused)" } else { "Time travel" }).size(16),
+        space::horizontal(),
+        if debugger.is_paused() {
+            button(text("Resume").size(12))
+                .on_press(Message::DebuggerResume)
+                .padding([4, 10])
+                .style(skin.button())
+        } else {
+            button(text("Resume").size(12)).padding([4, 10]).style(skin.button())
+        },
+        button(text("✕").size(12))
+            .on_press(Message::DebuggerToggled)
+            .padding([4, 8])
+            .style(skin.secondary_button()),
+    ]
+    .spacing(8)
+    .align_y(Center);
+
+    let panel = container(
+        column![
+            header,
+            text("Click a message to restore the state right after it").size(11),
+            // Newest messages are at the bottom and stay in view
+            scrollable(list).anchor_bottom().height(iced::Fill),
+        ]
+        .spacing(8),
+    )
+    .padding(12)
+    .width(380)
+    .height(iced::Fill)
+    .style(skin.popup());
+
+    // Block events over the panel so they don't reach the app below
+    container(opaque(panel))
+        .padding(10)
+        .align_right(iced::Fill)
+        .into()
+}
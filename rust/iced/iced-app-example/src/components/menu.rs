@@ -1,74 +1,56 @@
-use iced::widget::{button, column, container, text, Column};
+use iced::widget::{button, column, container, row, text, Column};
 
-use crate::message::{Message, ViewMode};
-use crate::styles::button::windows_7_button_style;
+use crate::message::Message;
+use crate::router::Router;
+use crate::styles::skin::Skin;
+use crate::views::{self, ViewId};
 
-pub fn create_menu(current_view: ViewMode) -> Column<'static, Message> {
-    let form_button = button(
-        container(text("📝 Form").size(16))
-            .padding(10)
+pub fn create_menu(current_view: ViewId, router: &Router, skin: Skin) -> Column<'static, Message> {
+    // Disabled while there is nowhere to go
+    let history_buttons = row![
+        button(text("◀ Back").size(12))
+            .on_press_maybe(router.can_go_back().then_some(Message::NavigateBack))
             .width(iced::Fill)
-            .center_x(iced::Fill),
-    )
-    .width(iced::Fill)
-    .style(windows_7_button_style)
-    .on_press(Message::ViewChanged(ViewMode::Form));
-
-    let chart_button = button(
-        container(text("📊 Chart").size(16))
-            .padding(10)
+            .style(skin.secondary_button()),
+        button(text("Forward ▶").size(12))
+            .on_press_maybe(router.can_go_forward().then_some(Message::NavigateForward))
             .width(iced::Fill)
-            .center_x(iced::Fill),
-    )
-    .width(iced::Fill)
-    .style(windows_7_button_style)
-    .on_press(Message::ViewChanged(ViewMode::Chart));
+            .style(skin.secondary_button()),
+    ]
+    .spacing(5);
 
-    let summary_button = button(
-        container(text("📋 Summary").size(16))
+    let mut menu = column![
+        container(text("Navigation").size(18))
             .padding(10)
             .width(iced::Fill)
             .center_x(iced::Fill),
-    )
-    .width(iced::Fill)
-    .style(windows_7_button_style)
-    .on_press(Message::ViewChanged(ViewMode::Summary));
+        history_buttons,
+    ]
+    .spacing(5)
+    .padding(10)
+    .width(200);
 
-    let browser_form_button = button(
-        container(text("🌐 Browser").size(16))
-            .padding(10)
+    for view in views::all() {
+        menu = menu.push(
+            button(
+                container(text(format!("{} {}", view.icon, view.title)).size(16))
+                    .padding(10)
+                    .width(iced::Fill)
+                    .center_x(iced::Fill),
+            )
             .width(iced::Fill)
-            .center_x(iced::Fill),
-    )
-    .width(iced::Fill)
-    .style(windows_7_button_style)
-    .on_press(Message::ViewChanged(ViewMode::BrowserForm));
+            .style(skin.button())
+            .on_press(Message::ViewChanged(view.id)),
+        );
+    }
 
-    let current_view_text = match current_view {
-        ViewMode::Form => "Form",
-        ViewMode::Chart => "Chart",
-        ViewMode::Summary => "Summary",
-        ViewMode::BrowserForm => "Browser",
-    };
-
-    column![
-        container(text("Navigation").size(18))
-            .padding(10)
-            .width(iced::Fill)
-            .center_x(iced::Fill),
-        form_button,
-        chart_button,
-        summary_button,
-        browser_form_button,
+    menu.push(
         container(column![
             text("").size(10),
-            text(format!("Current: {}", current_view_text)).size(12),
+            text(format!("Current: {}", views::get(current_view).title)).size(12),
         ])
         .padding(10)
         .width(iced::Fill)
         .center_x(iced::Fill),
-    ]
-    .spacing(5)
-    .padding(10)
-    .width(200)
+    )
 }
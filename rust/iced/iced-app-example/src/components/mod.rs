@@ -1,3 +1,7 @@
 pub mod menu;
 pub mod animated_buttons;
+pub mod command_palette;
 pub mod config_menu;
+pub mod modal;
+pub mod snackbar;
+pub mod debugger;
@@ -0,0 +1,51 @@
+use iced::widget::{button, center, column, container, mouse_area, opaque, row, text};
+use iced::{Background, Border, Color, Element, Shadow};
+
+use crate::message::Message;
+use crate::styles::skin::Skin;
+
+/// Full-screen modal layer - dims everything below and blocks click-through
+pub fn create_modal<'a>(content: Element<'a, Message>, on_blur: Message) -> Element<'a, Message> {
+    opaque(
+        mouse_area(
+            center(opaque(content)).style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.6))),
+                border: Border::default(),
+                text_color: None,
+                shadow: Shadow::default(),
+                snap: false,
+            }),
+        )
+        .on_press(on_blur),
+    )
+}
+
+/// Confirmation dialog shown before wiping all user data
+pub fn create_reset_confirmation(skin: Skin) -> Element<'static, Message> {
+    let dialog = container(
+        column![
+            text("Reset All Data?").size(22),
+            text("All form and browser form values will be restored to their defaults.")
+                .size(14),
+            text("The current theme is kept. You can undo for a few seconds afterwards.")
+                .size(12),
+            row![
+                button("Cancel")
+                    .on_press(Message::ConfigResetCancel)
+                    .padding(8)
+                    .style(skin.secondary_button()),
+                button("Reset")
+                    .on_press(Message::ConfigResetConfirm)
+                    .padding(8)
+                    .style(skin.danger_button()),
+            ]
+            .spacing(10),
+        ]
+        .spacing(12),
+    )
+    .padding(20)
+    .width(360)
+    .style(skin.popup());
+
+    create_modal(dialog.into(), Message::ConfigResetCancel)
+}
@@ -0,0 +1,29 @@
+use iced::widget::{bottom_center, button, container, row, text};
+use iced::{Center, Element};
+
+use crate::message::Message;
+use crate::styles::skin::Skin;
+
+/// Small notification at the bottom of the window with a single action
+pub fn create_snackbar(
+    label: String,
+    action_label: &'static str,
+    action: Message,
+    skin: Skin,
+) -> Element<'static, Message> {
+    let bar = container(
+        row![
+            text(label).size(14),
+            button(text(action_label).size(14))
+                .on_press(action)
+                .padding([4, 10])
+                .style(skin.secondary_button()),
+        ]
+        .spacing(20)
+        .align_y(Center),
+    )
+    .padding([8, 16])
+    .style(skin.popup());
+
+    bottom_center(bar).padding(20).into()
+}
@@ -0,0 +1,521 @@
+use iced::Color;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::styles::color;
+
+/// Data file the Chart view offers to load until another path is entered
+pub const DATA_FILE: &str = "chart-data.csv";
+
+/// Color column entry meaning bars keep their series color
+pub const NO_COLUMN: &str = "(none)";
+
+/// Cells of a CSV or JSON file, before any column is given a meaning
+///
+/// Loaded tables go into the session log, so a replay shows the same data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Row>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Row {
+    /// Position in the file for error messages: the line for CSV, counting
+    /// the header, and the record for JSON
+    pub number: usize,
+    /// One per column; missing trailing cells are empty
+    pub cells: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ImportError {
+    /// Logged as its message, which is all a replay shows of it
+    #[serde(with = "logged_io_error")]
+    Io(Arc<std::io::Error>),
+    Parse(String),
+}
+
+mod logged_io_error {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<S: Serializer>(error: &Arc<std::io::Error>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(error)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<std::io::Error>, D::Error> {
+        Ok(Arc::new(std::io::Error::other(String::deserialize(deserializer)?)))
+    }
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Io(error) => write!(f, "cannot read data file: {error}"),
+            ImportError::Parse(reason) => write!(f, "malformed data file: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<std::io::Error> for ImportError {
+    fn from(error: std::io::Error) -> Self {
+        ImportError::Io(Arc::new(error))
+    }
+}
+
+/// Read and parse `path`, as JSON if it ends in `.json` and as CSV otherwise
+pub async fn load(path: PathBuf) -> Result<Table, ImportError> {
+    let contents = tokio::fs::read_to_string(&path).await?;
+    if is_json(&path) {
+        parse_json(&contents)
+    } else {
+        parse_csv(&contents)
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+/// Comma separated values with a header line
+///
+/// Fields may be quoted to hold commas, line breaks or `""` for a quote.
+/// Blank lines are skipped.
+pub fn parse_csv(contents: &str) -> Result<Table, ImportError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            '\n' if quoted => {
+                line += 1;
+                field.push(c);
+            }
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut record)));
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(ImportError::Parse(format!("unterminated quote on line {record_line}")));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+
+    // A blank line reads as a single empty field
+    records.retain(|(_, record)| !(record.len() == 1 && record[0].trim().is_empty()));
+
+    let mut records = records.into_iter();
+    let Some((_, header)) = records.next() else {
+        return Err(ImportError::Parse("no header line".to_string()));
+    };
+    let columns: Vec<String> = header.into_iter().map(|name| name.trim().to_string()).collect();
+    let rows = records
+        .map(|(number, mut cells)| {
+            cells.resize(columns.len().max(cells.len()), String::new());
+            Row { number, cells }
+        })
+        .collect();
+
+    Ok(Table { columns, rows })
+}
+
+/// An array of flat objects, one per row
+///
+/// Columns are the keys in order of first appearance; nested values are kept
+/// as JSON text.
+pub fn parse_json(contents: &str) -> Result<Table, ImportError> {
+    let value: serde_json::Value =
+        serde_json::from_str(contents).map_err(|error| ImportError::Parse(error.to_string()))?;
+    let Some(records) = value.as_array() else {
+        return Err(ImportError::Parse("expected an array of objects".to_string()));
+    };
+
+    let mut columns: Vec<String> = Vec::new();
+    for record in records {
+        let Some(record) = record.as_object() else {
+            return Err(ImportError::Parse("expected an array of objects".to_string()));
+        };
+        for key in record.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+
+    let rows = records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| Row {
+            number: index + 1,
+            cells: columns
+                .iter()
+                .map(|column| match record.get(column) {
+                    None | Some(serde_json::Value::Null) => String::new(),
+                    Some(serde_json::Value::String(text)) => text.clone(),
+                    Some(value) => value.to_string(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(Table { columns, rows })
+}
+
+/// Which columns hold each bar's label, value and optional color
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnMapping {
+    pub label: String,
+    pub value: String,
+    pub color: Option<String>,
+}
+
+impl ColumnMapping {
+    /// Best guess for `table`: the first column as label, the first mostly
+    /// numeric one after it as value, and a column named "color" if any
+    pub fn guess(table: &Table) -> Self {
+        let label = table.columns.first().cloned().unwrap_or_default();
+        let is_numeric = |index: usize| {
+            let numbers = table
+                .rows
+                .iter()
+                .filter(|row| parse_number(&row.cells[index]).is_some())
+                .count();
+            numbers * 2 > table.rows.len()
+        };
+        let value = (1..table.columns.len())
+            .find(|index| is_numeric(*index))
+            .or((table.columns.len() > 1).then_some(1))
+            .map_or_else(|| label.clone(), |index| table.columns[index].clone());
+        let color = table
+            .columns
+            .iter()
+            .find(|column| column.eq_ignore_ascii_case("color") || column.eq_ignore_ascii_case("colour"))
+            .cloned();
+
+        Self { label, value, color }
+    }
+
+    /// Whether every mapped column exists in `table`
+    pub fn fits(&self, table: &Table) -> bool {
+        [Some(&self.label), Some(&self.value), self.color.as_ref()]
+            .into_iter()
+            .flatten()
+            .all(|column| table.columns.contains(column))
+    }
+}
+
+/// A loaded data file and how its columns map onto the chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    pub path: PathBuf,
+    pub table: Table,
+    mapping: ColumnMapping,
+    /// Read again whenever the table or mapping changes
+    bars: Bars,
+}
+
+/// Bars read from a [`Dataset`], leaving out rows that could not be used
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bars {
+    pub labels: Vec<String>,
+    pub values: Vec<f32>,
+    /// Color of each bar, `None` for the series color
+    pub colors: Vec<Option<Color>>,
+    pub errors: Vec<RowError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    pub row: usize,
+    pub reason: String,
+}
+
+impl std::fmt::Display for RowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "row {}: {}", self.row, self.reason)
+    }
+}
+
+impl Dataset {
+    pub fn new(path: PathBuf, table: Table) -> Self {
+        let mapping = ColumnMapping::guess(&table);
+        let bars = read_bars(&table, &mapping);
+        Self {
+            path,
+            table,
+            mapping,
+            bars,
+        }
+    }
+
+    /// Swap in a freshly read `table`, keeping the mapping if it still fits
+    pub fn reload(&mut self, table: Table) {
+        if !self.mapping.fits(&table) {
+            self.mapping = ColumnMapping::guess(&table);
+        }
+        self.table = table;
+        self.bars = read_bars(&self.table, &self.mapping);
+    }
+
+    pub fn mapping(&self) -> &ColumnMapping {
+        &self.mapping
+    }
+
+    pub fn set_mapping(&mut self, mapping: ColumnMapping) {
+        if mapping != self.mapping {
+            self.mapping = mapping;
+            self.bars = read_bars(&self.table, &self.mapping);
+        }
+    }
+
+    pub fn bars(&self) -> &Bars {
+        &self.bars
+    }
+}
+
+fn read_bars(table: &Table, mapping: &ColumnMapping) -> Bars {
+    let column = |name: &str| table.columns.iter().position(|column| column == name);
+    let mut bars = Bars::default();
+    let (Some(label_column), Some(value_column)) =
+        (column(&mapping.label), column(&mapping.value))
+    else {
+        return bars;
+    };
+    let color_column = mapping.color.as_deref().and_then(column);
+
+    let mut errors = Vec::new();
+    for row in &table.rows {
+        let mut error = |reason: String| {
+            errors.push(RowError {
+                row: row.number,
+                reason,
+            })
+        };
+
+        let label = row.cells[label_column].trim();
+        let value = row.cells[value_column].trim();
+        if label.is_empty() {
+            error("missing label".to_string());
+            continue;
+        }
+        let Some(value) = parse_number(value) else {
+            error(if value.is_empty() {
+                "missing value".to_string()
+            } else {
+                format!("\"{value}\" is not a number")
+            });
+            continue;
+        };
+        if bars.labels.iter().any(|existing| existing == label) {
+            error(format!("duplicate label \"{label}\""));
+            continue;
+        }
+
+        // A bad color is reported but the bar is still shown
+        let color = color_column
+            .map(|column| row.cells[column].trim())
+            .filter(|color| !color.is_empty())
+            .and_then(|color| {
+                let parsed = color::parse_hex(color);
+                if parsed.is_none() {
+                    error(format!("\"{color}\" is not a color"));
+                }
+                parsed
+            });
+
+        bars.labels.push(label.to_string());
+        bars.values.push(value);
+        bars.colors.push(color);
+    }
+
+    bars.errors = errors;
+    bars
+}
+
+fn parse_number(text: &str) -> Option<f32> {
+    text.trim().parse::<f32>().ok().filter(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(columns: &[&str], rows: &[&[&str]]) -> Table {
+        Table {
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: rows
+                .iter()
+                .enumerate()
+                .map(|(index, cells)| Row {
+                    number: index + 2,
+                    cells: cells.iter().map(|cell| cell.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn csv_reads_header_and_rows() {
+        let parsed = parse_csv("name, value\r\nA,1\r\n\r\nB,2.5\r\n").unwrap();
+
+        assert_eq!(parsed.columns, ["name", "value"]);
+        assert_eq!(parsed.rows.len(), 2);
+        assert_eq!(parsed.rows[0].cells, ["A", "1"]);
+        assert_eq!(parsed.rows[1].cells, ["B", "2.5"]);
+        // Line numbers count the header and the blank line
+        assert_eq!(parsed.rows[1].number, 4);
+    }
+
+    #[test]
+    fn csv_quotes_hold_commas_quotes_and_line_breaks() {
+        let parsed = parse_csv("label,value\n\"Smith, \"\"J\"\"\",3\n\"two\nlines\",4\nlast,5").unwrap();
+
+        assert_eq!(parsed.rows[0].cells, ["Smith, \"J\"", "3"]);
+        assert_eq!(parsed.rows[1].cells, ["two\nlines", "4"]);
+        // The quoted line break moves later rows down a line
+        assert_eq!(parsed.rows[2].number, 5);
+    }
+
+    #[test]
+    fn csv_short_rows_are_padded() {
+        let parsed = parse_csv("a,b,c\n1\n").unwrap();
+
+        assert_eq!(parsed.rows[0].cells, ["1", "", ""]);
+    }
+
+    #[test]
+    fn csv_errors() {
+        assert!(matches!(parse_csv(""), Err(ImportError::Parse(_))));
+        assert!(matches!(parse_csv("a,b\n\"open,1\n"), Err(ImportError::Parse(_))));
+    }
+
+    #[test]
+    fn json_reads_objects_in_key_order() {
+        let parsed = parse_json(r#"[{"label": "A", "value": 3}, {"value": 4.5, "label": "B", "color": null, "extra": true}]"#)
+            .unwrap();
+
+        assert_eq!(parsed.columns, ["label", "value", "color", "extra"]);
+        assert_eq!(parsed.rows[0].cells, ["A", "3", "", ""]);
+        assert_eq!(parsed.rows[1].cells, ["B", "4.5", "", "true"]);
+        assert_eq!(parsed.rows[1].number, 2);
+    }
+
+    #[test]
+    fn json_columns_keep_document_order() {
+        let parsed = parse_json(r#"[{"name": "x", "count": 3}]"#).unwrap();
+        assert_eq!(parsed.columns, ["name", "count"]);
+
+        let dataset = Dataset::new(PathBuf::from("data.json"), parsed);
+        assert_eq!(dataset.mapping().label, "name");
+        assert_eq!(dataset.mapping().value, "count");
+        assert_eq!(dataset.bars().values, [3.0]);
+        assert!(dataset.bars().errors.is_empty());
+    }
+
+    #[test]
+    fn json_must_be_an_array_of_objects() {
+        assert!(matches!(parse_json(r#"{"label": "A"}"#), Err(ImportError::Parse(_))));
+        assert!(matches!(parse_json("[1, 2]"), Err(ImportError::Parse(_))));
+        assert!(matches!(parse_json("[{"), Err(ImportError::Parse(_))));
+    }
+
+    #[test]
+    fn mapping_guess_finds_label_value_and_color() {
+        let data = table(
+            &["Site", "Note", "Reading", "Color"],
+            &[&["North", "ok", "12", "#f00"], &["South", "", "7.5", ""]],
+        );
+
+        assert_eq!(
+            ColumnMapping::guess(&data),
+            ColumnMapping {
+                label: "Site".to_string(),
+                value: "Reading".to_string(),
+                color: Some("Color".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn bars_skip_and_report_bad_rows() {
+        let dataset = Dataset::new(
+            PathBuf::from("data.csv"),
+            table(
+                &["label", "value", "color"],
+                &[
+                    &["A", "1", "#ff0000"],
+                    &["", "2", ""],
+                    &["B", "lots", ""],
+                    &["C", "", ""],
+                    &["A", "5", ""],
+                    &["D", "-3", "blue"],
+                ],
+            ),
+        );
+        let bars = dataset.bars();
+
+        assert_eq!(bars.labels, ["A", "D"]);
+        assert_eq!(bars.values, [1.0, -3.0]);
+        assert_eq!(bars.colors, [Some(Color::from_rgb(1.0, 0.0, 0.0)), None]);
+        let errors: Vec<String> = bars.errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            [
+                "row 3: missing label",
+                "row 4: \"lots\" is not a number",
+                "row 5: missing value",
+                "row 6: duplicate label \"A\"",
+                "row 7: \"blue\" is not a color",
+            ]
+        );
+    }
+
+    #[test]
+    fn reload_keeps_a_mapping_that_still_fits() {
+        let mut dataset = Dataset::new(
+            PathBuf::from("data.csv"),
+            table(&["name", "a", "b"], &[&["x", "1", "2"]]),
+        );
+        dataset.set_mapping(ColumnMapping {
+            value: "b".to_string(),
+            ..dataset.mapping().clone()
+        });
+        assert_eq!(dataset.bars().values, [2.0]);
+
+        dataset.reload(table(&["name", "b", "a"], &[&["x", "3", "4"]]));
+        assert_eq!(dataset.mapping().value, "b");
+        assert_eq!(dataset.bars().values, [3.0]);
+
+        dataset.reload(table(&["id", "count"], &[&["x", "3"]]));
+        assert_eq!(dataset.mapping().label, "id");
+        assert_eq!(dataset.mapping().value, "count");
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::app::App;
+use crate::history::Snapshot;
+use crate::message::{Message, ViewTransition};
+use crate::styles::skin::Skin;
+use crate::utils::animation::Animation;
+use crate::views::ViewId;
+
+/// Number of messages kept by the time-travel debugger
+pub const DEBUGGER_LIMIT: usize = 200;
+
+/// Longest message label shown in the overlay
+const LABEL_LENGTH: usize = 48;
+
+/// Everything the debugger can show and restore: the user data plus the
+/// view and animation flags that interact with it
+#[derive(Debug, Clone)]
+pub struct Frame {
+    user: Snapshot,
+    current_view: ViewId,
+    previous_view: ViewId,
+    view_transition: Animation,
+    transition_style: ViewTransition,
+    skin: Skin,
+    second_button_visible: bool,
+    second_button_animation: Animation,
+    config_menu_open: bool,
+    config_menu_animation: Animation,
+    reset_confirm_open: bool,
+    autosave_enabled: bool,
+    /// Animation clock, so a restored frame renders exactly as it did
+    now: Instant,
+}
+
+impl Frame {
+    pub fn from_app(app: &App) -> Self {
+        Self {
+            user: Snapshot::from_app(app),
+            current_view: app.current_view,
+            previous_view: app.previous_view,
+            view_transition: app.view_transition.clone(),
+            transition_style: app.transition_style,
+            skin: app.skin,
+            second_button_visible: app.second_button_visible,
+            second_button_animation: app.second_button_animation.clone(),
+            config_menu_open: app.config_menu_open,
+            config_menu_animation: app.config_menu_animation.clone(),
+            reset_confirm_open: app.reset_confirm_open,
+            autosave_enabled: app.autosave_enabled,
+            now: app.now,
+        }
+    }
+
+    pub fn apply(self, app: &mut App) {
+        self.user.apply(app);
+        app.current_view = self.current_view;
+        app.previous_view = self.previous_view;
+        app.view_transition = self.view_transition;
+        app.transition_style = self.transition_style;
+        app.skin = self.skin;
+        app.second_button_visible = self.second_button_visible;
+        app.second_button_animation = self.second_button_animation;
+        app.config_menu_open = self.config_menu_open;
+        app.config_menu_animation = self.config_menu_animation;
+        app.reset_confirm_open = self.reset_confirm_open;
+        app.autosave_enabled = self.autosave_enabled;
+        app.now = self.now;
+    }
+
+    /// Field names with their displayed values
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = self.user.fields();
+        fields.extend([
+            ("current_view", format!("{:?}", self.current_view)),
+            ("previous_view", format!("{:?}", self.previous_view)),
+            ("view_transition", animation_state(&self.view_transition, self.now)),
+            ("transition_style", self.transition_style.to_string()),
+            ("skin", self.skin.to_string()),
+            ("second_button_visible", self.second_button_visible.to_string()),
+            (
+                "second_button_animation",
+                animation_state(&self.second_button_animation, self.now),
+            ),
+            ("config_menu_open", self.config_menu_open.to_string()),
+            (
+                "config_menu_animation",
+                animation_state(&self.config_menu_animation, self.now),
+            ),
+            ("reset_confirm_open", self.reset_confirm_open.to_string()),
+            ("autosave_enabled", self.autosave_enabled.to_string()),
+        ]);
+        fields
+    }
+}
+
+/// Eased progress, marked while the animation is still running
+fn animation_state(animation: &Animation, now: Instant) -> String {
+    let progress = animation.progress(now);
+    if animation.is_active() {
+        format!("{progress:.2} ▶")
+    } else {
+        format!("{progress:.2}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldChange {
+    pub name: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+/// One handled message and what it did to the app
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub label: String,
+    /// Consecutive ticks are folded into one entry
+    pub count: usize,
+    pub changes: Vec<FieldChange>,
+    is_tick: bool,
+    /// State right after the message, restored when scrubbing here
+    state: Frame,
+}
+
+/// Bounded log of recent messages that can be scrubbed through, kept
+/// while the overlay is open
+#[derive(Debug, Default)]
+pub struct Debugger {
+    pub open: bool,
+    entries: VecDeque<Entry>,
+    /// Entry currently restored; `None` while running live
+    cursor: Option<usize>,
+}
+
+impl Debugger {
+    /// Whether `message` shows up in the log at all
+    pub fn observes(message: &Message) -> bool {
+        !matches!(
+            message,
+            Message::DebuggerToggled
+                | Message::DebuggerScrubbed(_)
+                | Message::DebuggerResume
+                | Message::ReplayTick(_)
+                | Message::ConfigMenuInteraction
+        )
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    /// Entry whose changes are shown - the scrubbed one, or the latest
+    pub fn selected(&self) -> Option<usize> {
+        self.cursor.or(self.entries.len().checked_sub(1))
+    }
+
+    /// Scrubbed back in time, with live updates frozen
+    pub fn is_paused(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    /// Log `message`, which took the app from `before` to `after`
+    pub fn log(&mut self, message: &Message, before: Frame, after: Frame) {
+        // Anything happening after a scrub continues from that point
+        self.resume();
+
+        let changes = diff(before.fields(), after.fields());
+        let is_tick = matches!(message, Message::Tick(_));
+
+        if is_tick {
+            // Idle ticks only poll timers - not worth a line each
+            if changes.is_empty() {
+                return;
+            }
+            if let Some(last) = self.entries.back_mut().filter(|entry| entry.is_tick) {
+                merge(&mut last.changes, changes);
+                last.count += 1;
+                last.state = after;
+                return;
+            }
+        }
+
+        self.entries.push_back(Entry {
+            label: label(message),
+            count: 1,
+            changes,
+            is_tick,
+            state: after,
+        });
+        while self.entries.len() > DEBUGGER_LIMIT {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Jump to the state right after entry `index`, returning it to apply
+    pub fn scrub(&mut self, index: usize) -> Option<Frame> {
+        let entry = self.entries.get(index)?;
+        self.cursor = Some(index);
+        Some(entry.state.clone())
+    }
+
+    /// Continue live from the scrubbed state, dropping the later entries
+    pub fn resume(&mut self) {
+        if let Some(cursor) = self.cursor.take() {
+            self.entries.truncate(cursor + 1);
+        }
+    }
+}
+
+fn label(message: &Message) -> String {
+    match message {
+        // Tick timestamps are noise in a list
+        Message::Tick(_) => return "Tick".to_string(),
+        Message::BrowserPasswordChanged(_) => return "BrowserPasswordChanged(…)".to_string(),
+        _ => {}
+    }
+
+    let label = format!("{message:?}");
+    if label.chars().count() > LABEL_LENGTH {
+        label.chars().take(LABEL_LENGTH - 1).chain(['…']).collect()
+    } else {
+        label
+    }
+}
+
+fn diff(before: Vec<(&'static str, String)>, after: Vec<(&'static str, String)>) -> Vec<FieldChange> {
+    before
+        .into_iter()
+        .zip(after)
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((name, before), (_, after))| FieldChange {
+            name,
+            before,
+            after,
+        })
+        .collect()
+}
+
+/// Fold `newer` changes into `changes`, keeping the oldest `before` values
+fn merge(changes: &mut Vec<FieldChange>, newer: Vec<FieldChange>) {
+    for change in newer {
+        match changes.iter_mut().find(|existing| existing.name == change.name) {
+            Some(existing) => existing.after = change.after,
+            None => changes.push(change),
+        }
+    }
+    changes.retain(|change| change.before != change.after);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frame of a default app with the counter at `value`
+    fn frame(value: i64) -> Frame {
+        Frame::from_app(&App {
+            value,
+            ..App::default()
+        })
+    }
+
+    fn tick() -> Message {
+        Message::Tick(Instant::now())
+    }
+
+    #[test]
+    fn log_records_what_each_message_changed() {
+        let mut debugger = Debugger::default();
+        debugger.log(&Message::Increment, frame(0), frame(1));
+        debugger.log(&Message::NameChanged("x".repeat(100)), frame(1), frame(1));
+
+        let entries: Vec<&Entry> = debugger.entries().collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].label, "Increment");
+        assert_eq!(entries[0].changes.len(), 1);
+        assert_eq!(entries[0].changes[0].name, "value");
+        assert_eq!((entries[0].changes[0].before.as_str(), entries[0].changes[0].after.as_str()), ("0", "1"));
+
+        // Messages that changed nothing are still listed, with long labels cut
+        assert!(entries[1].changes.is_empty());
+        assert_eq!(entries[1].label.chars().count(), LABEL_LENGTH);
+        assert!(entries[1].label.ends_with('…'));
+        assert_eq!(debugger.selected(), Some(1));
+    }
+
+    #[test]
+    fn consecutive_ticks_merge_and_idle_ones_are_dropped() {
+        let mut debugger = Debugger::default();
+        debugger.log(&tick(), frame(0), frame(0));
+        assert_eq!(debugger.entries().count(), 0);
+
+        debugger.log(&tick(), frame(0), frame(1));
+        debugger.log(&tick(), frame(1), frame(2));
+        let merged = debugger.entries().next().unwrap();
+        assert_eq!(merged.count, 2);
+        assert_eq!((merged.changes[0].before.as_str(), merged.changes[0].after.as_str()), ("0", "2"));
+
+        // Changes that cancel out leave nothing behind
+        debugger.log(&tick(), frame(2), frame(0));
+        assert!(debugger.entries().next().unwrap().changes.is_empty());
+
+        // Any other message ends the run of ticks
+        debugger.log(&Message::Increment, frame(0), frame(1));
+        debugger.log(&tick(), frame(1), frame(2));
+        assert_eq!(debugger.entries().count(), 3);
+    }
+
+    #[test]
+    fn log_keeps_the_latest_entries() {
+        let mut debugger = Debugger::default();
+        for value in 0..DEBUGGER_LIMIT as i64 + 10 {
+            debugger.log(&Message::Increment, frame(value), frame(value + 1));
+        }
+
+        assert_eq!(debugger.entries().count(), DEBUGGER_LIMIT);
+        let oldest = debugger.entries().next().unwrap();
+        assert_eq!(oldest.changes[0].before, "10");
+    }
+
+    #[test]
+    fn scrubbing_restores_and_resuming_drops_the_later_entries() {
+        let mut debugger = Debugger::default();
+        for value in 0..5 {
+            debugger.log(&Message::Increment, frame(value), frame(value + 1));
+        }
+
+        let mut app = App::default();
+        debugger.scrub(1).unwrap().apply(&mut app);
+        assert_eq!(app.value, 2);
+        assert!(debugger.is_paused());
+        assert_eq!(debugger.selected(), Some(1));
+        assert!(debugger.scrub(5).is_none());
+
+        // Scrubbing back and forth keeps the whole log
+        debugger.scrub(3).unwrap().apply(&mut app);
+        assert_eq!(app.value, 4);
+        assert_eq!(debugger.entries().count(), 5);
+
+        // New messages continue from the scrubbed state
+        debugger.log(&Message::Decrement, frame(4), frame(3));
+        assert!(!debugger.is_paused());
+        assert_eq!(debugger.entries().count(), 5);
+        assert_eq!(debugger.entries().last().unwrap().label, "Decrement");
+
+        debugger.scrub(0);
+        debugger.resume();
+        assert_eq!(debugger.entries().count(), 1);
+        assert_eq!(debugger.selected(), Some(0));
+    }
+
+    #[test]
+    fn app_only_records_while_the_overlay_is_open() {
+        let mut app = App::default();
+        let _ = app.update(Message::Increment);
+        assert_eq!(app.debugger.entries().count(), 0);
+
+        let _ = app.update(Message::DebuggerToggled);
+        let _ = app.update(Message::Increment);
+        assert_eq!(app.debugger.entries().count(), 1);
+    }
+}
@@ -0,0 +1,335 @@
+use iced::Theme;
+use std::collections::VecDeque;
+use std::mem::Discriminant;
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::message::{BrowserOption, Message, Role};
+
+/// Default number of undo steps kept
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// Continuous edits closer together than this merge into one undo step
+const COALESCE_WINDOW: Duration = Duration::from_millis(1000);
+
+/// How a message participates in the undo history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoKind {
+    /// Visual or navigation only - never recorded
+    Skip,
+    /// Each message is its own undo step
+    Single,
+    /// Typing and slider drags - consecutive messages merge into one step
+    Continuous,
+}
+
+pub fn undo_kind(message: &Message) -> UndoKind {
+    match message {
+        Message::NameChanged(_)
+        | Message::EmailChanged(_)
+        | Message::AgeChanged(_)
+        | Message::ExperienceChanged(_)
+        | Message::BrowserTextChanged(_)
+        | Message::BrowserPasswordChanged(_)
+        | Message::BrowserSliderChanged(_) => UndoKind::Continuous,
+        Message::Increment
+        | Message::Decrement
+        | Message::RoleSelected(_)
+        | Message::ThemeSelected(_)
+        | Message::CustomThemeSelected(_)
+        | Message::BrowserCheckboxToggled(_)
+        | Message::BrowserOptionSelected(_)
+        | Message::ConfigResetConfirm
+        | Message::ConfigResetUndo
+        | Message::ConfigImportSettings => UndoKind::Single,
+        _ => UndoKind::Skip,
+    }
+}
+
+/// Everything the user can edit in the Form and Browser views
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    value: i64,
+    name: String,
+    email: String,
+    age: u8,
+    experience: f32,
+    selected_role: Option<Role>,
+    selected_theme: Theme,
+    browser_text: String,
+    browser_password: String,
+    browser_slider_value: f32,
+    browser_checkbox: bool,
+    browser_selected_option: Option<BrowserOption>,
+}
+
+impl Snapshot {
+    pub fn from_app(app: &App) -> Self {
+        Self {
+            value: app.value,
+            name: app.name.clone(),
+            email: app.email.clone(),
+            age: app.age,
+            experience: app.experience,
+            selected_role: app.selected_role,
+            selected_theme: app.selected_theme.clone(),
+            browser_text: app.browser_text.clone(),
+            browser_password: app.browser_password.clone(),
+            browser_slider_value: app.browser_slider_value,
+            browser_checkbox: app.browser_checkbox,
+            browser_selected_option: app.browser_selected_option,
+        }
+    }
+
+    pub fn apply(self, app: &mut App) {
+        app.value = self.value;
+        app.name = self.name;
+        app.email = self.email;
+        app.age = self.age;
+        app.experience = self.experience;
+        app.selected_role = self.selected_role;
+        app.selected_theme = self.selected_theme;
+        app.browser_text = self.browser_text;
+        app.browser_password = self.browser_password;
+        app.browser_slider_value = self.browser_slider_value;
+        app.browser_checkbox = self.browser_checkbox;
+        app.browser_selected_option = self.browser_selected_option;
+    }
+
+    /// Field names with their displayed values, for the debugger's diffs
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("value", self.value.to_string()),
+            ("name", format!("{:?}", self.name)),
+            ("email", format!("{:?}", self.email)),
+            ("age", self.age.to_string()),
+            ("experience", self.experience.to_string()),
+            ("selected_role", format!("{:?}", self.selected_role)),
+            ("selected_theme", self.selected_theme.to_string()),
+            ("browser_text", format!("{:?}", self.browser_text)),
+            // Only the length of the password ever reaches the screen
+            ("browser_password", "•".repeat(self.browser_password.chars().count())),
+            ("browser_slider_value", self.browser_slider_value.to_string()),
+            ("browser_checkbox", self.browser_checkbox.to_string()),
+            ("browser_selected_option", format!("{:?}", self.browser_selected_option)),
+        ]
+    }
+}
+
+struct Entry<T> {
+    state: T,
+    /// Message that produced the change, for coalescing continuous edits
+    source: Option<Discriminant<Message>>,
+    changed_at: Instant,
+}
+
+/// Bounded undo/redo stacks of states before each change
+pub struct History<T> {
+    undo: VecDeque<Entry<T>>,
+    redo: Vec<T>,
+    limit: usize,
+}
+
+impl<T> History<T> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit: limit.max(1),
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit.max(1);
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+        // The redo stack's top is the next step, so drop from the bottom
+        let excess = self.redo.len().saturating_sub(self.limit);
+        self.redo.drain(..excess);
+    }
+
+    /// Record `before`, the state prior to a change caused by `message`
+    pub fn record(&mut self, before: T, message: &Message, now: Instant) {
+        let kind = undo_kind(message);
+        if kind == UndoKind::Skip {
+            return;
+        }
+
+        self.redo.clear();
+
+        let source = (kind == UndoKind::Continuous).then(|| std::mem::discriminant(message));
+
+        // Keep the older state of a running text edit or slider drag
+        if let Some(last) = self.undo.back_mut()
+            && source.is_some()
+            && last.source == source
+            && now.saturating_duration_since(last.changed_at) < COALESCE_WINDOW
+        {
+            last.changed_at = now;
+            return;
+        }
+
+        self.push(Entry {
+            state: before,
+            source,
+            changed_at: now,
+        });
+    }
+
+    /// Step back, returning the state to restore
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let entry = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(entry.state)
+    }
+
+    /// Step forward again, returning the state to restore
+    pub fn redo(&mut self, current: T, now: Instant) -> Option<T> {
+        let state = self.redo.pop()?;
+        self.push(Entry {
+            state: current,
+            source: None,
+            changed_at: now,
+        });
+        Some(state)
+    }
+
+    fn push(&mut self, entry: Entry<T>) {
+        self.undo.push_back(entry);
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> Message {
+        Message::NameChanged(text.to_string())
+    }
+
+    #[test]
+    fn record_keeps_the_state_before_each_step() {
+        let mut history = History::new(10);
+        let now = Instant::now();
+
+        history.record(0, &Message::Increment, now);
+        history.record(1, &Message::Increment, now);
+        history.record(2, &Message::DebuggerToggled, now);
+
+        assert_eq!(history.undo(2), Some(1));
+        assert_eq!(history.undo(1), Some(0));
+        assert_eq!(history.undo(0), None);
+
+        assert_eq!(history.redo(0, now), Some(1));
+        assert_eq!(history.redo(1, now), Some(2));
+        assert_eq!(history.redo(2, now), None);
+    }
+
+    #[test]
+    fn recording_drops_the_redo_steps() {
+        let mut history = History::new(10);
+        let now = Instant::now();
+
+        history.record(0, &Message::Increment, now);
+        assert_eq!(history.undo(1), Some(0));
+        history.record(0, &Message::Decrement, now);
+
+        assert_eq!(history.redo(-1, now), None);
+    }
+
+    #[test]
+    fn continuous_edits_within_the_window_coalesce() {
+        let mut history = History::new(10);
+        let start = Instant::now();
+        let step = COALESCE_WINDOW / 2;
+
+        // Each keystroke extends the window from the previous one
+        history.record("", &typed("a"), start);
+        history.record("a", &typed("ab"), start + step);
+        history.record("ab", &typed("abc"), start + step * 2);
+        assert_eq!(history.undo("abc"), Some(""));
+        assert_eq!(history.undo(""), None);
+    }
+
+    #[test]
+    fn edits_split_on_pauses_and_other_messages() {
+        let mut history = History::new(10);
+        let start = Instant::now();
+
+        history.record("", &typed("a"), start);
+        history.record("a", &typed("ab"), start + COALESCE_WINDOW);
+        assert_eq!(history.undo("ab"), Some("a"));
+
+        let mut history = History::new(10);
+        history.record("", &typed("a"), start);
+        history.record("a", &Message::EmailChanged("b".to_string()), start);
+        assert_eq!(history.undo("ab"), Some("a"));
+
+        // Single steps never merge, even with themselves
+        let mut history = History::new(10);
+        history.record(0, &Message::Increment, start);
+        history.record(1, &Message::Increment, start);
+        assert_eq!(history.undo(2), Some(1));
+    }
+
+    #[test]
+    fn limit_drops_the_oldest_steps() {
+        let mut history = History::new(3);
+        let now = Instant::now();
+        for value in 0..5 {
+            history.record(value, &Message::Increment, now);
+        }
+
+        assert_eq!(history.undo(5), Some(4));
+        assert_eq!(history.undo(4), Some(3));
+        assert_eq!(history.undo(3), Some(2));
+        assert_eq!(history.undo(2), None);
+    }
+
+    #[test]
+    fn lowering_the_limit_trims_both_stacks() {
+        let mut history = History::new(10);
+        let now = Instant::now();
+        for value in 0..6 {
+            history.record(value, &Message::Increment, now);
+        }
+        for value in (3..6).rev() {
+            history.undo(value + 1);
+        }
+
+        history.set_limit(2);
+        assert_eq!(history.limit(), 2);
+        assert_eq!(history.redo(3, now), Some(4));
+        assert_eq!(history.redo(4, now), Some(5));
+        assert_eq!(history.redo(5, now), None);
+
+        assert_eq!(history.undo(5), Some(4));
+        assert_eq!(history.undo(4), Some(3));
+        assert_eq!(history.undo(3), None);
+
+        history.set_limit(0);
+        assert_eq!(history.limit(), 1);
+    }
+
+    #[test]
+    fn lowering_the_limit_keeps_the_newest_undo_steps() {
+        let mut history = History::new(10);
+        let now = Instant::now();
+        for value in 0..3 {
+            history.record(value, &Message::Increment, now);
+        }
+
+        history.set_limit(2);
+        assert_eq!(history.undo(3), Some(2));
+        assert_eq!(history.undo(2), Some(1));
+        assert_eq!(history.undo(1), None);
+    }
+}
@@ -0,0 +1,561 @@
+use iced::keyboard::{self, key, Key, Modifiers};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::persistence;
+use crate::views::{self, ViewId};
+
+const KEYMAP_FILE: &str = "keymap.json";
+
+/// Something a keyboard shortcut can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    /// Switch to one of the registered views
+    ShowView(ViewId),
+    Back,
+    Forward,
+    CommandPalette,
+    /// Cancel an open dialog or the command palette, or else close the
+    /// config menu
+    Close,
+    OpenSettings,
+    ExportSettings,
+    Undo,
+    Redo,
+    ToggleDebugger,
+}
+
+impl Action {
+    /// Actions that are not about a particular view
+    const GENERAL: [Action; 9] = [
+        Action::Back,
+        Action::Forward,
+        Action::CommandPalette,
+        Action::Close,
+        Action::OpenSettings,
+        Action::ExportSettings,
+        Action::Undo,
+        Action::Redo,
+        Action::ToggleDebugger,
+    ];
+
+    /// Actions in the order conflicts are resolved: earlier ones keep their
+    /// shortcut. Every registered view comes first, in menu order.
+    pub fn all() -> impl Iterator<Item = Action> {
+        views::all()
+            .map(|view| Action::ShowView(view.id))
+            .chain(Action::GENERAL)
+    }
+
+    /// How the keymap file names the action, like `ShowBrowserForm`
+    pub fn name(self) -> String {
+        match self {
+            Action::ShowView(view) => format!("Show{}", views::get(view).title.replace(' ', "")),
+            action => format!("{action:?}"),
+        }
+    }
+
+    /// Bindings used unless the keymap file replaces them
+    fn default_shortcuts(self) -> Vec<String> {
+        let shortcuts: &[&str] = match self {
+            // Mod+1 to Mod+9 go through the views in menu order
+            Action::ShowView(view) => {
+                return views::all()
+                    .position(|registered| registered.id == view)
+                    .filter(|index| *index < 9)
+                    .map(|index| format!("Mod+{}", index + 1))
+                    .into_iter()
+                    .collect();
+            }
+            Action::Back => &["Alt+Left"],
+            Action::Forward => &["Alt+Right"],
+            Action::CommandPalette => &["Mod+P"],
+            Action::Close => &["Esc"],
+            Action::OpenSettings => &["Mod+,"],
+            Action::ExportSettings => &["Mod+S"],
+            Action::Undo => &["Mod+Z"],
+            Action::Redo => &["Mod+Shift+Z", "Mod+Y"],
+            Action::ToggleDebugger => &["F12"],
+        };
+        shortcuts.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Action::ShowView(view) => {
+                    return write!(f, "Show {}", views::get(*view).title.to_lowercase());
+                }
+                Action::Back => "Back",
+                Action::Forward => "Forward",
+                Action::CommandPalette => "Command palette",
+                Action::Close => "Close dialog or menu",
+                Action::OpenSettings => "Open settings",
+                Action::ExportSettings => "Export settings",
+                Action::Undo => "Undo",
+                Action::Redo => "Redo",
+                Action::ToggleDebugger => "Toggle debugger",
+            }
+        )
+    }
+}
+
+/// Key names accepted in the keymap file; the first spelling of each key is
+/// the one displayed
+const NAMED_KEYS: [(&str, key::Named); 28] = [
+    ("Esc", key::Named::Escape),
+    ("Escape", key::Named::Escape),
+    ("Enter", key::Named::Enter),
+    ("Return", key::Named::Enter),
+    ("Tab", key::Named::Tab),
+    ("Space", key::Named::Space),
+    ("Backspace", key::Named::Backspace),
+    ("Delete", key::Named::Delete),
+    ("Home", key::Named::Home),
+    ("End", key::Named::End),
+    ("PageUp", key::Named::PageUp),
+    ("PageDown", key::Named::PageDown),
+    ("Up", key::Named::ArrowUp),
+    ("Down", key::Named::ArrowDown),
+    ("Left", key::Named::ArrowLeft),
+    ("Right", key::Named::ArrowRight),
+    ("F1", key::Named::F1),
+    ("F2", key::Named::F2),
+    ("F3", key::Named::F3),
+    ("F4", key::Named::F4),
+    ("F5", key::Named::F5),
+    ("F6", key::Named::F6),
+    ("F7", key::Named::F7),
+    ("F8", key::Named::F8),
+    ("F9", key::Named::F9),
+    ("F10", key::Named::F10),
+    ("F11", key::Named::F11),
+    ("F12", key::Named::F12),
+];
+
+/// A key together with the exact modifiers held down, like `Ctrl+Shift+Z`
+///
+/// `Mod` stands for the platform's command key: Cmd on macOS, Ctrl elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    /// Characters are kept in lowercase
+    key: Key,
+    modifiers: Modifiers,
+}
+
+impl Shortcut {
+    pub fn matches(&self, key: &Key, modifiers: Modifiers) -> bool {
+        modifiers == self.modifiers && normalize(key) == self.key
+    }
+}
+
+/// Shifted letters arrive in uppercase; shortcuts compare them in lowercase
+fn normalize(key: &Key) -> Key {
+    match key.as_ref() {
+        Key::Character(c) => Key::Character(c.to_lowercase().into()),
+        _ => key.clone(),
+    }
+}
+
+impl std::str::FromStr for Shortcut {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        // A trailing "++" binds the plus key itself
+        let (names, key_name) = match text.strip_suffix("++") {
+            Some(names) => (names, "+"),
+            None => match text.rsplit_once('+') {
+                Some((names, key_name)) => (names, key_name),
+                None => ("", text),
+            },
+        };
+
+        let mut modifiers = Modifiers::empty();
+        for name in names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.trim().to_lowercase().as_str() {
+                "mod" => Modifiers::COMMAND,
+                "ctrl" | "control" => Modifiers::CTRL,
+                "shift" => Modifiers::SHIFT,
+                "alt" | "option" => Modifiers::ALT,
+                "cmd" | "super" | "logo" => Modifiers::LOGO,
+                _ => return Err(format!("unknown modifier \"{name}\"")),
+            };
+        }
+
+        let key_name = key_name.trim();
+        let key = if key_name.is_empty() {
+            return Err("missing key".to_string());
+        } else if key_name.chars().count() == 1 {
+            Key::Character(key_name.to_lowercase().into())
+        } else if let Some((_, named)) = NAMED_KEYS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key_name))
+        {
+            Key::Named(*named)
+        } else {
+            return Err(format!("unknown key \"{key_name}\""));
+        };
+
+        Ok(Shortcut { key, modifiers })
+    }
+}
+
+impl std::fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let logo = if cfg!(target_os = "macos") { "Cmd" } else { "Super" };
+        for (modifier, name) in [
+            (Modifiers::CTRL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::LOGO, logo),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        match self.key.as_ref() {
+            Key::Character(c) => write!(f, "{}", c.to_uppercase()),
+            Key::Named(named) => match NAMED_KEYS.iter().find(|(_, key)| *key == named) {
+                Some((name, _)) => write!(f, "{name}"),
+                None => write!(f, "{named:?}"),
+            },
+            Key::Unidentified => write!(f, "?"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeymapError {
+    /// The file exists but could not be read or is not a JSON object
+    File(String),
+    UnknownAction(String),
+    InvalidShortcut {
+        action: Action,
+        shortcut: String,
+        reason: String,
+    },
+    /// `shortcut` was bound to both; `kept` wins
+    Conflict {
+        shortcut: Shortcut,
+        kept: Action,
+        dropped: Action,
+    },
+}
+
+impl std::fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeymapError::File(reason) => write!(f, "cannot load keymap: {reason}"),
+            KeymapError::UnknownAction(name) => write!(f, "unknown keymap action \"{name}\""),
+            KeymapError::InvalidShortcut {
+                action,
+                shortcut,
+                reason,
+            } => write!(f, "invalid shortcut \"{shortcut}\" for {action}: {reason}"),
+            KeymapError::Conflict {
+                shortcut,
+                kept,
+                dropped,
+            } => write!(f, "{shortcut} is bound to both {kept} and {dropped}; keeping {kept}"),
+        }
+    }
+}
+
+/// Shortcuts for every [`Action`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Keymap {
+    bindings: Vec<(Shortcut, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_json("{}").0
+    }
+}
+
+impl Keymap {
+    /// Load `keymap.json` from the config directory; see [`Keymap::from_json`]
+    pub fn load() -> (Self, Vec<KeymapError>) {
+        match keymap_path() {
+            Some(path) => Self::load_from(&path),
+            None => (Self::default(), Vec::new()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> (Self, Vec<KeymapError>) {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Self::from_json(&json),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => (Self::default(), Vec::new()),
+            Err(error) => (Self::default(), vec![KeymapError::File(error.to_string())]),
+        }
+    }
+
+    /// Defaults with the actions named in `json` rebound, like
+    /// `{"ShowChart": "Ctrl+G", "Redo": ["Mod+Y"], "Undo": []}`
+    ///
+    /// Problems are collected rather than failing the whole keymap: bad
+    /// entries are skipped and conflicts keep the earlier action's binding.
+    pub fn from_json(json: &str) -> (Self, Vec<KeymapError>) {
+        let mut errors = Vec::new();
+        let overrides = match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(json) {
+            Ok(overrides) => overrides,
+            Err(error) => {
+                errors.push(KeymapError::File(error.to_string()));
+                serde_json::Map::new()
+            }
+        };
+
+        for name in overrides.keys() {
+            if !Action::all().any(|action| action.name() == *name) {
+                errors.push(KeymapError::UnknownAction(name.clone()));
+            }
+        }
+
+        let mut bindings: Vec<(Shortcut, Action)> = Vec::new();
+        for action in Action::all() {
+            let texts: Vec<String> = match overrides.get(&action.name()) {
+                None => action.default_shortcuts(),
+                Some(serde_json::Value::String(text)) => vec![text.clone()],
+                Some(serde_json::Value::Array(texts)) => texts
+                    .iter()
+                    .map(|text| text.as_str().map_or_else(|| text.to_string(), str::to_string))
+                    .collect(),
+                Some(other) => {
+                    errors.push(KeymapError::InvalidShortcut {
+                        action,
+                        shortcut: other.to_string(),
+                        reason: "expected a string or a list of strings".to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            for text in texts {
+                let shortcut = match text.parse::<Shortcut>() {
+                    Ok(shortcut) => shortcut,
+                    Err(reason) => {
+                        errors.push(KeymapError::InvalidShortcut {
+                            action,
+                            shortcut: text,
+                            reason,
+                        });
+                        continue;
+                    }
+                };
+
+                match bindings.iter().find(|(bound, _)| *bound == shortcut) {
+                    Some((_, kept)) if *kept == action => {}
+                    Some((_, kept)) => errors.push(KeymapError::Conflict {
+                        shortcut,
+                        kept: *kept,
+                        dropped: action,
+                    }),
+                    None => bindings.push((shortcut, action)),
+                }
+            }
+        }
+
+        (Self { bindings }, errors)
+    }
+
+    /// Action bound to `key` pressed with `modifiers`
+    pub fn action(&self, key: &Key, modifiers: Modifiers) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(shortcut, _)| shortcut.matches(key, modifiers))
+            .map(|(_, action)| *action)
+    }
+}
+
+/// `keymap.json` in the config directory, next to the saved state
+pub fn keymap_path() -> Option<PathBuf> {
+    Some(persistence::config_dir()?.join(KEYMAP_FILE))
+}
+
+/// A key press as seen by the keymap subscription
+#[derive(Debug, Clone)]
+pub struct KeyPress {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+/// Key presses no widget captured, so typing into a text input does not fire
+/// shortcuts. Text inputs let modified keys like Ctrl+Z through, so undo
+/// still works while one is focused.
+pub fn key_press(
+    event: iced::Event,
+    status: iced::event::Status,
+    _window: iced::window::Id,
+) -> Option<KeyPress> {
+    if status == iced::event::Status::Captured {
+        return None;
+    }
+
+    match event {
+        iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+            Some(KeyPress { key, modifiers })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::{chart, form, theme_editor};
+
+    const SHOW_FORM: Action = Action::ShowView(form::VIEW.id);
+    const SHOW_CHART: Action = Action::ShowView(chart::VIEW.id);
+
+    fn shortcut(text: &str) -> Shortcut {
+        text.parse().unwrap()
+    }
+
+    fn character(c: &str) -> Key {
+        Key::Character(c.into())
+    }
+
+    #[test]
+    fn shortcuts_parse_modifiers_and_keys() {
+        let redo = shortcut("Ctrl+Shift+Z");
+        assert_eq!(redo.modifiers, Modifiers::CTRL | Modifiers::SHIFT);
+        assert_eq!(redo.key, character("z"));
+
+        assert_eq!(shortcut("mod+,").modifiers, Modifiers::COMMAND);
+        assert_eq!(shortcut("esc").key, Key::Named(key::Named::Escape));
+        assert_eq!(shortcut("F12").key, Key::Named(key::Named::F12));
+        assert_eq!(shortcut("Ctrl++").key, character("+"));
+    }
+
+    #[test]
+    fn captured_key_presses_are_not_shortcuts() {
+        let press = || {
+            iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                key: character("1"),
+                modified_key: character("1"),
+                physical_key: key::Physical::Code(key::Code::Digit1),
+                location: keyboard::Location::Standard,
+                modifiers: Modifiers::COMMAND,
+                text: None,
+                repeat: false,
+            })
+        };
+        let window = iced::window::Id::unique();
+
+        assert!(key_press(press(), iced::event::Status::Ignored, window).is_some());
+        assert!(key_press(press(), iced::event::Status::Captured, window).is_none());
+    }
+
+    #[test]
+    fn bad_shortcuts_are_rejected() {
+        assert!("Hyper+A".parse::<Shortcut>().is_err());
+        assert!("Ctrl+Banana".parse::<Shortcut>().is_err());
+        assert!("Ctrl+".parse::<Shortcut>().is_err());
+    }
+
+    #[test]
+    fn shortcuts_display_in_canonical_form() {
+        assert_eq!(shortcut("shift+ctrl+z").to_string(), "Ctrl+Shift+Z");
+        assert_eq!(shortcut("Escape").to_string(), "Esc");
+        assert_eq!(shortcut("alt+pagedown").to_string(), "Alt+PageDown");
+    }
+
+    #[test]
+    fn modifiers_must_match_exactly() {
+        let undo = shortcut("Ctrl+Z");
+
+        assert!(undo.matches(&character("z"), Modifiers::CTRL));
+        assert!(!undo.matches(&character("z"), Modifiers::CTRL | Modifiers::SHIFT));
+        assert!(!undo.matches(&character("z"), Modifiers::empty()));
+        // Shift turns letters uppercase
+        assert!(shortcut("Ctrl+Shift+Z").matches(&character("Z"), Modifiers::CTRL | Modifiers::SHIFT));
+    }
+
+    #[test]
+    fn defaults_cover_navigation_and_menu() {
+        let keymap = Keymap::default();
+
+        assert_eq!(keymap.action(&character("2"), Modifiers::COMMAND), Some(SHOW_CHART));
+        assert_eq!(keymap.action(&character(","), Modifiers::COMMAND), Some(Action::OpenSettings));
+        assert_eq!(keymap.action(&character("s"), Modifiers::COMMAND), Some(Action::ExportSettings));
+        assert_eq!(
+            keymap.action(&Key::Named(key::Named::Escape), Modifiers::empty()),
+            Some(Action::Close)
+        );
+        assert_eq!(keymap.action(&character("2"), Modifiers::empty()), None);
+        assert_eq!(keymap.action(&character("y"), Modifiers::COMMAND), Some(Action::Redo));
+    }
+
+    #[test]
+    fn every_view_gets_a_shortcut_in_menu_order() {
+        let keymap = Keymap::default();
+
+        for (index, view) in views::all().enumerate().take(9) {
+            let digit = (index + 1).to_string();
+            assert_eq!(
+                keymap.action(&character(&digit), Modifiers::COMMAND),
+                Some(Action::ShowView(view.id))
+            );
+        }
+
+        let show_theme_editor = Action::ShowView(theme_editor::VIEW.id);
+        assert_eq!(show_theme_editor.name(), "ShowThemeEditor");
+        assert_eq!(show_theme_editor.to_string(), "Show theme editor");
+        let (keymap, errors) = Keymap::from_json(r#"{"ShowThemeEditor": "Alt+T"}"#);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(keymap.action(&character("t"), Modifiers::ALT), Some(show_theme_editor));
+    }
+
+    #[test]
+    fn file_rebinds_and_unbinds_actions() {
+        let (keymap, errors) = Keymap::from_json(r#"{"ShowChart": "Alt+C", "Undo": [], "Redo": ["Ctrl+R"]}"#);
+
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(keymap.action(&character("c"), Modifiers::ALT), Some(SHOW_CHART));
+        assert_eq!(keymap.action(&character("2"), Modifiers::COMMAND), None);
+        assert_eq!(keymap.action(&character("z"), Modifiers::COMMAND), None);
+        assert_eq!(keymap.action(&character("r"), Modifiers::CTRL), Some(Action::Redo));
+        // Untouched actions keep their defaults
+        assert_eq!(keymap.action(&character("1"), Modifiers::COMMAND), Some(SHOW_FORM));
+    }
+
+    #[test]
+    fn conflicts_keep_the_earlier_action() {
+        let (keymap, errors) = Keymap::from_json(r#"{"ExportSettings": "Mod+1"}"#);
+
+        assert_eq!(
+            errors,
+            [KeymapError::Conflict {
+                shortcut: shortcut("Mod+1"),
+                kept: SHOW_FORM,
+                dropped: Action::ExportSettings,
+            }]
+        );
+        assert_eq!(keymap.action(&character("1"), Modifiers::COMMAND), Some(SHOW_FORM));
+    }
+
+    #[test]
+    fn bad_entries_are_reported_and_skipped() {
+        let (keymap, errors) = Keymap::from_json(r#"{"Launch": "Ctrl+L", "ShowChart": ["Ctrl+Nope", "Ctrl+G"], "Undo": 5}"#);
+
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(matches!(&errors[0], KeymapError::UnknownAction(name) if name == "Launch"));
+        assert!(matches!(&errors[1], KeymapError::InvalidShortcut { action, .. } if *action == SHOW_CHART));
+        assert!(matches!(&errors[2], KeymapError::InvalidShortcut { action: Action::Undo, .. }));
+        assert_eq!(keymap.action(&character("g"), Modifiers::CTRL), Some(SHOW_CHART));
+    }
+
+    #[test]
+    fn malformed_file_falls_back_to_defaults() {
+        let (keymap, errors) = Keymap::from_json("[1, 2]");
+
+        assert!(matches!(errors[..], [KeymapError::File(_)]));
+        assert_eq!(keymap, Keymap::default());
+    }
+}
@@ -1,7 +1,19 @@
 // Module declarations
 mod app;
+mod cli;
+mod command_palette;
+mod dataset;
+mod debugger;
+mod history;
+mod keymap;
 mod message;
+mod persistence;
+mod router;
+mod session;
+mod settings;
 mod styles;
+mod theme_editor;
+mod theme_files;
 mod components;
 mod views;
 mod widgets;
@@ -10,8 +22,22 @@ mod utils;
 use app::App;
 
 pub fn main() -> iced::Result {
-    iced::application(App::new, App::update, App::view)
+    let options = match cli::Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+
+    iced::application(move || App::new(options.clone()), App::update, App::view)
         .subscription(App::subscription)
         .theme(App::theme)
+        // Closing is handled in App::update so the state can be saved first
+        .exit_on_close_request(false)
         .run()
 }
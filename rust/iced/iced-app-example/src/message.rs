@@ -1,8 +1,19 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Instant;
 
-use crate::styles::theme::ThemeType;
+use crate::command_palette::Command;
+use crate::dataset::{ImportError, Table};
+use crate::keymap::Action;
+use crate::router::Route;
+use crate::styles::skin::Skin;
+use crate::styles::theme::{CustomTheme, PaletteColor, ThemeChoice, ThemeType};
+use crate::utils::animation::Curve;
+use crate::theme_files::ThemeFileFormat;
+use crate::views::ViewId;
+use crate::widgets::{BarLayout, ChartKind, ExportFormat, ExportSize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Role {
     Developer,
     Designer,
@@ -29,7 +40,7 @@ impl std::fmt::Display for Role {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BrowserOption {
     OptionA,
     OptionB,
@@ -58,15 +69,70 @@ impl std::fmt::Display for BrowserOption {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ViewMode {
-    Form,
-    Chart,
-    Summary,
-    BrowserForm,
+/// How the outgoing and incoming views are animated on navigation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ViewTransition {
+    #[default]
+    CrossFade,
+    Slide,
+    Zoom,
 }
 
-#[derive(Debug, Clone)]
+impl ViewTransition {
+    pub const ALL: [ViewTransition; 3] = [
+        ViewTransition::CrossFade,
+        ViewTransition::Slide,
+        ViewTransition::Zoom,
+    ];
+}
+
+impl std::fmt::Display for ViewTransition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ViewTransition::CrossFade => "Cross-fade",
+                ViewTransition::Slide => "Slide",
+                ViewTransition::Zoom => "Zoom",
+            }
+        )
+    }
+}
+
+/// Animations whose curve can be picked in the config menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WidgetAnimation {
+    ViewTransition,
+    ButtonFade,
+    DrawerSlide,
+}
+
+impl WidgetAnimation {
+    pub const ALL: [WidgetAnimation; 3] = [
+        WidgetAnimation::ViewTransition,
+        WidgetAnimation::ButtonFade,
+        WidgetAnimation::DrawerSlide,
+    ];
+}
+
+impl std::fmt::Display for WidgetAnimation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                WidgetAnimation::ViewTransition => "View transition",
+                WidgetAnimation::ButtonFade => "Button fade",
+                WidgetAnimation::DrawerSlide => "Drawer slide",
+            }
+        )
+    }
+}
+
+/// Everything except wall-clock and window-handle messages can be written to
+/// a session log
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Message {
     Increment,
     Decrement,
@@ -76,10 +142,20 @@ pub enum Message {
     ExperienceChanged(f32),
     RoleSelected(Role),
     ThemeSelected(ThemeType),
-    ViewChanged(ViewMode),
+    /// Name of a saved custom theme
+    CustomThemeSelected(String),
+    ViewChanged(ViewId),
+    Navigate(Route),
+    NavigateBack,
+    NavigateForward,
+    TransitionSelected(ViewTransition),
+    CurveSelected(WidgetAnimation, Curve),
+    SkinSelected(Skin),
+    #[serde(skip)]
     Tick(Instant),
     ShowSecondButton,
     HideSecondButton,
+    SecondButtonHidden,
     // Browser form messages
     BrowserTextChanged(String),
     BrowserPasswordChanged(String),
@@ -90,7 +166,72 @@ pub enum Message {
     // Config menu
     ToggleConfigMenu,
     ConfigResetData,
+    ConfigResetConfirm,
+    ConfigResetCancel,
+    ConfigResetUndo,
     ConfigExportSettings,
     ConfigImportSettings,
     ConfigMenuInteraction, // Dummy message to block click-through
+    ConfigAutosaveToggled(bool),
+    #[serde(skip)]
+    WindowCloseRequested(iced::window::Id),
+    /// A key bound in the keymap was pressed
+    ShortcutPressed(Action),
+    // Command palette
+    CommandPaletteToggled,
+    CommandPaletteClosed,
+    CommandPaletteQueryChanged(String),
+    /// Move the highlight by this many entries
+    CommandPaletteMoved(i32),
+    CommandPaletteSubmitted,
+    CommandPaletteRun(Command),
+    // Theme editor
+    ThemeEditorNameChanged(String),
+    ThemeEditorStartedFrom(ThemeChoice),
+    ThemeEditorColorSelected(PaletteColor),
+    ThemeEditorHexChanged(PaletteColor, String),
+    /// Red (0), green (1) or blue (2) of the selected color
+    ThemeEditorChannelChanged(usize, u8),
+    ThemeEditorSaved,
+    ThemeEditorDeleted,
+    /// Custom themes written, or why not; the text is the status to show
+    /// once they are
+    ThemesSaved(String, Result<PathBuf, String>),
+    ThemeImportPathChanged(String),
+    ThemeImportRequested,
+    /// Themes read by an import, and why other files could not be
+    ThemesImported(Vec<CustomTheme>, Vec<String>),
+    ThemeExported(ThemeFileFormat),
+    // Undo history
+    Undo,
+    Redo,
+    HistoryLimitChanged(u8),
+    // Session replay
+    #[serde(skip)]
+    ReplayTick(Instant),
+    // Time-travel debugger
+    DebuggerToggled,
+    DebuggerScrubbed(usize),
+    DebuggerResume,
+    // Chart interaction
+    /// Index of the label whose bar was clicked
+    ChartBarPressed(usize),
+    ChartSeriesToggled(usize),
+    ChartLayoutSelected(BarLayout),
+    ChartKindSelected(ChartKind),
+    // Chart export
+    ChartExportSizeSelected(ExportSize),
+    ChartExportTransparentToggled(bool),
+    ChartExported(ExportFormat),
+    ChartExportFinished(Result<PathBuf, String>),
+    // Chart data import
+    ChartImportPathChanged(String),
+    ChartImportRequested,
+    ChartImportReloaded,
+    ChartDataLoaded(PathBuf, Result<Table, ImportError>),
+    ChartImportLabelSelected(String),
+    ChartImportValueSelected(String),
+    /// Column name, or [`crate::dataset::NO_COLUMN`]
+    ChartImportColorSelected(String),
+    ChartImportCleared,
 }
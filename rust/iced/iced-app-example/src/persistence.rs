@@ -0,0 +1,272 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::app::App;
+use crate::settings::{Settings, SettingsError};
+use crate::utils::animation::Animation;
+
+/// Wait this long after the last change before autosaving
+pub const AUTOSAVE_DEBOUNCE_MS: f32 = 1000.0;
+
+const APP_DIR: &str = "icedapp";
+const STATE_FILE: &str = "state.json";
+
+/// App state persisted between launches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedState {
+    #[serde(default)]
+    pub autosave: bool,
+    #[serde(default)]
+    pub value: i64,
+    #[serde(default)]
+    pub config_menu_open: bool,
+    /// Checked like an imported settings file
+    #[serde(deserialize_with = "validated_settings")]
+    pub settings: Settings,
+}
+
+fn validated_settings<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Settings, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Settings::from_value(value).map_err(serde::de::Error::custom)
+}
+
+impl SavedState {
+    pub fn from_app(app: &App) -> Self {
+        Self {
+            autosave: app.autosave_enabled,
+            value: app.value,
+            config_menu_open: app.config_menu_open,
+            settings: Settings::from_app(app),
+        }
+    }
+
+    pub fn apply(self, app: &mut App) {
+        app.autosave_enabled = self.autosave;
+        app.value = self.value;
+        app.config_menu_open = self.config_menu_open;
+        if self.config_menu_open {
+            let curve = app.config_menu_animation.current_curve();
+            app.config_menu_animation = Animation::default().curve(curve).completed();
+        }
+        self.settings.apply(app);
+    }
+}
+
+/// `$XDG_CONFIG_HOME/icedapp`, falling back to `~/.config`
+pub fn config_dir() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_home.join(APP_DIR))
+}
+
+/// Where the app writes its files: saved state and themes go in the config
+/// directory, exports in the working directory
+///
+/// A replayed session writes into a throwaway [`Storage::sandbox`] instead,
+/// so a replay never overwrites the user's real files. Reads, like imports,
+/// always use the real paths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Storage {
+    /// `None` when there is no home directory to save into
+    pub config_dir: Option<PathBuf>,
+    pub work_dir: PathBuf,
+}
+
+impl Storage {
+    /// The user's [`config_dir`] and the working directory
+    pub fn system() -> Self {
+        Self {
+            config_dir: config_dir(),
+            work_dir: std::env::current_dir().unwrap_or_default(),
+        }
+    }
+
+    /// Empty config and working directories of its own under the system
+    /// temp directory
+    pub fn sandbox() -> std::io::Result<Self> {
+        static SANDBOXES: AtomicUsize = AtomicUsize::new(0);
+        let root = std::env::temp_dir().join(format!(
+            "icedapp-replay-{}-{}",
+            std::process::id(),
+            SANDBOXES.fetch_add(1, Ordering::Relaxed)
+        ));
+        // Left behind by an earlier process that had the same id
+        let _ = std::fs::remove_dir_all(&root);
+
+        let storage = Self {
+            config_dir: Some(root.join("config")),
+            work_dir: root.join("work"),
+        };
+        std::fs::create_dir_all(&storage.work_dir)?;
+        Ok(storage)
+    }
+
+    /// `name` in the config directory, if there is one
+    pub fn config_file(&self, name: &str) -> Option<PathBuf> {
+        Some(self.config_dir.as_ref()?.join(name))
+    }
+
+    pub fn work_file(&self, name: &str) -> PathBuf {
+        self.work_dir.join(name)
+    }
+
+    /// `<stem>.<extension>` in the working directory, or the first free
+    /// `<stem>-2.<extension>`, `<stem>-3.<extension>`… so exports never
+    /// overwrite an earlier file
+    pub fn unused_work_file(&self, stem: &str, extension: &str) -> PathBuf {
+        let first = self.work_dir.join(format!("{stem}.{extension}"));
+        std::iter::once(first)
+            .chain((2..).map(|number| self.work_dir.join(format!("{stem}-{number}.{extension}"))))
+            .find(|path| !path.exists())
+            .expect("some numbered name is free")
+    }
+}
+
+/// `state.json` in the [`config_dir`]
+pub fn state_path() -> Option<PathBuf> {
+    Some(config_dir()?.join(STATE_FILE))
+}
+
+/// Load the saved state, `Ok(None)` when nothing has been saved yet
+pub fn load() -> Result<Option<SavedState>, SettingsError> {
+    match state_path() {
+        Some(path) => load_from(&path),
+        None => Ok(None),
+    }
+}
+
+pub fn load_from(path: &Path) -> Result<Option<SavedState>, SettingsError> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+pub fn save(storage: &Storage, state: &SavedState) -> Result<(), SettingsError> {
+    let path = storage
+        .config_file(STATE_FILE)
+        .ok_or_else(|| SettingsError::Invalid("no config directory available".to_string()))?;
+    save_to(&path, state)
+}
+
+/// Write the state atomically so a crash mid-save never leaves a truncated file
+pub fn save_to(path: &Path, state: &SavedState) -> Result<(), SettingsError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, serde_json::to_string_pretty(state)?)?;
+    std::fs::rename(&temp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("icedapp-persistence-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn saved_state_loads_back() {
+        let path = temp_path("saved.json");
+        let app = App {
+            autosave_enabled: true,
+            value: 7,
+            config_menu_open: true,
+            name: "Ada".to_string(),
+            ..App::default()
+        };
+
+        save_to(&path, &SavedState::from_app(&app)).unwrap();
+        let loaded = load_from(&path).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut restored = App::default();
+        loaded.apply(&mut restored);
+        assert!(restored.autosave_enabled);
+        assert_eq!(restored.value, 7);
+        assert_eq!(restored.name, "Ada");
+        assert!(restored.config_menu_open);
+        assert_eq!(restored.config_menu_animation.progress(restored.now), 1.0);
+    }
+
+    #[test]
+    fn nothing_saved_yet_is_not_an_error() {
+        assert!(load_from(&temp_path("missing.json")).unwrap().is_none());
+    }
+
+    #[test]
+    fn corrupt_files_are_errors() {
+        let path = temp_path("corrupt.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        std::fs::write(&path, "{\"autosave\": tru").unwrap();
+        assert!(matches!(load_from(&path), Err(SettingsError::Parse(_))));
+
+        // Settings that an import would reject are rejected here too
+        let mut state = SavedState::from_app(&App::default());
+        state.settings.form.age = 200;
+        std::fs::write(&path, serde_json::to_string(&state).unwrap()).unwrap();
+        let result = load_from(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.unwrap_err().to_string().contains("age 200"));
+    }
+
+    #[test]
+    fn startup_survives_a_corrupt_file() {
+        let path = temp_path("startup.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "not json").unwrap();
+
+        let mut app = App::default();
+        app.restore(load_from(&path));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(app.value, 0);
+        assert!(app.config_status.unwrap().starts_with("Could not restore last session"));
+    }
+
+    #[test]
+    fn only_the_settings_are_required() {
+        let settings = Settings::from_app(&App::default());
+        let json = serde_json::json!({ "settings": settings }).to_string();
+
+        let state: SavedState = serde_json::from_str(&json).unwrap();
+        assert!(!state.autosave);
+        assert_eq!(state.value, 0);
+        assert!(!state.config_menu_open);
+        assert!(serde_json::from_str::<SavedState>("{}").is_err());
+    }
+
+    #[test]
+    fn exports_get_numbered_instead_of_overwriting() {
+        let dir = temp_path("exports");
+        std::fs::create_dir_all(&dir).unwrap();
+        let storage = Storage {
+            config_dir: None,
+            work_dir: dir.clone(),
+        };
+
+        let first = storage.unused_work_file("chart", "png");
+        assert_eq!(first, dir.join("chart.png"));
+        std::fs::write(&first, "").unwrap();
+        let second = storage.unused_work_file("chart", "png");
+        assert_eq!(second, dir.join("chart-2.png"));
+        std::fs::write(&second, "").unwrap();
+        assert_eq!(storage.unused_work_file("chart", "png"), dir.join("chart-3.png"));
+        assert_eq!(storage.unused_work_file("chart", "svg"), dir.join("chart.svg"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
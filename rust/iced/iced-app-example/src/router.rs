@@ -0,0 +1,361 @@
+use iced::advanced::widget::operation::scrollable::{self, AbsoluteOffset};
+use iced::advanced::widget::operation::{Operation, Outcome};
+use iced::advanced::widget::{self, Id};
+use iced::{event, mouse, window, Rectangle, Task, Vector};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+use crate::message::Message;
+use crate::views::{self, Section, ViewId};
+
+/// How many views Back can go through before the oldest is forgotten
+const MAX_DEPTH: usize = 50;
+
+/// A view and optionally a section of it, written like `/form#settings`
+///
+/// Stored in session logs as that text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Route {
+    pub view: ViewId,
+    pub section: Option<Section>,
+}
+
+impl Route {
+    pub fn section(view: ViewId, section: Section) -> Self {
+        Route {
+            view,
+            section: Some(section),
+        }
+    }
+}
+
+impl From<ViewId> for Route {
+    fn from(view: ViewId) -> Self {
+        Route { view, section: None }
+    }
+}
+
+impl FromStr for Route {
+    type Err = RouteError;
+
+    fn from_str(route: &str) -> Result<Self, Self::Err> {
+        let route = route.trim();
+        let (path, fragment) = match route.split_once('#') {
+            Some((path, fragment)) => (path, Some(fragment)),
+            None => (route, None),
+        };
+        let path = path.trim_matches('/');
+
+        // The bare root opens the first view
+        let view = if path.is_empty() {
+            views::get(views::first())
+        } else {
+            views::find(path).ok_or_else(|| RouteError::UnknownView(path.to_string()))?
+        };
+
+        let section = match fragment.filter(|fragment| !fragment.is_empty()) {
+            Some(fragment) => Some(
+                *view
+                    .section(fragment)
+                    .ok_or_else(|| RouteError::UnknownSection(view.id, fragment.to_string()))?,
+            ),
+            None => None,
+        };
+
+        Ok(Route {
+            view: view.id,
+            section,
+        })
+    }
+}
+
+impl std::fmt::Display for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "/{}", self.view)?;
+        if let Some(section) = self.section {
+            write!(f, "#{}", section.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Route {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Route {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    UnknownView(String),
+    UnknownSection(ViewId, String),
+}
+
+impl std::fmt::Display for RouteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteError::UnknownView(path) => {
+                let paths: Vec<String> = views::all().map(|view| format!("/{}", view.id)).collect();
+                write!(f, "no view at `/{path}` (try {})", paths.join(", "))
+            }
+            RouteError::UnknownSection(view, section) => {
+                write!(f, "`/{view}` has no section `#{section}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+/// Views visited before and, after going back, after the current one
+///
+/// The current route is not stored here but passed in, so views changed
+/// without navigating (restoring settings, scrubbing the debugger) cannot
+/// leave the router out of step.
+#[derive(Debug, Clone, Default)]
+pub struct Router {
+    back: Vec<Route>,
+    forward: Vec<Route>,
+}
+
+impl Router {
+    /// Leaving `from` for a new route; anything ahead of it is dropped
+    pub fn visit(&mut self, from: Route) {
+        self.back.push(from);
+        if self.back.len() > MAX_DEPTH {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    /// Route before `current`, which becomes the way forward again
+    pub fn back(&mut self, current: Route) -> Option<Route> {
+        let route = self.back.pop()?;
+        self.forward.push(current);
+        Some(route)
+    }
+
+    /// Route gone back from last, with `current` becoming the way back
+    pub fn forward(&mut self, current: Route) -> Option<Route> {
+        let route = self.forward.pop()?;
+        self.back.push(current);
+        Some(route)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+}
+
+/// Scroll `scrollable` until `section` is at its top, or as near as it goes
+///
+/// The section is looked up in the laid out view, so it is found wherever
+/// the view puts it.
+pub fn scroll_to(scrollable: &'static str, section: Section) -> Task<Message> {
+    widget::operate(ScrollToSection {
+        scrollable: Id::from(scrollable),
+        section: section.id(),
+        content_top: None,
+        section_top: None,
+    })
+    .discard()
+}
+
+/// Finds where the content of the scrollable and the section start, then
+/// scrolls by the difference
+struct ScrollToSection {
+    scrollable: Id,
+    section: Id,
+    content_top: Option<f32>,
+    section_top: Option<f32>,
+}
+
+impl Operation for ScrollToSection {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation)) {
+        operate(self);
+    }
+
+    fn container(&mut self, id: Option<&Id>, bounds: Rectangle) {
+        if id == Some(&self.section) {
+            self.section_top = Some(bounds.y);
+        }
+    }
+
+    fn scrollable(
+        &mut self,
+        id: Option<&Id>,
+        _bounds: Rectangle,
+        content_bounds: Rectangle,
+        _translation: Vector,
+        _state: &mut dyn scrollable::Scrollable,
+    ) {
+        // Content is laid out unscrolled, so this is comparable to the section
+        if id == Some(&self.scrollable) {
+            self.content_top = Some(content_bounds.y);
+        }
+    }
+
+    fn finish(&self) -> Outcome<()> {
+        match (self.content_top, self.section_top) {
+            (Some(content_top), Some(section_top)) => Outcome::Chain(Box::new(scrollable::scroll_to(
+                self.scrollable.clone(),
+                AbsoluteOffset {
+                    x: None,
+                    y: Some(section_top - content_top),
+                },
+            ))),
+            _ => Outcome::None,
+        }
+    }
+}
+
+/// The back and forward buttons found on many mice
+pub fn mouse_button(event: iced::Event, _status: event::Status, _window: window::Id) -> Option<Message> {
+    match event {
+        iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Back)) => Some(Message::NavigateBack),
+        iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Forward)) => {
+            Some(Message::NavigateForward)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::views::{browser_form, chart, form, summary};
+
+    #[test]
+    fn routes_parse_views_and_sections() {
+        assert_eq!("/chart".parse(), Ok(Route::from(chart::VIEW.id)));
+        assert_eq!("summary".parse(), Ok(Route::from(summary::VIEW.id)));
+        assert_eq!("/Browser-Form/".parse(), Ok(Route::from(browser_form::VIEW.id)));
+        assert_eq!("/".parse(), Ok(Route::from(form::VIEW.id)));
+        assert_eq!("/form#settings".parse(), Ok(Route::section(form::VIEW.id, form::SETTINGS)));
+        assert_eq!("/form#".parse(), Ok(Route::from(form::VIEW.id)));
+    }
+
+    #[test]
+    fn unknown_routes_are_rejected() {
+        assert_eq!(
+            "/graphs".parse::<Route>(),
+            Err(RouteError::UnknownView("graphs".to_string()))
+        );
+        assert_eq!(
+            "/form#nowhere".parse::<Route>(),
+            Err(RouteError::UnknownSection(form::VIEW.id, "nowhere".to_string()))
+        );
+        // Sections only belong to their own view
+        assert!("/chart#settings".parse::<Route>().is_err());
+    }
+
+    #[test]
+    fn every_registered_route_round_trips() {
+        for view in views::all() {
+            let route = Route::from(view.id);
+            assert_eq!(route.to_string().parse(), Ok(route));
+            for section in view.sections {
+                let route = Route::section(view.id, *section);
+                assert_eq!(route.to_string().parse(), Ok(route));
+            }
+        }
+
+        let route = Route::section(form::VIEW.id, form::SETTINGS);
+        assert_eq!(route.to_string(), "/form#settings");
+        assert_eq!(serde_json::to_string(&route).unwrap(), r#""/form#settings""#);
+    }
+
+    #[test]
+    fn back_and_forward_walk_the_stack() {
+        let form = Route::from(form::VIEW.id);
+        let chart = Route::from(chart::VIEW.id);
+        let summary = Route::from(summary::VIEW.id);
+        let mut router = Router::default();
+
+        router.visit(form);
+        router.visit(chart);
+        assert_eq!(router.back(summary), Some(chart));
+        assert_eq!(router.back(chart), Some(form));
+        assert_eq!(router.back(form), None);
+
+        assert_eq!(router.forward(form), Some(chart));
+        assert!(router.can_go_forward());
+
+        // Visiting somewhere new drops the way forward
+        router.visit(chart);
+        assert!(!router.can_go_forward());
+        assert_eq!(router.forward(summary), None);
+    }
+
+    #[test]
+    fn history_forgets_the_oldest_views() {
+        let mut router = Router::default();
+        for _ in 0..MAX_DEPTH + 10 {
+            router.visit(Route::from(chart::VIEW.id));
+        }
+
+        let mut steps = 0;
+        while router.back(Route::from(form::VIEW.id)).is_some() {
+            steps += 1;
+        }
+        assert_eq!(steps, MAX_DEPTH);
+    }
+
+    #[test]
+    fn app_navigates_back_and_forward() {
+        let mut app = App::default();
+
+        let _ = app.update(Message::ViewChanged(chart::VIEW.id));
+        let _ = app.update(Message::Navigate("/summary".parse().unwrap()));
+        assert_eq!(app.current_view, summary::VIEW.id);
+
+        let _ = app.update(Message::NavigateBack);
+        assert_eq!(app.current_view, chart::VIEW.id);
+        let _ = app.update(Message::NavigateBack);
+        assert_eq!(app.current_view, form::VIEW.id);
+        // Nothing further back
+        let _ = app.update(Message::NavigateBack);
+        assert_eq!(app.current_view, form::VIEW.id);
+
+        let _ = app.update(Message::NavigateForward);
+        assert_eq!(app.current_view, chart::VIEW.id);
+
+        // Re-selecting the current view is not a step to go back through
+        let _ = app.update(Message::ViewChanged(chart::VIEW.id));
+        let _ = app.update(Message::NavigateBack);
+        assert_eq!(app.current_view, form::VIEW.id);
+    }
+
+    #[test]
+    fn going_back_returns_to_the_section() {
+        let mut app = App::default();
+        let settings = Route::section(form::VIEW.id, form::SETTINGS);
+
+        let _ = app.update(Message::Navigate(settings));
+        let _ = app.update(Message::ViewChanged(chart::VIEW.id));
+        assert_eq!(app.route(), Route::from(chart::VIEW.id));
+
+        let _ = app.update(Message::NavigateBack);
+        assert_eq!(app.route(), settings);
+        let _ = app.update(Message::NavigateForward);
+        let _ = app.update(Message::NavigateBack);
+        assert_eq!(app.route(), settings);
+
+        // A section left behind by changing views some other way is not kept
+        app.current_view = chart::VIEW.id;
+        assert_eq!(app.route(), Route::from(chart::VIEW.id));
+    }
+}
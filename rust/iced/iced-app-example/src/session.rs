@@ -0,0 +1,328 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::message::Message;
+use crate::utils::clock::Clock;
+
+/// One line of a session log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionEntry {
+    /// Milliseconds since the recording started
+    pub at_ms: u64,
+    pub event: SessionEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SessionEvent {
+    /// Animation tick - the timestamp is `at_ms`
+    Tick,
+    Message(Message),
+}
+
+impl SessionEntry {
+    /// Turn the entry back into a message for a replay that started at `base`
+    pub fn to_message(&self, base: Instant) -> Message {
+        match &self.event {
+            SessionEvent::Tick => Message::Tick(self.time(base)),
+            SessionEvent::Message(message) => message.clone(),
+        }
+    }
+
+    fn time(&self, base: Instant) -> Instant {
+        base + Duration::from_millis(self.at_ms)
+    }
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    Io(std::io::Error),
+    /// A message that could not be written to the log
+    Json(serde_json::Error),
+    /// A log line that is not a session entry
+    Line { line: usize, error: serde_json::Error },
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::Io(error) => write!(f, "cannot access session log: {error}"),
+            SessionError::Json(error) => write!(f, "cannot log message: {error}"),
+            SessionError::Line { line, error } => write!(f, "session line {line}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl From<std::io::Error> for SessionError {
+    fn from(error: std::io::Error) -> Self {
+        SessionError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SessionError {
+    fn from(error: serde_json::Error) -> Self {
+        SessionError::Json(error)
+    }
+}
+
+/// Appends every handled message to a JSON Lines file
+pub struct Recorder {
+    writer: BufWriter<File>,
+    started_at: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, now: Instant) -> Result<Self, SessionError> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+            started_at: now,
+        })
+    }
+
+    pub fn record(&mut self, message: &Message, now: Instant) -> Result<(), SessionError> {
+        let event = match message {
+            Message::Tick(_) => SessionEvent::Tick,
+            // Window handles and replay control only mean something in the original run
+            Message::WindowCloseRequested(_) | Message::ReplayTick(_) => return Ok(()),
+            message => SessionEvent::Message(message.clone()),
+        };
+
+        let entry = SessionEntry {
+            at_ms: now.saturating_duration_since(self.started_at).as_millis() as u64,
+            event,
+        };
+
+        serde_json::to_writer(&mut self.writer, &entry)?;
+        self.writer.write_all(b"\n")?;
+        // Flush per line so a crash still leaves a usable log behind
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Read a session log written by [`Recorder`]
+pub fn load(path: &Path) -> Result<Vec<SessionEntry>, SessionError> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|error| SessionError::Line {
+            line: number + 1,
+            error,
+        })?;
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// A session log being played back in real time
+pub struct Replay {
+    entries: Vec<SessionEntry>,
+    next: usize,
+    /// Start of the playback, also the base of the replayed manual clock
+    started_at: Instant,
+}
+
+impl Replay {
+    pub fn new(entries: Vec<SessionEntry>, now: Instant) -> Self {
+        Self {
+            entries,
+            next: 0,
+            started_at: now,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.entries.len()
+    }
+
+    /// Entries due by wall-clock `now`, as messages with the manual clock time
+    /// each one should be handled at
+    pub fn due(&mut self, now: Instant) -> Vec<(Instant, Message)> {
+        let elapsed = now.saturating_duration_since(self.started_at).as_millis() as u64;
+        let mut due = Vec::new();
+
+        while let Some(entry) = self.entries.get(self.next) {
+            if entry.at_ms > elapsed {
+                break;
+            }
+            due.push((entry.time(self.started_at), entry.to_message(self.started_at)));
+            self.next += 1;
+        }
+
+        due
+    }
+}
+
+/// Handle replayed messages, each at the manual clock time it was
+/// recorded at
+///
+/// Tasks returned by `update` are dropped: anything they produced in the
+/// original run (like animation completion messages) is already in the log.
+pub fn play(app: &mut App, due: Vec<(Instant, Message)>) {
+    for (time, message) in due {
+        app.clock = Clock::Manual(time);
+        let _ = app.update(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::{ImportError, Row, Table};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use crate::history::Snapshot;
+    use crate::persistence::Storage;
+    use crate::settings::SETTINGS_FILE;
+    use crate::views::summary;
+
+    fn entry(at_ms: u64, message: Message) -> SessionEntry {
+        SessionEntry {
+            at_ms,
+            event: SessionEvent::Message(message),
+        }
+    }
+
+    fn tick(at_ms: u64) -> SessionEntry {
+        SessionEntry {
+            at_ms,
+            event: SessionEvent::Tick,
+        }
+    }
+
+    /// Play all of `entries` with one replay tick, the way a replay run does
+    fn replay(entries: Vec<SessionEntry>, base: Instant) -> App {
+        let mut app = App::default();
+        app.start_replay(entries, base).unwrap();
+        let _ = app.update(Message::ReplayTick(base + Duration::from_secs(60)));
+        assert!(app.replay.is_none());
+        app
+    }
+
+    fn session() -> Vec<SessionEntry> {
+        vec![
+            entry(0, Message::NameChanged("Ada".to_string())),
+            entry(120, Message::Increment),
+            entry(240, Message::Increment),
+            entry(400, Message::ViewChanged(summary::VIEW.id)),
+            tick(416),
+            tick(560),
+            tick(720),
+        ]
+    }
+
+    #[test]
+    fn session_log_round_trips_through_json() {
+        let lines: Vec<String> = session()
+            .iter()
+            .map(|entry| serde_json::to_string(entry).unwrap())
+            .collect();
+
+        let parsed: Vec<SessionEntry> = lines
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(parsed.len(), session().len());
+        assert!(matches!(
+            parsed[0].event,
+            SessionEvent::Message(Message::NameChanged(ref name)) if name == "Ada"
+        ));
+        assert!(matches!(parsed[4].event, SessionEvent::Tick));
+    }
+
+    #[test]
+    fn malformed_lines_are_reported_by_number() {
+        let path = std::env::temp_dir().join(format!("icedapp-session-{}.jsonl", std::process::id()));
+        std::fs::write(&path, "{\"at_ms\":0,\"event\":\"Tick\"}\n\nnot json\n").unwrap();
+        let error = load(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(error, SessionError::Line { line: 3, .. }));
+        assert!(error.to_string().starts_with("session line 3:"));
+    }
+
+    #[test]
+    fn replays_are_deterministic() {
+        let base = Instant::now();
+
+        let first = replay(session(), base);
+        let second = replay(session(), base);
+
+        assert_eq!(Snapshot::from_app(&first), Snapshot::from_app(&second));
+        assert_eq!(first.value, 2);
+        assert_eq!(first.current_view, summary::VIEW.id);
+        // The last tick is past the transition, so it has settled
+        assert!(!first.view_transition.is_active());
+    }
+
+    #[test]
+    fn replayed_session_renders_headless() {
+        let app = replay(session(), Instant::now());
+
+        let mut ui = iced_test::simulator(app.view());
+        assert!(ui.find("Name: Ada").is_ok());
+        assert!(ui.find("Counter: 2").is_ok());
+    }
+
+    #[test]
+    fn replays_show_loaded_data() {
+        let table = Table {
+            columns: vec!["name".to_string(), "count".to_string()],
+            rows: vec![Row {
+                number: 2,
+                cells: vec!["apples".to_string(), "3".to_string()],
+            }],
+        };
+        let loaded = |result| entry(0, Message::ChartDataLoaded(PathBuf::from("fruit.csv"), result));
+        let logged = |entry: &SessionEntry| {
+            serde_json::from_str::<SessionEntry>(&serde_json::to_string(entry).unwrap()).unwrap()
+        };
+
+        let app = replay(vec![logged(&loaded(Ok(table)))], Instant::now());
+        let dataset = app.chart_dataset.as_ref().unwrap();
+        assert_eq!(dataset.bars().labels, ["apples"]);
+        assert_eq!(dataset.bars().values, [3.0]);
+
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        let app = replay(
+            vec![logged(&loaded(Err(ImportError::Io(Arc::new(missing)))))],
+            Instant::now(),
+        );
+        assert!(app.chart_dataset.is_none());
+        assert!(app.chart_status.unwrap().contains("no such file"));
+    }
+
+    #[test]
+    fn replays_write_into_a_sandbox() {
+        let app = replay(
+            vec![
+                entry(0, Message::ConfigAutosaveToggled(true)),
+                entry(10, Message::ConfigExportSettings),
+            ],
+            Instant::now(),
+        );
+
+        let storage = &app.storage;
+        let config_dir = storage.config_dir.clone().unwrap();
+        assert_ne!(*storage, Storage::system());
+        assert!(config_dir.starts_with(std::env::temp_dir()));
+        assert!(storage.work_dir.starts_with(std::env::temp_dir()));
+
+        assert!(config_dir.join("state.json").exists());
+        assert!(storage.work_file(SETTINGS_FILE).exists());
+
+        std::fs::remove_dir_all(config_dir.parent().unwrap()).unwrap();
+    }
+}
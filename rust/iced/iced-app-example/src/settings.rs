@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::app::App;
+use crate::message::{BrowserOption, Role, ViewTransition};
+use crate::styles::skin::Skin;
+use crate::styles::theme::{ThemeChoice, ThemeType};
+use crate::views::ViewId;
+
+/// Current version of the settings file schema
+///
+/// Fields added since version 1 are optional, so older files load as they
+/// are; bump this only for changes that need a migration.
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Default file name used by the config menu export/import actions
+pub const SETTINGS_FILE: &str = "icedapp-settings.json";
+
+/// Versioned snapshot of everything worth sharing as a demo preset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    pub version: u32,
+    pub theme: ThemeType,
+    /// Saved custom theme used instead of `theme` when it still exists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_theme: Option<String>,
+    pub view: ViewId,
+    /// Optional so presets written without it still load
+    #[serde(default)]
+    pub transition: ViewTransition,
+    #[serde(default)]
+    pub skin: Skin,
+    pub form: FormSettings,
+    pub browser_form: BrowserFormSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormSettings {
+    pub name: String,
+    pub email: String,
+    pub age: u8,
+    pub experience: f32,
+    pub selected_role: Option<Role>,
+}
+
+/// Browser form fields - the password is deliberately never written to disk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowserFormSettings {
+    pub text: String,
+    pub slider_value: f32,
+    pub checkbox: bool,
+    pub selected_option: Option<BrowserOption>,
+}
+
+#[derive(Debug)]
+pub enum SettingsError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    UnsupportedVersion(u32),
+    Invalid(String),
+}
+
+impl std::fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsError::Io(error) => write!(f, "cannot access settings file: {error}"),
+            SettingsError::Parse(error) => write!(f, "malformed settings file: {error}"),
+            SettingsError::UnsupportedVersion(version) => write!(
+                f,
+                "settings version {version} is newer than supported version {SETTINGS_VERSION}"
+            ),
+            SettingsError::Invalid(reason) => write!(f, "invalid settings: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+impl From<std::io::Error> for SettingsError {
+    fn from(error: std::io::Error) -> Self {
+        SettingsError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SettingsError {
+    fn from(error: serde_json::Error) -> Self {
+        SettingsError::Parse(error)
+    }
+}
+
+impl Settings {
+    /// Capture the shareable part of the app state
+    pub fn from_app(app: &App) -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            theme: ThemeType::from_theme(&app.selected_theme).unwrap_or(ThemeType::Light),
+            custom_theme: match ThemeChoice::from_theme(&app.selected_theme, &app.custom_themes) {
+                Some(ThemeChoice::Custom(name)) => Some(name),
+                _ => None,
+            },
+            view: app.current_view,
+            transition: app.transition_style,
+            skin: app.skin,
+            form: FormSettings {
+                name: app.name.clone(),
+                email: app.email.clone(),
+                age: app.age,
+                experience: app.experience,
+                selected_role: app.selected_role,
+            },
+            browser_form: BrowserFormSettings {
+                text: app.browser_text.clone(),
+                slider_value: app.browser_slider_value,
+                checkbox: app.browser_checkbox,
+                selected_option: app.browser_selected_option,
+            },
+        }
+    }
+
+    /// Write the settings back into the app
+    pub fn apply(self, app: &mut App) {
+        app.selected_theme = self
+            .custom_theme
+            .and_then(|name| ThemeChoice::Custom(name).to_theme(&app.custom_themes))
+            .unwrap_or_else(|| self.theme.to_theme());
+        app.transition_style = self.transition;
+        app.skin = self.skin;
+        if app.current_view != self.view {
+            app.previous_view = app.current_view;
+            app.current_view = self.view;
+        }
+
+        app.name = self.form.name;
+        app.email = self.form.email;
+        app.age = self.form.age;
+        app.experience = self.form.experience;
+        app.selected_role = self.form.selected_role;
+
+        app.browser_text = self.browser_form.text;
+        app.browser_slider_value = self.browser_form.slider_value;
+        app.browser_checkbox = self.browser_form.checkbox;
+        app.browser_selected_option = self.browser_form.selected_option;
+    }
+
+    pub fn to_json(&self) -> Result<String, SettingsError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse and validate a settings document
+    pub fn from_json(json: &str) -> Result<Self, SettingsError> {
+        Self::from_value(serde_json::from_str(json)?)
+    }
+
+    /// Validate an already parsed settings document
+    pub fn from_value(value: serde_json::Value) -> Result<Self, SettingsError> {
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| SettingsError::Invalid("missing \"version\" field".to_string()))?;
+        let version = u32::try_from(version)
+            .map_err(|_| SettingsError::Invalid(format!("version {version} is out of range")))?;
+
+        if version > SETTINGS_VERSION {
+            return Err(SettingsError::UnsupportedVersion(version));
+        }
+        if version == 0 {
+            return Err(SettingsError::Invalid("version 0 does not exist".to_string()));
+        }
+
+        let settings: Settings = serde_json::from_value(value)?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SettingsError> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SettingsError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Reject values the UI controls could never produce
+    fn validate(&self) -> Result<(), SettingsError> {
+        if self.form.age > 100 {
+            return Err(SettingsError::Invalid(format!(
+                "age {} is out of range 0-100",
+                self.form.age
+            )));
+        }
+        if !(0.0..=20.0).contains(&self.form.experience) {
+            return Err(SettingsError::Invalid(format!(
+                "experience {} is out of range 0-20",
+                self.form.experience
+            )));
+        }
+        if !(0.0..=100.0).contains(&self.browser_form.slider_value) {
+            return Err(SettingsError::Invalid(format!(
+                "browser slider value {} is out of range 0-100",
+                self.browser_form.slider_value
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Location of the settings file used by the config menu
+pub fn settings_path() -> PathBuf {
+    std::env::current_dir()
+        .unwrap_or_default()
+        .join(SETTINGS_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset() -> Settings {
+        Settings::from_app(&App {
+            name: "Ada".to_string(),
+            age: 36,
+            browser_slider_value: 75.0,
+            ..App::default()
+        })
+    }
+
+    #[test]
+    fn settings_round_trip_through_json() {
+        let settings = preset();
+        let json = settings.to_json().unwrap();
+        assert_eq!(Settings::from_json(&json).unwrap(), settings);
+    }
+
+    #[test]
+    fn settings_round_trip_through_a_file() {
+        let path = std::env::temp_dir().join(format!("icedapp-settings-{}.json", std::process::id()));
+        let settings = preset();
+
+        settings.save(&path).unwrap();
+        let loaded = Settings::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), settings);
+    }
+
+    /// `json` with `key` replaced by `value`, or removed for `Null`
+    fn with(key: &str, value: serde_json::Value) -> String {
+        let mut json = serde_json::to_value(preset()).unwrap();
+        let object = json.as_object_mut().unwrap();
+        if value.is_null() {
+            object.remove(key);
+        } else {
+            object.insert(key.to_string(), value);
+        }
+        json.to_string()
+    }
+
+    #[test]
+    fn version_must_be_present_and_supported() {
+        assert!(matches!(
+            Settings::from_json(&with("version", serde_json::Value::Null)),
+            Err(SettingsError::Invalid(_))
+        ));
+        assert!(matches!(
+            Settings::from_json(&with("version", serde_json::json!(0))),
+            Err(SettingsError::Invalid(_))
+        ));
+        assert!(matches!(
+            Settings::from_json(&with("version", serde_json::json!(SETTINGS_VERSION + 1))),
+            Err(SettingsError::UnsupportedVersion(version)) if version == SETTINGS_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn version_beyond_u32_is_rejected_not_truncated() {
+        // Truncated to 32 bits this would read as version 1
+        let version = (1_u64 << 32) + 1;
+        assert!(matches!(
+            Settings::from_json(&with("version", serde_json::json!(version))),
+            Err(SettingsError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn fields_added_after_version_1_are_optional() {
+        let mut json = serde_json::to_value(preset()).unwrap();
+        for key in ["transition", "skin", "custom_theme"] {
+            json.as_object_mut().unwrap().remove(key);
+        }
+
+        let loaded = Settings::from_json(&json.to_string()).unwrap();
+        assert_eq!(loaded.transition, ViewTransition::default());
+        assert_eq!(loaded.skin, Skin::default());
+        assert_eq!(loaded.custom_theme, None);
+    }
+
+    #[test]
+    fn out_of_range_values_are_invalid() {
+        let mut settings = preset();
+        settings.form.age = 101;
+        assert!(matches!(settings.validate(), Err(SettingsError::Invalid(_))));
+
+        let mut settings = preset();
+        settings.form.experience = -1.0;
+        assert!(matches!(settings.validate(), Err(SettingsError::Invalid(_))));
+
+        let mut settings = preset();
+        settings.browser_form.slider_value = 150.0;
+        let json = settings.to_json().unwrap();
+        assert!(matches!(Settings::from_json(&json), Err(SettingsError::Invalid(_))));
+    }
+
+    #[test]
+    fn malformed_json_is_a_parse_error() {
+        assert!(matches!(Settings::from_json("{"), Err(SettingsError::Parse(_))));
+        assert!(matches!(
+            Settings::from_json(&with("form", serde_json::json!("Ada"))),
+            Err(SettingsError::Parse(_))
+        ));
+    }
+}
@@ -1,5 +1,87 @@
-use iced::widget::button;
+use iced::widget::{button, container, text_input};
 use iced::{Background, Border, Color, Shadow, Theme, Vector};
+use std::f32::consts::FRAC_PI_2;
+
+use crate::styles::color::{is_dark, mix, over, readable, with_alpha};
+
+/// Windows 7 colors worked out from a theme's palette
+///
+/// The glossy face is the background with a shine on top, tinted with the
+/// primary color, and gets bluer (or whatever primary is) on hover. Colors
+/// blend perceptually, so the same amounts work on light and dark themes;
+/// dark ones just get a fainter shine to keep their buttons dark.
+struct Aero {
+    /// Top, middle and bottom of each gradient
+    face: [Color; 3],
+    hovered: [Color; 3],
+    pressed: [Color; 3],
+    disabled: [Color; 3],
+    border: Color,
+    /// Border and glow around hovered and pressed buttons
+    accent: Color,
+    disabled_border: Color,
+    text: Color,
+    disabled_text: Color,
+    shadow: Color,
+    /// Window body behind panels, faintly tinted like the Aero glass
+    window: Color,
+    /// Text box background
+    field: Color,
+    /// Etched line around group boxes
+    group: Color,
+    page_text: Color,
+}
+
+impl Aero {
+    fn new(theme: &Theme) -> Self {
+        let palette = theme.palette();
+        let (background, primary, text) = (palette.background, palette.primary, palette.text);
+        let is_dark = is_dark(background);
+        // White and black fade with the page while views cross-fade
+        let (white, black) = (with_alpha(Color::WHITE, background.a), with_alpha(Color::BLACK, background.a));
+
+        let (shine, tint) = if is_dark { (0.08, 0.1) } else { (0.6, 0.1) };
+        let top = mix(background, white, shine);
+        let face = [top, mix(top, primary, tint), mix(background, primary, tint * 2.0)];
+        let disabled = mix(background, text, 0.08);
+
+        Aero {
+            face,
+            hovered: [
+                mix(top, primary, tint),
+                mix(top, primary, tint * 2.5),
+                mix(background, primary, tint * 4.0),
+            ],
+            // Darker at the top, like the light is blocked
+            pressed: [
+                mix(background, primary, tint * 3.5),
+                mix(background, primary, tint * 3.0),
+                mix(background, primary, tint * 2.0),
+            ],
+            disabled: [disabled, mix(background, text, 0.11), mix(background, text, 0.15)],
+            border: mix(mix(text, primary, 0.3), background, 0.4),
+            accent: mix(primary, text, 0.15),
+            disabled_border: mix(background, text, 0.35),
+            text: readable(face[1], text),
+            disabled_text: mix(background, text, 0.45),
+            shadow: with_alpha(black, background.a * if is_dark { 0.4 } else { 0.15 }),
+            // Primary-colored glass laid over the background
+            window: over(with_alpha(primary, primary.a * tint / 2.0), background),
+            field: if is_dark { mix(background, black, 0.25) } else { white },
+            group: mix(background, text, 0.25),
+            page_text: text,
+        }
+    }
+}
+
+/// Top-to-bottom gradient through evenly spaced stops
+fn vertical_gradient(stops: [Color; 3]) -> Background {
+    let mut linear = iced::gradient::Linear::new(iced::Radians(FRAC_PI_2));
+    for (index, color) in stops.into_iter().enumerate() {
+        linear = linear.add_stop(index as f32 / 2.0, color);
+    }
+    Background::Gradient(iced::Gradient::Linear(linear))
+}
 
 /// Windows 7 style button with animated graying effect
 pub fn windows_7_button_style_with_gray(
@@ -7,63 +89,24 @@ pub fn windows_7_button_style_with_gray(
     status: button::Status,
     gray_amount: f32,
 ) -> button::Style {
-    let _palette = theme.palette();
-
-    // Interpolate between normal and disabled colors based on gray_amount
-    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    let aero = Aero::new(theme);
 
     match status {
         button::Status::Active => {
-            let normal_top = Color::from_rgb(0.95, 0.96, 0.98);
-            let normal_mid = Color::from_rgb(0.88, 0.91, 0.95);
-            let normal_bot = Color::from_rgb(0.82, 0.86, 0.92);
-            let gray_top = Color::from_rgb(0.92, 0.92, 0.92);
-            let gray_bot = Color::from_rgb(0.85, 0.85, 0.85);
+            let fade = |from: Color, to: Color| mix(from, to, gray_amount);
 
             button::Style {
-                background: Some(Background::Gradient(iced::Gradient::Linear(
-                    iced::gradient::Linear::new(iced::Radians(1.5708))
-                        .add_stop(
-                            0.0,
-                            Color::from_rgb(
-                                lerp(normal_top.r, gray_top.r, gray_amount),
-                                lerp(normal_top.g, gray_top.g, gray_amount),
-                                lerp(normal_top.b, gray_top.b, gray_amount),
-                            ),
-                        )
-                        .add_stop(
-                            0.5,
-                            Color::from_rgb(
-                                lerp(normal_mid.r, (gray_top.r + gray_bot.r) / 2.0, gray_amount),
-                                lerp(normal_mid.g, (gray_top.g + gray_bot.g) / 2.0, gray_amount),
-                                lerp(normal_mid.b, (gray_top.b + gray_bot.b) / 2.0, gray_amount),
-                            ),
-                        )
-                        .add_stop(
-                            1.0,
-                            Color::from_rgb(
-                                lerp(normal_bot.r, gray_bot.r, gray_amount),
-                                lerp(normal_bot.g, gray_bot.g, gray_amount),
-                                lerp(normal_bot.b, gray_bot.b, gray_amount),
-                            ),
-                        ),
-                ))),
+                background: Some(vertical_gradient(std::array::from_fn(|index| {
+                    fade(aero.face[index], aero.disabled[index])
+                }))),
                 border: Border {
-                    color: Color::from_rgb(
-                        lerp(0.44, 0.65, gray_amount),
-                        lerp(0.50, 0.65, gray_amount),
-                        lerp(0.60, 0.65, gray_amount),
-                    ),
+                    color: fade(aero.border, aero.disabled_border),
                     width: 1.0,
                     radius: 4.0.into(),
                 },
-                text_color: Color::from_rgb(
-                    lerp(0.1, 0.6, gray_amount),
-                    lerp(0.1, 0.6, gray_amount),
-                    lerp(0.1, 0.6, gray_amount),
-                ),
+                text_color: fade(aero.text, aero.disabled_text),
                 shadow: Shadow {
-                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.15 * (1.0 - gray_amount)),
+                    color: with_alpha(aero.shadow, aero.shadow.a * (1.0 - gray_amount)),
                     offset: Vector::new(0.0, 1.0),
                     blur_radius: 2.0,
                 },
@@ -76,46 +119,36 @@ pub fn windows_7_button_style_with_gray(
 
 /// Standard Windows 7 style button
 pub fn windows_7_button_style(theme: &Theme, status: button::Status) -> button::Style {
-    let _palette = theme.palette();
+    let aero = Aero::new(theme);
 
     match status {
         button::Status::Active => button::Style {
-            // Windows 7 uses a subtle gradient - we'll use a light blue-gray
-            background: Some(Background::Gradient(iced::Gradient::Linear(
-                iced::gradient::Linear::new(iced::Radians(1.5708)) // 90 degrees (top to bottom)
-                    .add_stop(0.0, Color::from_rgb(0.95, 0.96, 0.98)) // Light top
-                    .add_stop(0.5, Color::from_rgb(0.88, 0.91, 0.95)) // Mid gradient
-                    .add_stop(1.0, Color::from_rgb(0.82, 0.86, 0.92)), // Darker bottom
-            ))),
+            // Windows 7 uses a subtle gradient, light at the top
+            background: Some(vertical_gradient(aero.face)),
             border: Border {
-                color: Color::from_rgb(0.44, 0.50, 0.60), // Subtle gray-blue border
+                color: aero.border,
                 width: 1.0,
                 radius: 4.0.into(), // Rounded corners like Windows 7
             },
-            text_color: Color::from_rgb(0.1, 0.1, 0.1), // Dark gray text
+            text_color: aero.text,
             shadow: Shadow {
-                color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
+                color: aero.shadow,
                 offset: Vector::new(0.0, 1.0),
                 blur_radius: 2.0,
             },
             snap: false,
         },
         button::Status::Hovered => button::Style {
-            // Brighter gradient on hover - Windows 7 signature light blue glow
-            background: Some(Background::Gradient(iced::Gradient::Linear(
-                iced::gradient::Linear::new(iced::Radians(1.5708))
-                    .add_stop(0.0, Color::from_rgb(0.92, 0.95, 0.99)) // Lighter blue-white
-                    .add_stop(0.5, Color::from_rgb(0.82, 0.90, 0.98)) // Light blue
-                    .add_stop(1.0, Color::from_rgb(0.70, 0.84, 0.97)), // Windows 7 blue
-            ))),
+            // Windows 7 signature glow, in the theme's primary color
+            background: Some(vertical_gradient(aero.hovered)),
             border: Border {
-                color: Color::from_rgb(0.26, 0.47, 0.69), // Stronger blue border on hover
+                color: aero.accent,
                 width: 1.0,
                 radius: 4.0.into(),
             },
-            text_color: Color::from_rgb(0.05, 0.05, 0.05),
+            text_color: aero.text,
             shadow: Shadow {
-                color: Color::from_rgba(0.26, 0.47, 0.69, 0.4), // Blue glow
+                color: with_alpha(aero.accent, aero.accent.a * 0.4),
                 offset: Vector::new(0.0, 0.0),
                 blur_radius: 6.0,
             },
@@ -123,40 +156,98 @@ pub fn windows_7_button_style(theme: &Theme, status: button::Status) -> button::
         },
         button::Status::Pressed => button::Style {
             // Darker, inverted gradient when pressed
-            background: Some(Background::Gradient(iced::Gradient::Linear(
-                iced::gradient::Linear::new(iced::Radians(1.5708))
-                    .add_stop(0.0, Color::from_rgb(0.70, 0.78, 0.88)) // Darker top
-                    .add_stop(0.5, Color::from_rgb(0.76, 0.83, 0.91)) // Mid
-                    .add_stop(1.0, Color::from_rgb(0.82, 0.88, 0.94)), // Lighter bottom (inverted)
-            ))),
+            background: Some(vertical_gradient(aero.pressed)),
             border: Border {
-                color: Color::from_rgb(0.26, 0.47, 0.69),
+                color: aero.accent,
                 width: 1.0,
                 radius: 4.0.into(),
             },
-            text_color: Color::from_rgb(0.1, 0.1, 0.1),
+            text_color: aero.text,
             shadow: Shadow::default(), // No shadow when pressed
             snap: false,
         },
         button::Status::Disabled => button::Style {
             // Grayed out appearance
-            background: Some(Background::Gradient(iced::Gradient::Linear(
-                iced::gradient::Linear::new(iced::Radians(1.5708))
-                    .add_stop(0.0, Color::from_rgb(0.92, 0.92, 0.92))
-                    .add_stop(1.0, Color::from_rgb(0.85, 0.85, 0.85)),
-            ))),
+            background: Some(vertical_gradient(aero.disabled)),
             border: Border {
-                color: Color::from_rgb(0.65, 0.65, 0.65),
+                color: aero.disabled_border,
                 width: 1.0,
                 radius: 4.0.into(),
             },
-            text_color: Color::from_rgb(0.6, 0.6, 0.6), // Grayed text
+            text_color: aero.disabled_text,
             shadow: Shadow::default(),
             snap: false,
         },
     }
 }
 
+/// Windows 7 text box, outlined in the accent color while hovered or focused
+pub fn windows_7_input_style(theme: &Theme, status: text_input::Status) -> text_input::Style {
+    let aero = Aero::new(theme);
+
+    let (background, border) = match status {
+        text_input::Status::Active => (aero.field, aero.border),
+        text_input::Status::Hovered | text_input::Status::Focused { .. } => (aero.field, aero.accent),
+        text_input::Status::Disabled => (aero.disabled[0], aero.disabled_border),
+    };
+    let value = if status == text_input::Status::Disabled {
+        aero.disabled_text
+    } else {
+        aero.page_text
+    };
+
+    text_input::Style {
+        background: Background::Color(background),
+        border: Border {
+            color: border,
+            width: 1.0,
+            radius: 2.0.into(),
+        },
+        icon: value,
+        placeholder: aero.disabled_text,
+        value,
+        selection: with_alpha(aero.accent, aero.accent.a * 0.35),
+    }
+}
+
+/// Windows 7 dialog body
+pub fn windows_7_panel_style(theme: &Theme) -> container::Style {
+    let aero = Aero::new(theme);
+
+    container::Style {
+        background: Some(Background::Color(aero.window)),
+        border: Border {
+            color: aero.border,
+            width: 1.0,
+            radius: 6.0.into(),
+        },
+        text_color: Some(aero.page_text),
+        shadow: Shadow {
+            color: aero.shadow,
+            offset: Vector::new(0.0, 2.0),
+            blur_radius: 8.0,
+        },
+        snap: false,
+    }
+}
+
+/// Windows 7 group box - a thin rounded outline
+pub fn windows_7_group_style(theme: &Theme) -> container::Style {
+    let aero = Aero::new(theme);
+
+    container::Style {
+        background: None,
+        border: Border {
+            color: aero.group,
+            width: 1.0,
+            radius: 3.0.into(),
+        },
+        text_color: Some(aero.page_text),
+        shadow: Shadow::default(),
+        snap: false,
+    }
+}
+
 /// Apply opacity to button style for fade animations
 pub fn apply_opacity_to_button_style(
     mut style: button::Style,
@@ -164,46 +255,51 @@ pub fn apply_opacity_to_button_style(
 ) -> button::Style {
     // Apply opacity to all colors in the button style
     if let Some(Background::Color(color)) = style.background {
-        style.background = Some(Background::Color(Color::from_rgba(
-            color.r,
-            color.g,
-            color.b,
-            opacity,
-        )));
+        style.background = Some(Background::Color(with_alpha(color, opacity)));
     } else if let Some(Background::Gradient(gradient)) = style.background {
         // For gradients, we need to apply opacity to each stop
         let iced::Gradient::Linear(linear) = gradient;
         let mut new_linear = iced::gradient::Linear::new(linear.angle);
-        for stop_option in linear.stops.iter() {
-            if let Some(stop) = stop_option {
-                new_linear = new_linear.add_stop(
-                    stop.offset,
-                    Color::from_rgba(stop.color.r, stop.color.g, stop.color.b, opacity),
-                );
-            }
+        for stop in linear.stops.iter().flatten() {
+            new_linear = new_linear.add_stop(stop.offset, with_alpha(stop.color, opacity));
         }
         style.background = Some(Background::Gradient(iced::Gradient::Linear(new_linear)));
     }
 
     // Apply opacity to text and border
-    style.text_color = Color::from_rgba(
-        style.text_color.r,
-        style.text_color.g,
-        style.text_color.b,
-        opacity,
-    );
-    style.border.color = Color::from_rgba(
-        style.border.color.r,
-        style.border.color.g,
-        style.border.color.b,
-        opacity,
-    );
-    style.shadow.color = Color::from_rgba(
-        style.shadow.color.r,
-        style.shadow.color.g,
-        style.shadow.color.b,
-        style.shadow.color.a * opacity,
-    );
+    style.text_color = with_alpha(style.text_color, opacity);
+    style.border.color = with_alpha(style.border.color, opacity);
+    style.shadow.color = with_alpha(style.shadow.color, style.shadow.color.a * opacity);
 
     style
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::styles::color::{contrast_ratio, WCAG_AA};
+
+    #[test]
+    fn buttons_follow_the_theme() {
+        let light = Aero::new(&Theme::Light);
+        let night = Aero::new(&Theme::TokyoNight);
+        assert_ne!(light.face, night.face);
+
+        for theme in [Theme::Light, Theme::Dracula, Theme::TokyoNight, Theme::SolarizedLight] {
+            let aero = Aero::new(&theme);
+            assert!(contrast_ratio(aero.text, aero.face[1]) >= WCAG_AA, "{theme}");
+            // Dark themes keep their buttons dark
+            assert_eq!(is_dark(aero.face[1]), is_dark(theme.palette().background), "{theme}");
+        }
+    }
+
+    #[test]
+    fn graying_ends_at_the_disabled_look() {
+        let theme = Theme::Nord;
+        let grayed = windows_7_button_style_with_gray(&theme, button::Status::Active, 1.0);
+        let disabled = windows_7_button_style(&theme, button::Status::Disabled);
+
+        assert_eq!(grayed.text_color.into_rgba8(), disabled.text_color.into_rgba8());
+        assert_eq!(grayed.border.color.into_rgba8(), disabled.border.color.into_rgba8());
+    }
+}
@@ -0,0 +1,461 @@
+use iced::Color;
+
+/// Contrast ratio WCAG AA asks of normal-sized text
+pub const WCAG_AA: f32 = 4.5;
+
+/// Hue in degrees, with saturation and lightness from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    pub alpha: f32,
+}
+
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        let max = color.r.max(color.g).max(color.b);
+        let min = color.r.min(color.g).min(color.b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+
+        if delta == 0.0 {
+            return Hsl {
+                hue: 0.0,
+                saturation: 0.0,
+                lightness,
+                alpha: color.a,
+            };
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == color.r {
+            (color.g - color.b) / delta
+        } else if max == color.g {
+            (color.b - color.r) / delta + 2.0
+        } else {
+            (color.r - color.g) / delta + 4.0
+        };
+
+        Hsl {
+            hue: (hue * 60.0).rem_euclid(360.0),
+            saturation,
+            lightness,
+            alpha: color.a,
+        }
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        let chroma = (1.0 - (2.0 * hsl.lightness - 1.0).abs()) * hsl.saturation;
+        let sector = hsl.hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = hsl.lightness - chroma / 2.0;
+
+        Color::from_rgba(r + m, g + m, b + m, hsl.alpha)
+    }
+}
+
+/// Perceptual lightness (0 to 1), chroma and hue in degrees
+///
+/// Equal steps in lightness look equally large whatever the hue, unlike
+/// HSL, which makes it the space to lighten, darken and blend in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub lightness: f32,
+    pub chroma: f32,
+    pub hue: f32,
+    pub alpha: f32,
+}
+
+impl From<Color> for Oklch {
+    fn from(color: Color) -> Self {
+        let Oklab { l, a, b, alpha } = Oklab::from(color);
+
+        Oklch {
+            lightness: l,
+            chroma: a.hypot(b),
+            hue: b.atan2(a).to_degrees().rem_euclid(360.0),
+            alpha,
+        }
+    }
+}
+
+impl From<Oklch> for Color {
+    /// Colors sRGB cannot show lose chroma until they fit, keeping their
+    /// lightness and hue
+    fn from(oklch: Oklch) -> Self {
+        // Black and white have no hue to keep
+        let chroma = if oklch.lightness > 0.0 && oklch.lightness < 1.0 {
+            oklch.chroma
+        } else {
+            0.0
+        };
+        let to_oklab = |chroma: f32| {
+            let (sin, cos) = oklch.hue.to_radians().sin_cos();
+            Oklab {
+                l: oklch.lightness.clamp(0.0, 1.0),
+                a: chroma * cos,
+                b: chroma * sin,
+                alpha: oklch.alpha,
+            }
+        };
+
+        let full = to_oklab(chroma).to_linear();
+        if in_gamut(full) {
+            return from_linear(full);
+        }
+
+        let (mut low, mut high) = (0.0, chroma);
+        for _ in 0..20 {
+            let chroma = (low + high) / 2.0;
+            if in_gamut(to_oklab(chroma).to_linear()) {
+                low = chroma;
+            } else {
+                high = chroma;
+            }
+        }
+        from_linear(to_oklab(low).to_linear())
+    }
+}
+
+/// Cartesian form of [`Oklch`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Oklab {
+    l: f32,
+    a: f32,
+    b: f32,
+    alpha: f32,
+}
+
+impl From<Color> for Oklab {
+    // https://bottosson.github.io/posts/oklab/
+    fn from(color: Color) -> Self {
+        let [r, g, b, alpha] = color.into_linear();
+
+        let l = (0.41222146 * r + 0.53633255 * g + 0.051445995 * b).cbrt();
+        let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+        let s = (0.08830246 * r + 0.28171885 * g + 0.6299787 * b).cbrt();
+
+        Oklab {
+            l: 0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+            a: 1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            b: 0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+            alpha,
+        }
+    }
+}
+
+impl Oklab {
+    /// Linear RGB, possibly outside 0 to 1
+    fn to_linear(self) -> [f32; 4] {
+        let l = (self.l + 0.39633778 * self.a + 0.21580376 * self.b).powi(3);
+        let m = (self.l - 0.105561346 * self.a - 0.06385417 * self.b).powi(3);
+        let s = (self.l - 0.08948418 * self.a - 1.2914855 * self.b).powi(3);
+
+        [
+            4.0767417 * l - 3.3077116 * m + 0.23096994 * s,
+            -1.268438 * l + 2.6097574 * m - 0.34131938 * s,
+            -0.0041960863 * l - 0.7034186 * m + 1.7076147 * s,
+            self.alpha,
+        ]
+    }
+}
+
+/// Rounding leaves in-gamut colors a hair outside 0 to 1
+fn in_gamut([r, g, b, _]: [f32; 4]) -> bool {
+    const EPSILON: f32 = 1e-4;
+    [r, g, b].iter().all(|channel| (-EPSILON..=1.0 + EPSILON).contains(channel))
+}
+
+fn from_linear([r, g, b, a]: [f32; 4]) -> Color {
+    Color::from_linear_rgba(r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0), a)
+}
+
+/// Blend `amount` (0 to 1) of the way from `from` to `to`
+///
+/// Blends in Oklab, so halfway between black and white looks halfway and
+/// blends of saturated colors do not go muddy the way sRGB blends do.
+pub fn mix(from: Color, to: Color, amount: f32) -> Color {
+    let amount = amount.clamp(0.0, 1.0);
+    let (from, to) = (Oklab::from(from), Oklab::from(to));
+    let lerp = |a: f32, b: f32| a + (b - a) * amount;
+
+    from_linear(
+        Oklab {
+            l: lerp(from.l, to.l),
+            a: lerp(from.a, to.a),
+            b: lerp(from.b, to.b),
+            alpha: lerp(from.alpha, to.alpha),
+        }
+        .to_linear(),
+    )
+}
+
+/// Raise the perceptual lightness by `amount`, up to white
+pub fn lighten(color: Color, amount: f32) -> Color {
+    let oklch = Oklch::from(color);
+    Oklch {
+        lightness: (oklch.lightness + amount).min(1.0),
+        ..oklch
+    }
+    .into()
+}
+
+/// Lower the perceptual lightness by `amount`, down to black
+pub fn darken(color: Color, amount: f32) -> Color {
+    lighten(color, -amount)
+}
+
+pub fn with_alpha(color: Color, alpha: f32) -> Color {
+    Color { a: alpha, ..color }
+}
+
+/// `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`, the `#` being optional
+///
+/// Safe for any text a user types or a file holds; `Color`'s own parser
+/// slices by byte and panics inside multi-byte characters.
+pub fn parse_hex(text: &str) -> Option<Color> {
+    let digits: Vec<u8> = text
+        .strip_prefix('#')
+        .unwrap_or(text)
+        .chars()
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()?;
+    let channels: Vec<u8> = match digits.len() {
+        3 | 4 => digits.iter().map(|digit| digit * 17).collect(),
+        6 | 8 => digits.chunks(2).map(|pair| pair[0] * 16 + pair[1]).collect(),
+        _ => return None,
+    };
+    let alpha = channels.get(3).map_or(1.0, |alpha| f32::from(*alpha) / 255.0);
+    Some(Color::from_rgba8(channels[0], channels[1], channels[2], alpha))
+}
+
+/// `top` drawn over `bottom` (source-over compositing), blended on the
+/// gamma-encoded sRGB channels the way CSS blends colors rather than in
+/// linear light
+pub fn over(top: Color, bottom: Color) -> Color {
+    let alpha = top.a + bottom.a * (1.0 - top.a);
+    if alpha == 0.0 {
+        return Color::TRANSPARENT;
+    }
+    let blend = |top_channel: f32, bottom_channel: f32| {
+        (top_channel * top.a + bottom_channel * bottom.a * (1.0 - top.a)) / alpha
+    };
+
+    Color::from_rgba(blend(top.r, bottom.r), blend(top.g, bottom.g), blend(top.b, bottom.b), alpha)
+}
+
+/// WCAG relative luminance, from 0 for black to 1 for white; alpha is
+/// ignored
+pub fn relative_luminance(color: Color) -> f32 {
+    let [r, g, b, _] = color.into_linear();
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// WCAG contrast ratio, from 1 for equal colors to 21 for black on white
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Whether light text reads better than dark text on `color`
+pub fn is_dark(color: Color) -> bool {
+    Oklch::from(color).lightness < 0.6
+}
+
+/// `text` if it meets [`WCAG_AA`] on `background`, otherwise the nearest
+/// lighter or darker shade of it that does, or else black or white at the
+/// text's alpha
+pub fn readable(background: Color, text: Color) -> Color {
+    if contrast_ratio(background, text) >= WCAG_AA {
+        return text;
+    }
+
+    let adjust = if is_dark(background) { lighten } else { darken };
+    for step in 1..=5 {
+        let candidate = adjust(text, step as f32 * 0.1);
+        if contrast_ratio(background, candidate) >= WCAG_AA {
+            return candidate;
+        }
+    }
+
+    let fallback = if contrast_ratio(background, Color::WHITE) >= contrast_ratio(background, Color::BLACK) {
+        Color::WHITE
+    } else {
+        Color::BLACK
+    };
+    with_alpha(fallback, text.a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Color, b: Color) {
+        let distance = [a.r - b.r, a.g - b.g, a.b - b.b, a.a - b.a]
+            .iter()
+            .fold(0.0f32, |max, channel| max.max(channel.abs()));
+        assert!(distance < 1e-3, "{a:?} != {b:?}");
+    }
+
+    fn rgb8(hex: &str) -> Color {
+        hex.parse().unwrap()
+    }
+
+    #[test]
+    fn hex_colors_parse_in_every_length() {
+        assert_eq!(parse_hex("#123456"), Some(Color::from_rgb8(0x12, 0x34, 0x56)));
+        assert_eq!(parse_hex("123456"), Some(Color::from_rgb8(0x12, 0x34, 0x56)));
+        assert_eq!(parse_hex("#fA0"), Some(Color::from_rgb8(0xff, 0xaa, 0x00)));
+        assert_eq!(parse_hex("#ff000080"), Some(Color::from_rgba8(0xff, 0, 0, 128.0 / 255.0)));
+        assert_eq!(parse_hex("#f008"), Some(Color::from_rgba8(0xff, 0, 0, 136.0 / 255.0)));
+    }
+
+    #[test]
+    fn anything_else_is_not_a_hex_color() {
+        for text in ["", "#", "#12", "#12345", "#1234567", "#12345g", "##123", " #123", "#1é3", "é"] {
+            assert_eq!(parse_hex(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn hsl_matches_known_values() {
+        let red = Hsl::from(Color::from_rgb(1.0, 0.0, 0.0));
+        assert_eq!((red.hue, red.saturation, red.lightness), (0.0, 1.0, 0.5));
+
+        let teal = Hsl::from(rgb8("#008080"));
+        assert!((teal.hue - 180.0).abs() < 0.1);
+        assert!((teal.lightness - 0.251).abs() < 0.001);
+
+        let gray = Hsl::from(rgb8("#808080"));
+        assert_eq!((gray.hue, gray.saturation), (0.0, 0.0));
+    }
+
+    #[test]
+    fn hsl_round_trips() {
+        for hex in ["#000000", "#ffffff", "#ff0000", "#123456", "#bd93f9", "#f1fa8c", "#7aa2f7"] {
+            let color = rgb8(hex);
+            assert_close(Color::from(Hsl::from(color)), color);
+        }
+        assert_close(
+            Hsl { hue: 420.0, saturation: 1.0, lightness: 0.5, alpha: 1.0 }.into(),
+            Color::from_rgb(1.0, 1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn oklch_matches_known_values() {
+        let white = Oklch::from(Color::WHITE);
+        assert!((white.lightness - 1.0).abs() < 1e-3);
+        assert!(white.chroma < 1e-3);
+
+        // Reference values from the Oklab author's conversion
+        let red = Oklch::from(Color::from_rgb(1.0, 0.0, 0.0));
+        assert!((red.lightness - 0.628).abs() < 1e-3);
+        assert!((red.chroma - 0.2577).abs() < 1e-3);
+        assert!((red.hue - 29.23).abs() < 0.1);
+    }
+
+    #[test]
+    fn oklch_round_trips_and_stays_in_gamut() {
+        for hex in ["#000000", "#ffffff", "#ff0000", "#00ff00", "#123456", "#bd93f9", "#282a36"] {
+            let color = rgb8(hex);
+            assert_close(Color::from(Oklch::from(color)), color);
+        }
+
+        // Far too much chroma for sRGB: it is reduced, lightness kept
+        let vivid = Color::from(Oklch { lightness: 0.7, chroma: 0.5, hue: 150.0, alpha: 1.0 });
+        for channel in [vivid.r, vivid.g, vivid.b] {
+            assert!((0.0..=1.0).contains(&channel));
+        }
+        assert!((Oklch::from(vivid).lightness - 0.7).abs() < 0.01);
+    }
+
+    #[test]
+    fn mixing_is_perceptual() {
+        let (black, white) = (Color::BLACK, Color::WHITE);
+        assert_close(mix(black, white, 0.0), black);
+        assert_close(mix(black, white, 1.0), white);
+        assert_close(mix(black, white, 2.0), white);
+
+        // Halfway looks halfway, unlike linear light's washed-out gray
+        let middle = Oklch::from(mix(black, white, 0.5));
+        assert!((middle.lightness - 0.5).abs() < 1e-3);
+
+        // Alpha blends straight
+        assert!((mix(Color::TRANSPARENT, white, 0.25).a - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn lightening_and_darkening_clamp() {
+        let blue = rgb8("#1e66f5");
+        let lighter = Oklch::from(lighten(blue, 0.1));
+        assert!((lighter.lightness - (Oklch::from(blue).lightness + 0.1)).abs() < 0.01);
+        assert!((lighter.hue - Oklch::from(blue).hue).abs() < 2.0);
+
+        assert_close(lighten(blue, 2.0), Color::WHITE);
+        assert_close(darken(blue, 2.0), Color::BLACK);
+        assert_close(darken(lighten(Color::from_rgb(0.5, 0.5, 0.5), 0.1), 0.1), Color::from_rgb(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn compositing_blends_by_alpha() {
+        let red = Color::from_rgb(1.0, 0.0, 0.0);
+        assert_close(over(red, Color::WHITE), red);
+        assert_close(over(Color::TRANSPARENT, Color::WHITE), Color::WHITE);
+        assert_close(over(with_alpha(red, 0.5), Color::WHITE), Color::from_rgb(1.0, 0.5, 0.5));
+
+        // Two half-transparent layers let a quarter through
+        let stacked = over(with_alpha(red, 0.5), with_alpha(Color::BLACK, 0.5));
+        assert!((stacked.a - 0.75).abs() < 1e-6);
+        assert_close(Color { a: 1.0, ..stacked }, Color::from_rgb(2.0 / 3.0, 0.0, 0.0));
+
+        assert_eq!(over(Color::TRANSPARENT, Color::TRANSPARENT), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn contrast_ratios_match_wcag() {
+        assert!((contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 1e-3);
+        assert!((contrast_ratio(Color::WHITE, Color::BLACK) - 21.0).abs() < 1e-3);
+        assert!((contrast_ratio(rgb8("#123456"), rgb8("#123456")) - 1.0).abs() < 1e-6);
+
+        // The classic borderline: #777 on white just misses AA
+        let gray = contrast_ratio(rgb8("#777777"), Color::WHITE);
+        assert!((gray - 4.48).abs() < 0.01);
+        assert!(contrast_ratio(rgb8("#767676"), Color::WHITE) >= WCAG_AA);
+
+        assert!((relative_luminance(Color::WHITE) - 1.0).abs() < 1e-6);
+        assert_eq!(relative_luminance(Color::BLACK), 0.0);
+    }
+
+    #[test]
+    fn readable_text_keeps_its_hue_when_it_can() {
+        let background = Color::WHITE;
+        let text = rgb8("#1e66f5");
+        assert_eq!(readable(background, text), text);
+
+        // Too light to read: darkened, not replaced
+        let pale = rgb8("#a6c8ff");
+        let fixed = readable(background, pale);
+        assert!(contrast_ratio(background, fixed) >= WCAG_AA);
+        assert_ne!(fixed, Color::BLACK);
+        assert!((Oklch::from(fixed).hue - Oklch::from(pale).hue).abs() < 5.0);
+
+        for background in [rgb8("#282a36"), rgb8("#fdf6e3"), rgb8("#808080")] {
+            assert!(contrast_ratio(background, readable(background, background)) >= WCAG_AA);
+        }
+        assert!(is_dark(rgb8("#282a36")));
+        assert!(!is_dark(rgb8("#fdf6e3")));
+    }
+}
@@ -2,3 +2,5 @@ pub mod button;
 pub mod theme;
 pub mod web;
 pub mod plain;
+pub mod skin;
+pub mod color;
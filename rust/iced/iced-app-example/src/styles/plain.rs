@@ -1,118 +1,109 @@
 use iced::widget::{button, container, text_input};
 use iced::{Background, Border, Color, Shadow, Theme};
 
+use crate::styles::color::{is_dark, mix, with_alpha};
+
+/// Unstyled HTML controls in a theme's colors: gray shades of the text over
+/// the background, with fields a step lighter than the page on light themes
+/// and a step darker on dark ones
+struct Plain {
+    background: Color,
+    text: Color,
+    field: Color,
+    selection: Color,
+}
+
+impl Plain {
+    fn new(theme: &Theme) -> Self {
+        let palette = theme.palette();
+
+        Plain {
+            background: palette.background,
+            text: palette.text,
+            // Fields fade with the page while views cross-fade
+            field: if is_dark(palette.background) {
+                mix(palette.background, with_alpha(Color::BLACK, palette.background.a), 0.25)
+            } else {
+                with_alpha(Color::WHITE, palette.background.a)
+            },
+            selection: with_alpha(palette.primary, palette.primary.a * 0.35),
+        }
+    }
+
+    /// `amount` of the way from the background to the text color
+    fn gray(&self, amount: f32) -> Color {
+        mix(self.background, self.text, amount)
+    }
+}
+
 /// Plain HTML-style button - minimal styling like unstyled HTML
 pub fn plain_button_style(theme: &Theme, status: button::Status) -> button::Style {
-    let palette = theme.palette();
-    
-    match status {
-        button::Status::Active => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.94, 0.94, 0.94))), // Light gray like default HTML button
-            border: Border {
-                color: Color::from_rgb(0.67, 0.67, 0.67), // Gray border
-                width: 1.0,
-                radius: 2.0.into(), // Minimal rounding
-            },
-            text_color: palette.text,
-            shadow: Shadow::default(), // No shadow
-            snap: false,
-        },
-        button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.88, 0.88, 0.88))), // Slightly darker on hover
-            border: Border {
-                color: Color::from_rgb(0.60, 0.60, 0.60),
-                width: 1.0,
-                radius: 2.0.into(),
-            },
-            text_color: palette.text,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.82, 0.82, 0.82))), // Even darker when pressed
-            border: Border {
-                color: Color::from_rgb(0.50, 0.50, 0.50),
-                width: 1.0,
-                radius: 2.0.into(),
-            },
-            text_color: palette.text,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Disabled => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.94, 0.94, 0.94))),
-            border: Border {
-                color: Color::from_rgb(0.80, 0.80, 0.80),
-                width: 1.0,
-                radius: 2.0.into(),
-            },
-            text_color: Color::from_rgb(0.67, 0.67, 0.67),
-            shadow: Shadow::default(),
-            snap: false,
+    let plain = Plain::new(theme);
+
+    // Face and border get a little darker (or lighter) with each status
+    let (face, border, text_color) = match status {
+        button::Status::Active => (plain.gray(0.06), plain.gray(0.33), plain.text),
+        button::Status::Hovered => (plain.gray(0.12), plain.gray(0.4), plain.text),
+        button::Status::Pressed => (plain.gray(0.18), plain.gray(0.5), plain.text),
+        button::Status::Disabled => (plain.gray(0.06), plain.gray(0.2), plain.gray(0.33)),
+    };
+
+    button::Style {
+        background: Some(Background::Color(face)),
+        border: Border {
+            color: border,
+            width: 1.0,
+            radius: 2.0.into(), // Minimal rounding
         },
+        text_color,
+        shadow: Shadow::default(), // No shadow
+        snap: false,
     }
 }
 
 /// Plain HTML input style - like unstyled text input
 pub fn plain_input_style(theme: &Theme, status: text_input::Status) -> text_input::Style {
-    let palette = theme.palette();
-    
-    match status {
-        text_input::Status::Active => text_input::Style {
-            background: Background::Color(Color::WHITE),
-            border: Border {
-                color: Color::from_rgb(0.67, 0.67, 0.67), // Simple gray border
-                width: 1.0,
-                radius: 0.0.into(), // No rounding - pure HTML style
-            },
-            icon: palette.text,
-            placeholder: Color::from_rgb(0.67, 0.67, 0.67),
-            value: Color::BLACK,
-            selection: Color::from_rgba(0.67, 0.84, 1.0, 0.5), // Light blue selection
-        },
-        text_input::Status::Hovered => text_input::Style {
-            background: Background::Color(Color::WHITE),
-            border: Border {
-                color: Color::from_rgb(0.50, 0.50, 0.50), // Slightly darker on hover
-                width: 1.0,
-                radius: 0.0.into(),
-            },
-            icon: palette.text,
-            placeholder: Color::from_rgb(0.67, 0.67, 0.67),
-            value: Color::BLACK,
-            selection: Color::from_rgba(0.67, 0.84, 1.0, 0.5),
-        },
-        text_input::Status::Focused { .. } => text_input::Style {
-            background: Background::Color(Color::WHITE),
-            border: Border {
-                color: Color::BLACK, // Black border when focused (like HTML)
-                width: 2.0,
-                radius: 0.0.into(),
-            },
-            icon: palette.text,
-            placeholder: Color::from_rgb(0.67, 0.67, 0.67),
-            value: Color::BLACK,
-            selection: Color::from_rgba(0.67, 0.84, 1.0, 0.5),
-        },
-        text_input::Status::Disabled => text_input::Style {
-            background: Background::Color(Color::from_rgb(0.94, 0.94, 0.94)),
-            border: Border {
-                color: Color::from_rgb(0.80, 0.80, 0.80),
-                width: 1.0,
-                radius: 0.0.into(),
-            },
-            icon: Color::from_rgb(0.67, 0.67, 0.67),
-            placeholder: Color::from_rgb(0.80, 0.80, 0.80),
-            value: Color::from_rgb(0.67, 0.67, 0.67),
-            selection: Color::TRANSPARENT,
+    let plain = Plain::new(theme);
+
+    let (border, width) = match status {
+        text_input::Status::Active => (plain.gray(0.33), 1.0),
+        text_input::Status::Hovered => (plain.gray(0.5), 1.0),
+        // Text-colored outline when focused (like HTML)
+        text_input::Status::Focused { .. } => (plain.text, 2.0),
+        text_input::Status::Disabled => {
+            return text_input::Style {
+                background: Background::Color(plain.gray(0.06)),
+                border: Border {
+                    color: plain.gray(0.2),
+                    width: 1.0,
+                    radius: 0.0.into(),
+                },
+                icon: plain.gray(0.33),
+                placeholder: plain.gray(0.2),
+                value: plain.gray(0.33),
+                selection: Color::TRANSPARENT,
+            }
+        }
+    };
+
+    text_input::Style {
+        background: Background::Color(plain.field),
+        border: Border {
+            color: border,
+            width,
+            radius: 0.0.into(), // No rounding - pure HTML style
         },
+        icon: plain.text,
+        placeholder: plain.gray(0.33),
+        value: plain.text,
+        selection: plain.selection,
     }
 }
 
 /// Plain container - no styling
 pub fn plain_container_style(theme: &Theme) -> container::Style {
     let palette = theme.palette();
-    
+
     container::Style {
         background: Some(Background::Color(palette.background)),
         border: Border::default(), // No border
@@ -124,16 +115,16 @@ pub fn plain_container_style(theme: &Theme) -> container::Style {
 
 /// Plain fieldset/form group - with simple border like HTML fieldset
 pub fn plain_fieldset_style(theme: &Theme) -> container::Style {
-    let palette = theme.palette();
-    
+    let plain = Plain::new(theme);
+
     container::Style {
         background: None,
         border: Border {
-            color: Color::from_rgb(0.75, 0.75, 0.75),
+            color: plain.gray(0.25),
             width: 1.0,
             radius: 0.0.into(),
         },
-        text_color: Some(palette.text),
+        text_color: Some(plain.text),
         shadow: Shadow::default(),
         snap: false,
     }
@@ -0,0 +1,279 @@
+use iced::widget::{button, container, text_input};
+use iced::{Background, Border, Color, Shadow, Theme, Vector};
+use serde::{Deserialize, Serialize};
+
+use crate::styles::button::{
+    windows_7_button_style, windows_7_button_style_with_gray, windows_7_group_style, windows_7_input_style,
+    windows_7_panel_style,
+};
+use crate::styles::color::{mix, with_alpha};
+use crate::styles::plain::{plain_button_style, plain_container_style, plain_fieldset_style, plain_input_style};
+use crate::styles::web::{
+    bootstrap_button_style, bootstrap_card_style, bootstrap_danger_button_style, bootstrap_input_style,
+    bootstrap_secondary_button_style, bootstrap_success_button_style, bootstrap_warning_button_style,
+    form_group_style,
+};
+
+type ButtonStyle = fn(&Theme, button::Status) -> button::Style;
+type InputStyle = fn(&Theme, text_input::Status) -> text_input::Style;
+type ContainerStyle = fn(&Theme) -> container::Style;
+
+/// Widget style vocabulary used everywhere, chosen separately from the
+/// color theme; every skin takes its colors from the theme's palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Skin {
+    #[default]
+    Windows7,
+    Bootstrap,
+    PlainHtml,
+    /// iced's own built-in styles
+    Native,
+}
+
+impl Skin {
+    pub const ALL: [Skin; 4] = [Skin::Windows7, Skin::Bootstrap, Skin::PlainHtml, Skin::Native];
+
+    /// The main button style
+    pub fn button(self) -> ButtonStyle {
+        match self {
+            Skin::Windows7 => windows_7_button_style,
+            Skin::Bootstrap => bootstrap_button_style,
+            Skin::PlainHtml => plain_button_style,
+            Skin::Native => button::primary,
+        }
+    }
+
+    /// Less prominent buttons, like Cancel and navigation
+    pub fn secondary_button(self) -> ButtonStyle {
+        match self {
+            Skin::Bootstrap => bootstrap_secondary_button_style,
+            Skin::Native => button::secondary,
+            // Windows 7 and plain HTML buttons all look alike
+            Skin::Windows7 | Skin::PlainHtml => self.button(),
+        }
+    }
+
+    pub fn success_button(self) -> ButtonStyle {
+        match self {
+            Skin::Bootstrap => bootstrap_success_button_style,
+            Skin::Native => button::success,
+            Skin::Windows7 | Skin::PlainHtml => self.button(),
+        }
+    }
+
+    pub fn warning_button(self) -> ButtonStyle {
+        match self {
+            Skin::Bootstrap => bootstrap_warning_button_style,
+            Skin::Native => button::warning,
+            Skin::Windows7 | Skin::PlainHtml => self.button(),
+        }
+    }
+
+    pub fn danger_button(self) -> ButtonStyle {
+        match self {
+            Skin::Bootstrap => bootstrap_danger_button_style,
+            Skin::Native => button::danger,
+            Skin::Windows7 | Skin::PlainHtml => self.button(),
+        }
+    }
+
+    /// Main button part way (`gray_amount` 0 to 1) to looking disabled, for
+    /// buttons that fade out
+    pub fn grayed_button(self, gray_amount: f32) -> impl Fn(&Theme, button::Status) -> button::Style {
+        move |theme, status| match self {
+            Skin::Windows7 => windows_7_button_style_with_gray(theme, status, gray_amount),
+            _ => gray_out(self.button(), theme, status, gray_amount),
+        }
+    }
+
+    pub fn input(self) -> InputStyle {
+        match self {
+            Skin::Windows7 => windows_7_input_style,
+            Skin::Bootstrap => bootstrap_input_style,
+            Skin::PlainHtml => plain_input_style,
+            Skin::Native => text_input::default,
+        }
+    }
+
+    /// Panels and dialogs holding a form
+    pub fn container(self) -> ContainerStyle {
+        match self {
+            Skin::Windows7 => windows_7_panel_style,
+            Skin::Bootstrap => bootstrap_card_style,
+            Skin::PlainHtml => plain_container_style,
+            Skin::Native => container::bordered_box,
+        }
+    }
+
+    /// Groups of related fields within a form
+    pub fn fieldset(self) -> ContainerStyle {
+        match self {
+            Skin::Windows7 => windows_7_group_style,
+            Skin::Bootstrap => form_group_style,
+            Skin::PlainHtml => plain_fieldset_style,
+            Skin::Native => |_theme| container::Style::default(),
+        }
+    }
+
+    /// Panels floating above the page, like dialogs, menus and notifications:
+    /// the skin's panel with a drop shadow, whatever its panels usually do
+    pub fn popup(self) -> impl Fn(&Theme) -> container::Style {
+        move |theme| container::Style {
+            shadow: Shadow {
+                color: Color::from_rgba(0.0, 0.0, 0.0, 0.5),
+                offset: Vector::new(0.0, 4.0),
+                blur_radius: 16.0,
+            },
+            ..self.container()(theme)
+        }
+    }
+
+    /// Rows of a list to pick from: the selected row looks like the main
+    /// button, the others stay flat until hovered
+    pub fn list_item(self, is_selected: bool) -> impl Fn(&Theme, button::Status) -> button::Style {
+        move |theme, status| {
+            if is_selected {
+                return self.button()(theme, status);
+            }
+
+            let style = self.secondary_button()(theme, status);
+            match status {
+                button::Status::Hovered | button::Status::Pressed => style,
+                button::Status::Active | button::Status::Disabled => button::Style {
+                    background: None,
+                    text_color: theme.palette().text,
+                    border: Border {
+                        color: Color::TRANSPARENT,
+                        ..style.border
+                    },
+                    shadow: Shadow::default(),
+                    ..style
+                },
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Skin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Skin::Windows7 => "Windows 7",
+                Skin::Bootstrap => "Bootstrap",
+                Skin::PlainHtml => "Plain HTML",
+                Skin::Native => "Native",
+            }
+        )
+    }
+}
+
+/// Blend a flat button's active look towards its disabled one
+fn gray_out(style: ButtonStyle, theme: &Theme, status: button::Status, amount: f32) -> button::Style {
+    let active = style(theme, status);
+    if status != button::Status::Active {
+        return active;
+    }
+    let disabled = style(theme, button::Status::Disabled);
+
+    button::Style {
+        background: match (active.background, disabled.background) {
+            (Some(Background::Color(from)), Some(Background::Color(to))) => {
+                Some(Background::Color(mix(from, to, amount)))
+            }
+            (background, _) => background,
+        },
+        border: Border {
+            color: mix(active.border.color, disabled.border.color, amount),
+            ..active.border
+        },
+        text_color: mix(active.text_color, disabled.text_color, amount),
+        shadow: Shadow {
+            color: with_alpha(active.shadow.color, active.shadow.color.a * (1.0 - amount)),
+            ..active.shadow
+        },
+        ..active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skins_round_trip_through_settings_json() {
+        for skin in Skin::ALL {
+            let json = serde_json::to_string(&skin).unwrap();
+            assert_eq!(serde_json::from_str::<Skin>(&json).unwrap(), skin);
+        }
+    }
+
+    #[test]
+    fn skins_style_widgets_differently() {
+        let theme = Theme::TokyoNight;
+        let active: Vec<button::Style> = Skin::ALL
+            .iter()
+            .map(|skin| skin.button()(&theme, button::Status::Active))
+            .collect();
+
+        for (index, style) in active.iter().enumerate() {
+            for other in &active[index + 1..] {
+                assert_ne!(style, other);
+            }
+        }
+    }
+
+    #[test]
+    fn graying_out_ends_disabled() {
+        let theme = Theme::Dracula;
+        for skin in Skin::ALL {
+            let grayed = skin.grayed_button(1.0)(&theme, button::Status::Active);
+            let disabled = skin.button()(&theme, button::Status::Disabled);
+            assert_eq!(grayed.text_color.into_rgba8(), disabled.text_color.into_rgba8(), "{skin}");
+
+            // Mixing converts through Oklab and back, so compare at 8 bits
+            let untouched = skin.grayed_button(0.0)(&theme, button::Status::Active);
+            let active = skin.button()(&theme, button::Status::Active);
+            assert_eq!(untouched.text_color.into_rgba8(), active.text_color.into_rgba8(), "{skin}");
+        }
+    }
+
+    #[test]
+    fn list_rows_stay_flat_until_hovered_or_selected() {
+        let theme = Theme::Nord;
+        for skin in Skin::ALL {
+            let idle = skin.list_item(false)(&theme, button::Status::Active);
+            assert_eq!(idle.background, None, "{skin}");
+            assert_eq!(idle.text_color, theme.palette().text, "{skin}");
+
+            let hovered = skin.list_item(false)(&theme, button::Status::Hovered);
+            assert_eq!(hovered, skin.secondary_button()(&theme, button::Status::Hovered), "{skin}");
+            let selected = skin.list_item(true)(&theme, button::Status::Active);
+            assert_eq!(selected, skin.button()(&theme, button::Status::Active), "{skin}");
+        }
+    }
+
+    #[test]
+    fn faded_themes_fade_every_part_of_a_skin() {
+        let faded = crate::styles::theme::faded(&Theme::Light, 0.5);
+        let opacity = |background: Option<Background>| match background {
+            Some(Background::Color(color)) => color.a,
+            Some(Background::Gradient(iced::Gradient::Linear(linear))) => {
+                linear.stops.iter().flatten().map(|stop| stop.color.a).fold(0.0, f32::max)
+            }
+            None => 0.0,
+        };
+
+        for skin in Skin::ALL {
+            let button = skin.button()(&faded, button::Status::Hovered);
+            assert!(opacity(button.background) <= 0.5 + 1e-3, "{skin}");
+            assert!(button.text_color.a <= 0.5 + 1e-3, "{skin}");
+            assert!(button.shadow.color.a <= 0.5 + 1e-3, "{skin}");
+
+            let input = skin.input()(&faded, text_input::Status::Focused { is_hovered: false });
+            assert!(opacity(Some(input.background)) <= 0.5 + 1e-3, "{skin}");
+            assert!(input.selection.a <= 0.5 + 1e-3, "{skin}");
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use iced::Theme;
+use iced::theme::palette::Extended;
+use iced::theme::Palette;
+use iced::{Color, Theme};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use crate::styles::color::{self, with_alpha};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ThemeType {
     Light,
     Dark,
@@ -78,6 +83,18 @@ impl ThemeType {
             ThemeType::Ferra => Theme::Ferra,
         }
     }
+
+    /// Built-in theme shown as `name`, ignoring case
+    pub fn named(name: &str) -> Option<ThemeType> {
+        ThemeType::ALL
+            .into_iter()
+            .find(|theme| theme.to_string().eq_ignore_ascii_case(name))
+    }
+
+    /// Find the built-in theme type matching an iced theme
+    pub fn from_theme(theme: &Theme) -> Option<ThemeType> {
+        ThemeType::ALL.iter().find(|t| t.to_theme() == *theme).copied()
+    }
 }
 
 impl std::fmt::Display for ThemeType {
@@ -112,3 +129,231 @@ impl std::fmt::Display for ThemeType {
         )
     }
 }
+
+/// A theme for the pick lists: one of iced's built-ins or a saved custom one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeChoice {
+    BuiltIn(ThemeType),
+    /// Name of a [`CustomTheme`]
+    Custom(String),
+}
+
+impl ThemeChoice {
+    /// Built-in themes followed by the custom ones
+    pub fn all(custom: &[CustomTheme]) -> Vec<ThemeChoice> {
+        ThemeType::ALL
+            .into_iter()
+            .map(ThemeChoice::BuiltIn)
+            .chain(custom.iter().map(|theme| ThemeChoice::Custom(theme.name.clone())))
+            .collect()
+    }
+
+    /// The iced theme, `None` if the custom theme has since been deleted
+    pub fn to_theme(&self, custom: &[CustomTheme]) -> Option<Theme> {
+        match self {
+            ThemeChoice::BuiltIn(theme_type) => Some(theme_type.to_theme()),
+            ThemeChoice::Custom(name) => custom
+                .iter()
+                .find(|theme| theme.name == *name)
+                .map(CustomTheme::to_theme),
+        }
+    }
+
+    /// The choice behind an iced theme, if it is still available
+    pub fn from_theme(theme: &Theme, custom: &[CustomTheme]) -> Option<ThemeChoice> {
+        if let Some(theme_type) = ThemeType::from_theme(theme) {
+            return Some(ThemeChoice::BuiltIn(theme_type));
+        }
+        custom
+            .iter()
+            .find(|custom| custom.to_theme() == *theme)
+            .map(|custom| ThemeChoice::Custom(custom.name.clone()))
+    }
+}
+
+impl std::fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeChoice::BuiltIn(theme_type) => write!(f, "{theme_type}"),
+            ThemeChoice::Custom(name) => write!(f, "{name} (custom)"),
+        }
+    }
+}
+
+/// One of the colors a theme's palette is built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaletteColor {
+    Background,
+    Text,
+    Primary,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl PaletteColor {
+    pub const ALL: [PaletteColor; 6] = [
+        PaletteColor::Background,
+        PaletteColor::Text,
+        PaletteColor::Primary,
+        PaletteColor::Success,
+        PaletteColor::Warning,
+        PaletteColor::Danger,
+    ];
+
+    pub fn get(self, palette: &Palette) -> Color {
+        match self {
+            PaletteColor::Background => palette.background,
+            PaletteColor::Text => palette.text,
+            PaletteColor::Primary => palette.primary,
+            PaletteColor::Success => palette.success,
+            PaletteColor::Warning => palette.warning,
+            PaletteColor::Danger => palette.danger,
+        }
+    }
+
+    pub fn set(self, palette: &mut Palette, color: Color) {
+        let slot = match self {
+            PaletteColor::Background => &mut palette.background,
+            PaletteColor::Text => &mut palette.text,
+            PaletteColor::Primary => &mut palette.primary,
+            PaletteColor::Success => &mut palette.success,
+            PaletteColor::Warning => &mut palette.warning,
+            PaletteColor::Danger => &mut palette.danger,
+        };
+        *slot = color;
+    }
+}
+
+impl std::fmt::Display for PaletteColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                PaletteColor::Background => "Background",
+                PaletteColor::Text => "Text",
+                PaletteColor::Primary => "Primary",
+                PaletteColor::Success => "Success",
+                PaletteColor::Warning => "Warning",
+                PaletteColor::Danger => "Danger",
+            }
+        )
+    }
+}
+
+/// `theme` with every palette color at `opacity` of its alpha, so whatever
+/// is styled from it fades as a whole
+pub fn faded(theme: &Theme, opacity: f32) -> Theme {
+    let fade = |color: Color| with_alpha(color, color.a * opacity);
+
+    let mut palette = theme.palette();
+    for slot in PaletteColor::ALL {
+        let color = fade(slot.get(&palette));
+        slot.set(&mut palette, color);
+    }
+
+    // Fade the theme's own shades rather than generating new ones, which
+    // would pick opaque text wherever the faded colors lose contrast
+    let mut extended = *theme.extended_palette();
+    let Extended { background, primary, secondary, success, warning, danger, .. } = &mut extended;
+    let pairs = [
+        &mut background.base,
+        &mut background.weakest,
+        &mut background.weaker,
+        &mut background.weak,
+        &mut background.neutral,
+        &mut background.strong,
+        &mut background.stronger,
+        &mut background.strongest,
+        &mut primary.base,
+        &mut primary.weak,
+        &mut primary.strong,
+        &mut secondary.base,
+        &mut secondary.weak,
+        &mut secondary.strong,
+        &mut success.base,
+        &mut success.weak,
+        &mut success.strong,
+        &mut warning.base,
+        &mut warning.weak,
+        &mut warning.strong,
+        &mut danger.base,
+        &mut danger.weak,
+        &mut danger.strong,
+    ];
+    for pair in pairs {
+        pair.color = fade(pair.color);
+        pair.text = fade(pair.text);
+    }
+
+    Theme::custom_with_fn(theme.to_string(), palette, |_| extended)
+}
+
+/// How far `theme` has been [`faded`]; themes themselves have an opaque
+/// background
+pub fn opacity(theme: &Theme) -> f32 {
+    theme.palette().background.a
+}
+
+/// A named palette made in the theme editor, stored with `#rrggbb` colors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "StoredTheme", into = "StoredTheme")]
+pub struct CustomTheme {
+    pub name: String,
+    pub palette: Palette,
+}
+
+impl CustomTheme {
+    pub fn to_theme(&self) -> Theme {
+        Theme::custom(self.name.clone(), self.palette)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredTheme {
+    name: String,
+    background: String,
+    text: String,
+    primary: String,
+    success: String,
+    warning: String,
+    danger: String,
+}
+
+impl From<CustomTheme> for StoredTheme {
+    fn from(theme: CustomTheme) -> Self {
+        let hex = |color: PaletteColor| color.get(&theme.palette).to_string();
+        StoredTheme {
+            background: hex(PaletteColor::Background),
+            text: hex(PaletteColor::Text),
+            primary: hex(PaletteColor::Primary),
+            success: hex(PaletteColor::Success),
+            warning: hex(PaletteColor::Warning),
+            danger: hex(PaletteColor::Danger),
+            name: theme.name,
+        }
+    }
+}
+
+impl TryFrom<StoredTheme> for CustomTheme {
+    type Error = String;
+
+    fn try_from(stored: StoredTheme) -> Result<Self, Self::Error> {
+        let parse = |color: PaletteColor, hex: &str| {
+            color::parse_hex(hex).ok_or_else(|| format!("{color} of `{}` is not a hex color: `{hex}`", stored.name))
+        };
+
+        Ok(CustomTheme {
+            palette: Palette {
+                background: parse(PaletteColor::Background, &stored.background)?,
+                text: parse(PaletteColor::Text, &stored.text)?,
+                primary: parse(PaletteColor::Primary, &stored.primary)?,
+                success: parse(PaletteColor::Success, &stored.success)?,
+                warning: parse(PaletteColor::Warning, &stored.warning)?,
+                danger: parse(PaletteColor::Danger, &stored.danger)?,
+            },
+            name: stored.name,
+        })
+    }
+}
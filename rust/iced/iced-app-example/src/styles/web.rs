@@ -1,240 +1,129 @@
 use iced::widget::{button, container, text_input};
 use iced::{Background, Border, Color, Shadow, Theme};
 
-/// Bootstrap-inspired button style - flat with subtle borders
-pub fn bootstrap_button_style(_theme: &Theme, status: button::Status) -> button::Style {
-    let _palette = _theme.palette();
-    
-    match status {
-        button::Status::Active => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.48, 0.80))), // Bootstrap primary blue
-            border: Border {
-                color: Color::from_rgb(0.0, 0.42, 0.70),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.42, 0.70))), // Darker blue on hover
+use crate::styles::color::{is_dark, mix, readable, with_alpha};
+
+/// Bootstrap's light and dark modes, worked out from a theme's palette
+struct Bootstrap {
+    background: Color,
+    text: Color,
+    /// What buttons shade towards when hovered and pressed: black on
+    /// light themes, white on dark ones
+    shade: Color,
+    input_border: Color,
+    /// Input border under the mouse
+    input_hover: Color,
+    placeholder: Color,
+    muted: Color,
+    shadow: f32,
+}
+
+impl Bootstrap {
+    fn new(theme: &Theme) -> Self {
+        let palette = theme.palette();
+        let (background, text) = (palette.background, palette.text);
+        let is_dark = is_dark(background);
+
+        Bootstrap {
+            background,
+            text,
+            // The shade fades with the page while views cross-fade
+            shade: with_alpha(if is_dark { Color::WHITE } else { Color::BLACK }, background.a),
+            input_border: mix(background, text, 0.2),
+            input_hover: mix(background, text, 0.4),
+            placeholder: mix(background, text, 0.55),
+            muted: mix(background, text, 0.12),
+            shadow: background.a * if is_dark { 0.3 } else { 0.15 },
+        }
+    }
+
+    /// Flat button filled with `color`, shaded a step further for each of
+    /// hover and press
+    fn button(&self, color: Color, status: button::Status) -> button::Style {
+        let step = match status {
+            button::Status::Active => 0.0,
+            button::Status::Hovered => 0.12,
+            button::Status::Pressed => 0.2,
+            button::Status::Disabled => return self.disabled_button(),
+        };
+        let fill = mix(color, self.shade, step);
+
+        button::Style {
+            background: Some(Background::Color(fill)),
             border: Border {
-                color: Color::from_rgb(0.0, 0.36, 0.60),
+                color: mix(color, self.shade, step + 0.1),
                 width: 1.0,
                 radius: 4.0.into(),
             },
-            text_color: Color::WHITE,
-            shadow: Shadow {
-                color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
-                offset: iced::Vector::new(0.0, 2.0),
-                blur_radius: 4.0,
+            text_color: readable(fill, self.background),
+            shadow: if status == button::Status::Hovered {
+                Shadow {
+                    color: Color::from_rgba(0.0, 0.0, 0.0, self.shadow),
+                    offset: iced::Vector::new(0.0, 2.0),
+                    blur_radius: 4.0,
+                }
+            } else {
+                Shadow::default()
             },
             snap: false,
-        },
-        button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.0, 0.36, 0.60))), // Even darker when pressed
-            border: Border {
-                color: Color::from_rgb(0.0, 0.30, 0.50),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Disabled => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.82, 0.84, 0.86))),
+        }
+    }
+
+    fn disabled_button(&self) -> button::Style {
+        button::Style {
+            background: Some(Background::Color(self.muted)),
             border: Border {
-                color: Color::from_rgb(0.72, 0.74, 0.76),
+                color: self.input_border,
                 width: 1.0,
                 radius: 4.0.into(),
             },
-            text_color: Color::from_rgb(0.5, 0.5, 0.5),
+            text_color: self.placeholder,
             shadow: Shadow::default(),
             snap: false,
-        },
+        }
     }
 }
 
+/// Bootstrap-inspired button style - flat with subtle borders
+pub fn bootstrap_button_style(theme: &Theme, status: button::Status) -> button::Style {
+    Bootstrap::new(theme).button(theme.palette().primary, status)
+}
+
 /// Secondary button style (gray/outline style)
-pub fn bootstrap_secondary_button_style(_theme: &Theme, status: button::Status) -> button::Style {
-    let _palette = _theme.palette();
-    
-    match status {
-        button::Status::Active => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.42, 0.46, 0.50))), // Bootstrap secondary gray
-            border: Border {
-                color: Color::from_rgb(0.36, 0.40, 0.44),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.36, 0.40, 0.44))),
-            border: Border {
-                color: Color::from_rgb(0.30, 0.34, 0.38),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow {
-                color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
-                offset: iced::Vector::new(0.0, 2.0),
-                blur_radius: 4.0,
-            },
-            snap: false,
-        },
-        button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.30, 0.34, 0.38))),
-            border: Border {
-                color: Color::from_rgb(0.24, 0.28, 0.32),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Disabled => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.82, 0.84, 0.86))),
-            border: Border {
-                color: Color::from_rgb(0.72, 0.74, 0.76),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::from_rgb(0.5, 0.5, 0.5),
-            shadow: Shadow::default(),
-            snap: false,
-        },
-    }
+pub fn bootstrap_secondary_button_style(theme: &Theme, status: button::Status) -> button::Style {
+    // Bootstrap's secondary is a gray; the theme's own gray keeps its tint
+    Bootstrap::new(theme).button(theme.extended_palette().secondary.base.color, status)
 }
 
 /// Success button style (green)
-pub fn bootstrap_success_button_style(_theme: &Theme, status: button::Status) -> button::Style {
-    match status {
-        button::Status::Active => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.16, 0.66, 0.36))), // Bootstrap success green
-            border: Border {
-                color: Color::from_rgb(0.12, 0.58, 0.30),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.12, 0.58, 0.30))),
-            border: Border {
-                color: Color::from_rgb(0.08, 0.50, 0.24),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow {
-                color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
-                offset: iced::Vector::new(0.0, 2.0),
-                blur_radius: 4.0,
-            },
-            snap: false,
-        },
-        button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.08, 0.50, 0.24))),
-            border: Border {
-                color: Color::from_rgb(0.04, 0.42, 0.18),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Disabled => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.82, 0.84, 0.86))),
-            border: Border {
-                color: Color::from_rgb(0.72, 0.74, 0.76),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::from_rgb(0.5, 0.5, 0.5),
-            shadow: Shadow::default(),
-            snap: false,
-        },
-    }
+pub fn bootstrap_success_button_style(theme: &Theme, status: button::Status) -> button::Style {
+    Bootstrap::new(theme).button(theme.palette().success, status)
 }
 
-/// Danger/warning button style (red)
-pub fn bootstrap_danger_button_style(_theme: &Theme, status: button::Status) -> button::Style {
-    match status {
-        button::Status::Active => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.86, 0.20, 0.27))), // Bootstrap danger red
-            border: Border {
-                color: Color::from_rgb(0.78, 0.16, 0.23),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.78, 0.16, 0.23))),
-            border: Border {
-                color: Color::from_rgb(0.70, 0.12, 0.19),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow {
-                color: Color::from_rgba(0.0, 0.0, 0.0, 0.15),
-                offset: iced::Vector::new(0.0, 2.0),
-                blur_radius: 4.0,
-            },
-            snap: false,
-        },
-        button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.70, 0.12, 0.19))),
-            border: Border {
-                color: Color::from_rgb(0.62, 0.08, 0.15),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::WHITE,
-            shadow: Shadow::default(),
-            snap: false,
-        },
-        button::Status::Disabled => button::Style {
-            background: Some(Background::Color(Color::from_rgb(0.82, 0.84, 0.86))),
-            border: Border {
-                color: Color::from_rgb(0.72, 0.74, 0.76),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            text_color: Color::from_rgb(0.5, 0.5, 0.5),
-            shadow: Shadow::default(),
-            snap: false,
-        },
-    }
+/// Warning button style (yellow)
+pub fn bootstrap_warning_button_style(theme: &Theme, status: button::Status) -> button::Style {
+    Bootstrap::new(theme).button(theme.palette().warning, status)
+}
+
+/// Danger button style (red)
+pub fn bootstrap_danger_button_style(theme: &Theme, status: button::Status) -> button::Style {
+    Bootstrap::new(theme).button(theme.palette().danger, status)
 }
 
 /// Bootstrap-inspired container/card style
 pub fn bootstrap_card_style(theme: &Theme) -> container::Style {
-    let palette = theme.palette();
-    
+    let bootstrap = Bootstrap::new(theme);
+
     container::Style {
-        background: Some(Background::Color(palette.background)),
+        background: Some(Background::Color(bootstrap.background)),
         border: Border {
-            color: Color::from_rgba(0.0, 0.0, 0.0, 0.125), // Light border
+            color: with_alpha(bootstrap.text, bootstrap.text.a * 0.125), // Light border
             width: 1.0,
             radius: 4.0.into(),
         },
-        text_color: Some(palette.text),
+        text_color: Some(bootstrap.text),
         shadow: Shadow {
-            color: Color::from_rgba(0.0, 0.0, 0.0, 0.075),
+            color: Color::from_rgba(0.0, 0.0, 0.0, bootstrap.shadow / 2.0),
             offset: iced::Vector::new(0.0, 2.0),
             blur_radius: 4.0,
         },
@@ -245,7 +134,7 @@ pub fn bootstrap_card_style(theme: &Theme) -> container::Style {
 /// Form control container style
 pub fn form_group_style(theme: &Theme) -> container::Style {
     let palette = theme.palette();
-    
+
     container::Style {
         background: None,
         border: Border::default(),
@@ -257,56 +146,40 @@ pub fn form_group_style(theme: &Theme) -> container::Style {
 
 /// Input field style (for text inputs)
 pub fn bootstrap_input_style(theme: &Theme, status: text_input::Status) -> text_input::Style {
-    let palette = theme.palette();
-    
-    match status {
-        text_input::Status::Active => text_input::Style {
-            background: Background::Color(palette.background),
-            border: Border {
-                color: Color::from_rgb(0.81, 0.83, 0.85), // Bootstrap form-control border
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            icon: palette.text,
-            placeholder: Color::from_rgb(0.42, 0.46, 0.50),
-            value: palette.text,
-            selection: Color::from_rgba(0.0, 0.48, 0.80, 0.3),
-        },
-        text_input::Status::Hovered => text_input::Style {
-            background: Background::Color(palette.background),
-            border: Border {
-                color: Color::from_rgb(0.52, 0.60, 0.68), // Darker border on hover
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            icon: palette.text,
-            placeholder: Color::from_rgb(0.42, 0.46, 0.50),
-            value: palette.text,
-            selection: Color::from_rgba(0.0, 0.48, 0.80, 0.3),
-        },
-        text_input::Status::Focused { .. } => text_input::Style {
-            background: Background::Color(palette.background),
-            border: Border {
-                color: Color::from_rgb(0.0, 0.48, 0.80), // Blue border when focused (Bootstrap focus)
-                width: 2.0,
-                radius: 4.0.into(),
-            },
-            icon: palette.text,
-            placeholder: Color::from_rgb(0.42, 0.46, 0.50),
-            value: palette.text,
-            selection: Color::from_rgba(0.0, 0.48, 0.80, 0.3),
-        },
-        text_input::Status::Disabled => text_input::Style {
-            background: Background::Color(Color::from_rgb(0.91, 0.92, 0.93)),
-            border: Border {
-                color: Color::from_rgb(0.81, 0.83, 0.85),
-                width: 1.0,
-                radius: 4.0.into(),
-            },
-            icon: Color::from_rgb(0.5, 0.5, 0.5),
-            placeholder: Color::from_rgb(0.5, 0.5, 0.5),
-            value: Color::from_rgb(0.5, 0.5, 0.5),
-            selection: Color::TRANSPARENT,
+    let bootstrap = Bootstrap::new(theme);
+    let primary = theme.palette().primary;
+
+    let (border, width) = match status {
+        text_input::Status::Active => (bootstrap.input_border, 1.0),
+        text_input::Status::Hovered => (bootstrap.input_hover, 1.0),
+        // Bootstrap's focus ring, in the theme's primary color
+        text_input::Status::Focused { .. } => (primary, 2.0),
+        text_input::Status::Disabled => {
+            return text_input::Style {
+                background: Background::Color(bootstrap.muted),
+                border: Border {
+                    color: bootstrap.input_border,
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                icon: bootstrap.placeholder,
+                placeholder: bootstrap.placeholder,
+                value: bootstrap.placeholder,
+                selection: Color::TRANSPARENT,
+            }
+        }
+    };
+
+    text_input::Style {
+        background: Background::Color(bootstrap.background),
+        border: Border {
+            color: border,
+            width,
+            radius: 4.0.into(),
         },
+        icon: bootstrap.text,
+        placeholder: bootstrap.placeholder,
+        value: bootstrap.text,
+        selection: with_alpha(primary, primary.a * 0.3),
     }
 }
@@ -0,0 +1,291 @@
+use iced::theme::Palette;
+use iced::{Color, Theme};
+use std::path::{Path, PathBuf};
+
+use crate::persistence::{self, Storage};
+use crate::styles::color;
+use crate::styles::theme::{CustomTheme, PaletteColor, ThemeType};
+
+const THEMES_FILE: &str = "themes.json";
+
+/// Palette being built in the theme editor
+#[derive(Debug, Clone)]
+pub struct ThemeEditor {
+    pub name: String,
+    pub palette: Palette,
+    /// Color the RGB sliders change
+    pub selected: PaletteColor,
+    /// Text of each color's hex field, in [`PaletteColor::ALL`] order; kept
+    /// as typed so half-written values do not jump around
+    pub hex: [String; 6],
+    /// Theme file or directory of them to import
+    pub import_path: String,
+    pub status: Option<String>,
+}
+
+impl Default for ThemeEditor {
+    fn default() -> Self {
+        let mut editor = ThemeEditor {
+            name: String::new(),
+            palette: Palette::LIGHT,
+            selected: PaletteColor::Primary,
+            hex: Default::default(),
+            import_path: String::new(),
+            status: None,
+        };
+        editor.start_from(&Theme::Light);
+        editor
+    }
+}
+
+impl ThemeEditor {
+    /// Replace the palette with a copy of `theme`'s
+    pub fn start_from(&mut self, theme: &Theme) {
+        self.palette = theme.palette();
+        for (hex, color) in self.hex.iter_mut().zip(PaletteColor::ALL) {
+            *hex = color.get(&self.palette).to_string();
+        }
+    }
+
+    pub fn color(&self, color: PaletteColor) -> Color {
+        color.get(&self.palette)
+    }
+
+    pub fn hex(&self, color: PaletteColor) -> &str {
+        &self.hex[index(color)]
+    }
+
+    /// Take the hex field's text, using it once it is a valid color
+    pub fn set_hex(&mut self, color: PaletteColor, text: String) {
+        if let Some(parsed) = color::parse_hex(&text) {
+            color.set(&mut self.palette, Color { a: 1.0, ..parsed });
+        }
+        self.hex[index(color)] = text;
+    }
+
+    /// Red, green and blue of the selected color
+    pub fn channels(&self) -> [u8; 3] {
+        let [r, g, b, _] = self.color(self.selected).into_rgba8();
+        [r, g, b]
+    }
+
+    /// Set red (0), green (1) or blue (2) of the selected color
+    pub fn set_channel(&mut self, channel: usize, value: u8) {
+        let mut channels = self.channels();
+        channels[channel] = value;
+        let [r, g, b] = channels;
+        let color = Color::from_rgb8(r, g, b);
+
+        self.selected.set(&mut self.palette, color);
+        self.hex[index(self.selected)] = color.to_string();
+    }
+
+    /// The palette as a theme, for the live preview
+    pub fn preview(&self) -> Theme {
+        let name = self.name.trim();
+        Theme::custom(if name.is_empty() { "Untitled" } else { name }.to_string(), self.palette)
+    }
+
+    /// The theme to save, if its name is usable
+    pub fn to_custom(&self) -> Result<CustomTheme, String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("Give the theme a name first".to_string());
+        }
+        if ThemeType::named(name).is_some() {
+            return Err(format!("\"{name}\" is a built-in theme"));
+        }
+
+        Ok(CustomTheme {
+            name: name.to_string(),
+            palette: self.palette,
+        })
+    }
+}
+
+fn index(color: PaletteColor) -> usize {
+    PaletteColor::ALL
+        .iter()
+        .position(|candidate| *candidate == color)
+        .unwrap_or(0)
+}
+
+#[derive(Debug)]
+pub enum ThemesError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    NoConfigDir,
+}
+
+impl std::fmt::Display for ThemesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemesError::Io(error) => write!(f, "cannot access themes file: {error}"),
+            ThemesError::Parse(error) => write!(f, "malformed themes file: {error}"),
+            ThemesError::NoConfigDir => write!(f, "no config directory available"),
+        }
+    }
+}
+
+impl std::error::Error for ThemesError {}
+
+impl From<std::io::Error> for ThemesError {
+    fn from(error: std::io::Error) -> Self {
+        ThemesError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ThemesError {
+    fn from(error: serde_json::Error) -> Self {
+        ThemesError::Parse(error)
+    }
+}
+
+/// `themes.json` in the config directory, next to the saved state
+pub fn themes_path() -> Option<PathBuf> {
+    Some(persistence::config_dir()?.join(THEMES_FILE))
+}
+
+/// Saved custom themes; none until the first one is saved
+pub fn load() -> Result<Vec<CustomTheme>, ThemesError> {
+    match themes_path() {
+        Some(path) => load_from(&path),
+        None => Ok(Vec::new()),
+    }
+}
+
+pub fn load_from(path: &Path) -> Result<Vec<CustomTheme>, ThemesError> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Write `themes` in the background, as [`Task::perform`](iced::Task::perform) runs it
+pub async fn save(storage: Storage, themes: Vec<CustomTheme>) -> Result<PathBuf, ThemesError> {
+    let path = storage.config_file(THEMES_FILE).ok_or(ThemesError::NoConfigDir)?;
+    save_to(&path, &themes).await?;
+    Ok(path)
+}
+
+/// Written atomically like the saved state
+pub async fn save_to(path: &Path, themes: &[CustomTheme]) -> Result<(), ThemesError> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+
+    let temp = path.with_extension("json.tmp");
+    tokio::fs::write(&temp, serde_json::to_string_pretty(themes)?).await?;
+    tokio::fs::rename(&temp, path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::message::Message;
+    use iced::executor;
+
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        executor::Default::new().unwrap().block_on(future)
+    }
+
+    #[test]
+    fn hex_fields_apply_once_valid() {
+        let mut editor = ThemeEditor::default();
+
+        editor.set_hex(PaletteColor::Primary, "#12".to_string());
+        assert_eq!(editor.hex(PaletteColor::Primary), "#12");
+        assert_eq!(editor.color(PaletteColor::Primary), Palette::LIGHT.primary);
+
+        editor.set_hex(PaletteColor::Primary, "#123456".to_string());
+        assert_eq!(editor.color(PaletteColor::Primary), Color::from_rgb8(0x12, 0x34, 0x56));
+
+        // Non-ASCII text is not a color and must not panic
+        editor.set_hex(PaletteColor::Primary, "#1é3".to_string());
+        assert_eq!(editor.color(PaletteColor::Primary), Color::from_rgb8(0x12, 0x34, 0x56));
+    }
+
+    #[test]
+    fn sliders_change_the_selected_color_and_its_hex() {
+        let mut editor = ThemeEditor {
+            selected: PaletteColor::Danger,
+            ..ThemeEditor::default()
+        };
+
+        editor.set_channel(0, 255);
+        editor.set_channel(1, 0);
+        editor.set_channel(2, 0x80);
+
+        assert_eq!(editor.channels(), [255, 0, 0x80]);
+        assert_eq!(editor.hex(PaletteColor::Danger), "#ff0080");
+        assert_eq!(editor.palette.background, Palette::LIGHT.background);
+    }
+
+    #[test]
+    fn names_must_be_set_and_not_built_in() {
+        let mut editor = ThemeEditor::default();
+        assert!(editor.to_custom().is_err());
+
+        editor.name = "  dracula ".to_string();
+        assert!(editor.to_custom().is_err());
+
+        editor.name = " Sunrise ".to_string();
+        assert_eq!(editor.to_custom().unwrap().name, "Sunrise");
+    }
+
+    #[test]
+    fn saved_themes_load_back() {
+        let path = std::env::temp_dir().join(format!("icedapp-themes-{}.json", std::process::id()));
+        let mut editor = ThemeEditor::default();
+        editor.start_from(&Theme::Nord);
+        editor.name = "Nordish".to_string();
+        let theme = editor.to_custom().unwrap();
+
+        block_on(save_to(&path, std::slice::from_ref(&theme))).unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+        let loaded = load_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(json.contains(&format!("\"primary\": \"{}\"", Theme::Nord.palette().primary)));
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "Nordish");
+        // Hex keeps 8 bits per channel
+        let [r, g, b, _] = loaded[0].palette.text.into_rgba8();
+        assert_eq!(Color::from_rgb8(r, g, b).to_string(), Theme::Nord.palette().text.to_string());
+    }
+
+    #[test]
+    fn deleting_the_theme_in_use_falls_back_to_the_default() {
+        let mut app = App::default();
+        app.theme_editor.start_from(&Theme::Nord);
+        app.theme_editor.name = "Nordish".to_string();
+        let theme = app.theme_editor.to_custom().unwrap();
+        app.selected_theme = theme.to_theme();
+        app.custom_themes.push(theme);
+
+        let _ = app.update(Message::ThemeEditorDeleted);
+        assert!(app.custom_themes.is_empty());
+        assert_eq!(app.selected_theme, Theme::Light);
+
+        // The file is written in the background and reports back
+        let _ = app.update(Message::ThemesSaved(
+            "Deleted \"Nordish\"".to_string(),
+            Err("disk full".to_string()),
+        ));
+        assert_eq!(
+            app.theme_editor.status.as_deref(),
+            Some("Deleted \"Nordish\", but saving failed: disk full")
+        );
+    }
+
+    #[test]
+    fn bad_colors_are_reported() {
+        let json = r##"[{"name": "Broken", "background": "#fff", "text": "#000", "primary": "blue",
+            "success": "#0f0", "warning": "#ff0", "danger": "#f00"}]"##;
+        let error = serde_json::from_str::<Vec<CustomTheme>>(json).unwrap_err();
+        assert!(error.to_string().contains("Primary of `Broken`"));
+    }
+}
@@ -0,0 +1,542 @@
+use iced::theme::Palette;
+use iced::Color;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::persistence::Storage;
+use crate::styles::color::{mix, relative_luminance};
+use crate::styles::theme::{CustomTheme, ThemeType};
+
+/// Base16 slots the six palette colors come from; base24 schemes share them
+const BACKGROUND: &str = "base00";
+const TEXT: &str = "base05";
+const PRIMARY: &str = "base0D";
+const SUCCESS: &str = "base0B";
+const WARNING: &str = "base0A";
+const DANGER: &str = "base08";
+
+/// File formats themes are exchanged in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeFileFormat {
+    /// base16 or base24 scheme, as used by terminal and editor themes
+    Base16,
+    /// The `themes.json` entry format: a name and six `#rrggbb` colors
+    Json,
+}
+
+impl ThemeFileFormat {
+    pub const ALL: [ThemeFileFormat; 2] = [ThemeFileFormat::Base16, ThemeFileFormat::Json];
+
+    /// Format of a file, judged by its extension
+    pub fn of(path: &Path) -> Option<ThemeFileFormat> {
+        let extension = path.extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("yaml") || extension.eq_ignore_ascii_case("yml") {
+            Some(ThemeFileFormat::Base16)
+        } else if extension.eq_ignore_ascii_case("json") {
+            Some(ThemeFileFormat::Json)
+        } else {
+            None
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ThemeFileFormat::Base16 => "yaml",
+            ThemeFileFormat::Json => "json",
+        }
+    }
+}
+
+impl std::fmt::Display for ThemeFileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ThemeFileFormat::Base16 => "base16 YAML",
+                ThemeFileFormat::Json => "JSON",
+            }
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum PaletteError {
+    Io(std::io::Error),
+    /// Not a `.yaml`, `.yml` or `.json` file
+    UnknownFormat,
+    /// A YAML line that is not `key: value`
+    Syntax { line: usize, reason: String },
+    /// A base16 slot the scheme does not define
+    MissingColor(String),
+    InvalidColor { key: String, value: String },
+    Json(serde_json::Error),
+    /// A directory without any theme files
+    NoThemes,
+}
+
+impl std::fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteError::Io(error) => write!(f, "cannot access theme file: {error}"),
+            PaletteError::UnknownFormat => {
+                write!(f, "not a theme file; expected .yaml, .yml or .json")
+            }
+            PaletteError::Syntax { line, reason } => write!(f, "line {line}: {reason}"),
+            PaletteError::MissingColor(key) => write!(f, "incomplete palette: no {key}"),
+            PaletteError::InvalidColor { key, value } => {
+                write!(f, "{key} is not a hex color: \"{value}\"")
+            }
+            PaletteError::Json(error) => write!(f, "malformed theme file: {error}"),
+            PaletteError::NoThemes => write!(f, "no .yaml, .yml or .json files found"),
+        }
+    }
+}
+
+impl std::error::Error for PaletteError {}
+
+impl From<std::io::Error> for PaletteError {
+    fn from(error: std::io::Error) -> Self {
+        PaletteError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for PaletteError {
+    fn from(error: serde_json::Error) -> Self {
+        PaletteError::Json(error)
+    }
+}
+
+/// A file that could not be imported
+#[derive(Debug)]
+pub struct ImportError {
+    pub path: PathBuf,
+    pub error: PaletteError,
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.path.file_name().unwrap_or(self.path.as_os_str());
+        write!(f, "{}: {}", name.to_string_lossy(), self.error)
+    }
+}
+
+/// Themes from a file, or from every theme file directly inside a
+/// directory; files that fail are reported without stopping the rest
+///
+/// Reads in the background, as [`Task::perform`](iced::Task::perform) runs it.
+pub async fn import(path: PathBuf) -> (Vec<CustomTheme>, Vec<ImportError>) {
+    let fail = |path: &Path, error: PaletteError| {
+        (
+            Vec::new(),
+            vec![ImportError {
+                path: path.to_path_buf(),
+                error,
+            }],
+        )
+    };
+
+    if !is_dir(&path).await {
+        return match import_file(&path).await {
+            Ok(themes) => (themes, Vec::new()),
+            Err(error) => fail(&path, error),
+        };
+    }
+
+    let mut entries = match tokio::fs::read_dir(&path).await {
+        Ok(entries) => entries,
+        Err(error) => return fail(&path, error.into()),
+    };
+    let mut files: Vec<PathBuf> = Vec::new();
+    while let Some(entry) = entries.next_entry().await.ok().flatten() {
+        let file = entry.path();
+        if ThemeFileFormat::of(&file).is_some() && !is_dir(&file).await {
+            files.push(file);
+        }
+    }
+    if files.is_empty() {
+        return fail(&path, PaletteError::NoThemes);
+    }
+    files.sort();
+
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+    for file in files {
+        match import_file(&file).await {
+            Ok(imported) => themes.extend(imported),
+            Err(error) => errors.push(ImportError { path: file, error }),
+        }
+    }
+    (themes, errors)
+}
+
+async fn is_dir(path: &Path) -> bool {
+    tokio::fs::metadata(path).await.is_ok_and(|metadata| metadata.is_dir())
+}
+
+async fn import_file(path: &Path) -> Result<Vec<CustomTheme>, PaletteError> {
+    let format = ThemeFileFormat::of(path).ok_or(PaletteError::UnknownFormat)?;
+    let contents = tokio::fs::read_to_string(path).await?;
+    match format {
+        ThemeFileFormat::Base16 => {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            Ok(vec![parse_base16(&contents, &stem)?])
+        }
+        ThemeFileFormat::Json => parse_json(&contents),
+    }
+}
+
+/// A base16 or base24 scheme, in either the original flat layout
+/// (`scheme:` and `base00:` at the top level) or the newer one with a
+/// `palette:` section. Only the flat `key: value` subset of YAML these
+/// files use is understood.
+pub fn parse_base16(yaml: &str, fallback_name: &str) -> Result<CustomTheme, PaletteError> {
+    let mut values: Vec<(String, String)> = Vec::new();
+
+    for (index, line) in yaml.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            return Err(PaletteError::Syntax {
+                line: index + 1,
+                reason: format!("expected `key: value`, found \"{trimmed}\""),
+            });
+        };
+        let value = yaml_scalar(value.trim()).map_err(|reason| PaletteError::Syntax {
+            line: index + 1,
+            reason,
+        })?;
+        values.push((key.trim().to_string(), value));
+    }
+
+    let get = |key: &str| {
+        values
+            .iter()
+            .rev()
+            .find(|(candidate, _)| candidate.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+            .filter(|value| !value.is_empty())
+    };
+
+    // base24 adds base10 to base17; both need every one of their slots
+    let system = get("system").or(get("scheme-system")).unwrap_or("base16");
+    let is_base24 = system.eq_ignore_ascii_case("base24") || get("base10").is_some();
+    let slots = if is_base24 { 24 } else { 16 };
+    let mut colors = Vec::with_capacity(slots);
+    for slot in 0..slots {
+        let key = format!("base{slot:02X}");
+        let value = get(&key).ok_or_else(|| PaletteError::MissingColor(key.clone()))?;
+        let color = parse_color(&key, value)?;
+        colors.push((key, color));
+    }
+    let color = |key: &str| {
+        colors
+            .iter()
+            .find(|(slot, _)| slot == key)
+            .map(|(_, color)| *color)
+            .unwrap_or_default()
+    };
+
+    let name = get("name").or(get("scheme")).unwrap_or(fallback_name).trim();
+    Ok(CustomTheme {
+        name: imported_name(name),
+        palette: Palette {
+            background: color(BACKGROUND),
+            text: color(TEXT),
+            primary: color(PRIMARY),
+            success: color(SUCCESS),
+            warning: color(WARNING),
+            danger: color(DANGER),
+        },
+    })
+}
+
+/// A quoted or plain YAML scalar, without any trailing comment
+fn yaml_scalar(value: &str) -> Result<String, String> {
+    let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+        // A `#` only starts a comment after whitespace, so `#` alone is empty
+        let end = value.find(" #").unwrap_or(value.len());
+        return Ok(if value.starts_with('#') { "" } else { &value[..end] }
+            .trim()
+            .to_string());
+    };
+
+    let rest = &value[1..];
+    let end = rest
+        .find(quote)
+        .ok_or_else(|| format!("missing closing {quote} in {value}"))?;
+    let after = rest[end + 1..].trim();
+    if !after.is_empty() && !after.starts_with('#') {
+        return Err(format!("unexpected \"{after}\" after quoted value"));
+    }
+    Ok(rest[..end].to_string())
+}
+
+/// `rrggbb`, with or without `#`, as base16 files write it
+fn parse_color(key: &str, value: &str) -> Result<Color, PaletteError> {
+    let hex = value.trim().trim_start_matches('#');
+    let invalid = || PaletteError::InvalidColor {
+        key: key.to_string(),
+        value: value.to_string(),
+    };
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    hex.parse().map_err(|_| invalid())
+}
+
+/// One theme object or a list of them, each a `name` and the six
+/// `background`, `text`, `primary`, `success`, `warning` and `danger` colors
+pub fn parse_json(json: &str) -> Result<Vec<CustomTheme>, PaletteError> {
+    // Checking the shape first keeps serde's error pointing at the field
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let themes = if value.is_array() {
+        serde_json::from_value::<Vec<CustomTheme>>(value)?
+    } else {
+        vec![serde_json::from_value::<CustomTheme>(value)?]
+    };
+
+    Ok(themes
+        .into_iter()
+        .map(|theme| CustomTheme {
+            name: imported_name(&theme.name),
+            ..theme
+        })
+        .collect())
+}
+
+/// Keep imported schemes apart from the built-in themes of the same name
+fn imported_name(name: &str) -> String {
+    match ThemeType::named(name) {
+        Some(_) => format!("{name} (imported)"),
+        None => name.to_string(),
+    }
+}
+
+/// The theme as a base16 scheme; the slots a six-color palette lacks are
+/// blended from the ones it has
+pub fn to_base16(theme: &CustomTheme) -> String {
+    let palette = &theme.palette;
+    let (background, text) = (palette.background, palette.text);
+    let is_dark = relative_luminance(background) < relative_luminance(text);
+
+    let slots = [
+        background,
+        mix(background, text, 0.08),
+        mix(background, text, 0.18),
+        mix(background, text, 0.35),
+        mix(background, text, 0.6),
+        text,
+        mix(text, if is_dark { Color::WHITE } else { Color::BLACK }, 0.3),
+        mix(text, if is_dark { Color::WHITE } else { Color::BLACK }, 0.6),
+        palette.danger,
+        mix(palette.warning, palette.danger, 0.5),
+        palette.warning,
+        palette.success,
+        mix(palette.primary, palette.success, 0.5),
+        palette.primary,
+        mix(palette.primary, palette.danger, 0.5),
+        mix(palette.danger, background, 0.4),
+    ];
+
+    let mut yaml = format!(
+        "system: \"base16\"\nname: \"{}\"\nauthor: \"icedapp\"\nvariant: \"{}\"\npalette:\n",
+        theme.name.replace('"', "'"),
+        if is_dark { "dark" } else { "light" },
+    );
+    for (slot, color) in slots.into_iter().enumerate() {
+        yaml.push_str(&format!("  base{slot:02X}: \"{}\"\n", Color { a: 1.0, ..color }));
+    }
+    yaml
+}
+
+pub fn to_json(theme: &CustomTheme) -> Result<String, PaletteError> {
+    Ok(serde_json::to_string_pretty(theme)?)
+}
+
+/// Write `theme` into the working directory as `<name>.yaml` or
+/// `<name>.json`, numbered rather than replacing an earlier export
+pub fn export(storage: &Storage, theme: &CustomTheme, format: ThemeFileFormat) -> Result<PathBuf, PaletteError> {
+    let contents = match format {
+        ThemeFileFormat::Base16 => to_base16(theme),
+        ThemeFileFormat::Json => to_json(theme)?,
+    };
+    let path = storage.unused_work_file(&slug(theme), format.extension());
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// The theme's name as a file name, like `solarized-dusk`
+fn slug(theme: &CustomTheme) -> String {
+    let slug: String = theme
+        .name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() { "theme" } else { slug }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::executor;
+
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        executor::Default::new().unwrap().block_on(future)
+    }
+
+    const OCEAN: &str = r##"
+scheme: "Ocean" # the original layout
+author: "Chris Kempson (http://chriskempson.com)"
+base00: "2b303b"
+base01: "343d46"
+base02: "4f5b66"
+base03: "65737e"
+base04: "a7adba"
+base05: "c0c5ce"
+base06: "dfe1e8"
+base07: "eff1f5"
+base08: "bf616a"
+base09: "d08770"
+base0A: "ebcb8b"
+base0B: "a3be8c"
+base0C: "96b5b4"
+base0D: "8fa1b3"
+base0E: "b48ead"
+base0F: "ab7967"
+"##;
+
+    fn rgb(hex: &str) -> Color {
+        hex.parse().unwrap()
+    }
+
+    #[test]
+    fn base16_maps_onto_the_palette() {
+        let theme = parse_base16(OCEAN, "ocean").unwrap();
+
+        assert_eq!(theme.name, "Ocean");
+        assert_eq!(theme.palette.background, rgb("2b303b"));
+        assert_eq!(theme.palette.text, rgb("c0c5ce"));
+        assert_eq!(theme.palette.primary, rgb("8fa1b3"));
+        assert_eq!(theme.palette.success, rgb("a3be8c"));
+        assert_eq!(theme.palette.warning, rgb("ebcb8b"));
+        assert_eq!(theme.palette.danger, rgb("bf616a"));
+    }
+
+    #[test]
+    fn newer_layout_with_a_palette_section() {
+        let mut yaml = String::from("system: \"base24\"\nname: 'Deep'\nvariant: dark\npalette:\n");
+        for slot in 0..24 {
+            yaml.push_str(&format!("  base{slot:02X}: \"#{:02x}0000\"\n", slot * 10));
+        }
+
+        let theme = parse_base16(&yaml, "fallback").unwrap();
+        assert_eq!(theme.name, "Deep");
+        assert_eq!(theme.palette.text, Color::from_rgb8(50, 0, 0));
+
+        // base24 needs its extra slots too
+        let cut = yaml.replace("  base17: \"#e60000\"\n", "");
+        assert!(matches!(
+            parse_base16(&cut, "fallback"),
+            Err(PaletteError::MissingColor(key)) if key == "base17"
+        ));
+    }
+
+    #[test]
+    fn malformed_schemes_say_what_is_wrong() {
+        let missing = OCEAN.replace("base0D: \"8fa1b3\"\n", "");
+        assert_eq!(
+            parse_base16(&missing, "ocean").unwrap_err().to_string(),
+            "incomplete palette: no base0D"
+        );
+
+        let invalid = OCEAN.replace("\"bf616a\"", "\"reddish\"");
+        assert_eq!(
+            parse_base16(&invalid, "ocean").unwrap_err().to_string(),
+            "base08 is not a hex color: \"reddish\""
+        );
+
+        let syntax = OCEAN.replace("base02: \"4f5b66\"", "base02 \"4f5b66\"");
+        assert!(matches!(
+            parse_base16(&syntax, "ocean"),
+            Err(PaletteError::Syntax { line: 6, .. })
+        ));
+
+        let unclosed = OCEAN.replace("\"65737e\"", "\"65737e");
+        assert!(parse_base16(&unclosed, "ocean").is_err());
+    }
+
+    #[test]
+    fn names_fall_back_and_avoid_built_ins() {
+        let unnamed = OCEAN.replace("scheme: \"Ocean\" # the original layout\n", "");
+        assert_eq!(parse_base16(&unnamed, "ocean-file").unwrap().name, "ocean-file");
+
+        let nord = OCEAN.replace("\"Ocean\"", "\"Nord\"");
+        assert_eq!(parse_base16(&nord, "nord").unwrap().name, "Nord (imported)");
+    }
+
+    #[test]
+    fn json_holds_one_theme_or_a_list() {
+        let one = r##"{"name": "Mint", "background": "#ffffff", "text": "#111111",
+            "primary": "#3eb489", "success": "#00aa00", "warning": "#ffaa00", "danger": "#cc0000"}"##;
+        assert_eq!(parse_json(one).unwrap()[0].palette.primary, rgb("3eb489"));
+        assert_eq!(parse_json(&format!("[{one}, {one}]")).unwrap().len(), 2);
+
+        let incomplete = one.replace(", \"danger\": \"#cc0000\"", "");
+        assert!(parse_json(&incomplete).unwrap_err().to_string().contains("missing field `danger`"));
+    }
+
+    #[test]
+    fn exports_import_back() {
+        let theme = parse_base16(OCEAN, "ocean").unwrap();
+
+        let yaml = to_base16(&theme);
+        assert!(yaml.contains("variant: \"dark\""));
+        assert_eq!(parse_base16(&yaml, "x").unwrap(), theme);
+
+        let json = to_json(&theme).unwrap();
+        assert_eq!(parse_json(&json).unwrap(), vec![theme]);
+    }
+
+    #[test]
+    fn directories_import_every_theme_file() {
+        let dir = std::env::temp_dir().join(format!("icedapp-theme-import-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ocean.yaml"), OCEAN).unwrap();
+        std::fs::write(dir.join("broken.yml"), "scheme: \"Broken\"\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a theme").unwrap();
+
+        let (themes, errors) = block_on(import(dir.clone()));
+        let missing = block_on(import(dir.join("nothing.yaml")));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(themes.len(), 1);
+        assert_eq!(themes[0].name, "Ocean");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "broken.yml: incomplete palette: no base00");
+        assert!(matches!(missing.1[0].error, PaletteError::Io(_)));
+    }
+
+    #[test]
+    fn exports_are_named_after_the_theme_and_never_overwrite() {
+        let dir = std::env::temp_dir().join(format!("icedapp-theme-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let storage = Storage {
+            config_dir: None,
+            work_dir: dir.clone(),
+        };
+        let theme = parse_base16(OCEAN, "ocean").unwrap();
+
+        let first = export(&storage, &theme, ThemeFileFormat::Base16).unwrap();
+        let second = export(&storage, &theme, ThemeFileFormat::Base16).unwrap();
+        let json = export(&storage, &theme, ThemeFileFormat::Json).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, dir.join("ocean.yaml"));
+        assert_eq!(second, dir.join("ocean-2.yaml"));
+        assert_eq!(json, dir.join("ocean.json"));
+    }
+}
@@ -1,30 +1,388 @@
-use std::time::Instant;
+use iced::Color;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
+use crate::message::Message;
+
+pub use super::easing::Easing;
+use super::spring::Spring;
+
+/// Duration shared by the view transition, button fade and config drawer
+pub const DEFAULT_DURATION: Duration = Duration::from_millis(300);
+
+/// Which end of the animation is being approached
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AnimationDirection {
-    FadeIn,
-    FadeOut,
-    None,
-}
-
-/// Calculate animation progress with ease-out cubic easing
-pub fn get_animation_progress(start: Option<Instant>, duration_ms: f32) -> f32 {
-    if let Some(start_time) = start {
-        let elapsed = start_time.elapsed().as_millis() as f32;
-        let progress = (elapsed / duration_ms).min(1.0);
-        
-        // Ease-out cubic for smooth deceleration
-        1.0 - (1.0 - progress).powi(3)
-    } else {
-        1.0
-    }
-}
-
-/// Calculate opacity based on animation direction and progress
-pub fn get_opacity(direction: AnimationDirection, progress: f32) -> f32 {
-    match direction {
-        AnimationDirection::FadeIn => progress,
-        AnimationDirection::FadeOut => 1.0 - progress,
-        AnimationDirection::None => 1.0,
+pub enum Direction {
+    /// Progress runs towards 1.0
+    Forward,
+    /// Progress runs back towards 0.0
+    Reverse,
+}
+
+/// How an [`Animation`] moves between its ends
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Curve {
+    /// Along an easing curve, taking the animation's duration
+    Eased(Easing),
+    /// Critically damped spring settling in about the duration; reversing
+    /// mid-flight keeps the current velocity instead of turning sharply
+    Spring,
+}
+
+impl Curve {
+    /// Every curve an animation can be given, in menu order
+    pub fn all() -> Vec<Curve> {
+        Easing::ALL
+            .into_iter()
+            .chain([Easing::EASE])
+            .map(Curve::Eased)
+            .chain([Curve::Spring])
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Curve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Curve::Eased(easing) => write!(f, "{easing}"),
+            Curve::Spring => write!(f, "Spring"),
+        }
+    }
+}
+
+/// A single 0.0..=1.0 animation that can be reversed mid-flight
+///
+/// All queries take `now` explicitly, so the math runs without a window or
+/// a real clock.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    duration: Duration,
+    curve: Curve,
+    /// Position and velocity while following [`Curve::Spring`]
+    spring: Spring,
+    direction: Direction,
+    /// Linear progress at the moment the current run started
+    from: f32,
+    /// `None` while idle at the end selected by `direction`
+    started_at: Option<Instant>,
+    on_complete: Option<Message>,
+}
+
+impl Animation {
+    /// Idle animation resting at 0.0
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            curve: Curve::Eased(Easing::CubicOut),
+            spring: Spring::with_settle_time(0.0, duration),
+            direction: Direction::Reverse,
+            from: 0.0,
+            started_at: None,
+            on_complete: None,
+        }
+    }
+
+    pub fn easing(self, easing: Easing) -> Self {
+        self.curve(Curve::Eased(easing))
+    }
+
+    pub fn curve(mut self, curve: Curve) -> Self {
+        self.set_curve(curve);
+        self
+    }
+
+    /// Idle animation resting at 1.0 instead
+    pub fn completed(mut self) -> Self {
+        self.direction = Direction::Forward;
+        self.from = 1.0;
+        self.spring = Spring::with_settle_time(1.0, self.duration);
+        self
+    }
+
+    pub fn current_curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// Switch curves; a run in progress finishes on the new one
+    pub fn set_curve(&mut self, curve: Curve) {
+        self.curve = curve;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    /// Run towards `direction`, continuing from the current position if the
+    /// animation is interrupted. `on_complete` is returned by [`Animation::tick`]
+    /// once the run finishes.
+    pub fn play(&mut self, direction: Direction, now: Instant, on_complete: Option<Message>) {
+        self.spring = self.spring_at(now);
+        self.from = self.linear_progress(now);
+        self.direction = direction;
+        self.started_at = Some(now);
+        self.on_complete = on_complete;
+
+        self.spring.set_target(self.target());
+        self.spring.step_to(now);
+    }
+
+    /// Jump back to the start and run forward again
+    pub fn restart(&mut self, now: Instant) {
+        self.restart_from(0.0, now);
+    }
+
+    /// Jump to `progress` (linear, 0.0..=1.0) and run forward from there
+    pub fn restart_from(&mut self, progress: f32, now: Instant) {
+        self.direction = Direction::Forward;
+        self.from = progress.clamp(0.0, 1.0);
+        self.started_at = Some(now);
+        self.on_complete = None;
+
+        self.spring = Spring::with_settle_time(self.from, self.duration);
+        self.spring.set_target(1.0);
+        self.spring.step_to(now);
+    }
+
+    /// Progress without easing, 0.0..=1.0; a spring's position is already
+    /// its own curve
+    pub fn linear_progress(&self, now: Instant) -> f32 {
+        let target = self.target();
+        let Some(started_at) = self.started_at else {
+            return target;
+        };
+        if self.curve == Curve::Spring {
+            return self.spring_at(now).position().clamp(0.0, 1.0);
+        }
+
+        // A partial run only takes the remaining share of the duration
+        let distance = (target - self.from).abs();
+        if distance == 0.0 || self.duration.is_zero() {
+            return target;
+        }
+
+        let elapsed = now.saturating_duration_since(started_at).as_secs_f32();
+        let step = elapsed / self.duration.as_secs_f32();
+        if step >= distance {
+            return target;
+        }
+
+        self.from + step * (target - self.from).signum()
+    }
+
+    /// Eased progress, 0.0..=1.0
+    pub fn progress(&self, now: Instant) -> f32 {
+        match self.curve {
+            Curve::Eased(easing) => easing.apply(self.linear_progress(now)),
+            Curve::Spring => self.linear_progress(now),
+        }
+    }
+
+    /// Settle a finished run, returning its completion message
+    pub fn tick(&mut self, now: Instant) -> Option<Message> {
+        if !self.is_active() {
+            return None;
+        }
+
+        self.spring = self.spring_at(now);
+        let finished = match self.curve {
+            Curve::Eased(_) => self.linear_progress(now) == self.target(),
+            Curve::Spring => !self.spring.is_active(),
+        };
+        if finished {
+            self.started_at = None;
+            self.from = self.target();
+            self.spring = Spring::with_settle_time(self.from, self.duration);
+            return self.on_complete.take();
+        }
+        None
+    }
+
+    /// The spring advanced to `now`; it is kept stepping whatever the curve,
+    /// so switching to it mid-flight does not start from a stale position
+    fn spring_at(&self, now: Instant) -> Spring {
+        let mut spring = self.spring;
+        spring.step_to(now);
+        spring
+    }
+
+    fn target(&self) -> f32 {
+        match self.direction {
+            Direction::Forward => 1.0,
+            Direction::Reverse => 0.0,
+        }
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::new(DEFAULT_DURATION)
+    }
+}
+
+/// Values that can be blended by a [`Tween`]
+pub trait Interpolate: Clone {
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Color::from_rgba(
+            self.r.interpolate(&other.r, t),
+            self.g.interpolate(&other.g, t),
+            self.b.interpolate(&other.b, t),
+            self.a.interpolate(&other.a, t),
+        )
+    }
+}
+
+/// Animated transition between two values of `T`
+#[derive(Debug, Clone)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    animation: Animation,
+}
+
+impl<T: Interpolate> Tween<T> {
+    /// Tween resting at `value`
+    pub fn new(value: T, duration: Duration) -> Self {
+        Self {
+            from: value.clone(),
+            to: value,
+            animation: Animation::new(duration),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.animation.is_active()
+    }
+
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    pub fn value(&self, now: Instant) -> T {
+        self.from.interpolate(&self.to, self.animation.progress(now))
+    }
+
+    /// Start moving towards `to` from wherever the tween currently is
+    pub fn retarget(&mut self, to: T, now: Instant) {
+        self.from = self.value(now);
+        self.to = to;
+        self.animation.restart(now);
+    }
+
+    pub fn tick(&mut self, now: Instant) -> Option<Message> {
+        self.animation.tick(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn idle_animation_rests_at_zero() {
+        let animation = Animation::new(STEP * 3);
+        assert!(!animation.is_active());
+        assert_eq!(animation.progress(Instant::now()), 0.0);
+    }
+
+    #[test]
+    fn forward_run_completes_with_message() {
+        let start = Instant::now();
+        let mut animation = Animation::new(STEP * 3).easing(Easing::Linear);
+        animation.play(Direction::Forward, start, Some(Message::ConfigMenuInteraction));
+
+        assert!((animation.progress(start + STEP) - 1.0 / 3.0).abs() < 1e-4);
+        assert!(animation.tick(start + STEP).is_none());
+        assert!(matches!(
+            animation.tick(start + STEP * 3),
+            Some(Message::ConfigMenuInteraction)
+        ));
+        assert!(!animation.is_active());
+        assert_eq!(animation.progress(start + STEP * 10), 1.0);
+    }
+
+    #[test]
+    fn reversal_continues_from_current_position() {
+        let start = Instant::now();
+        let mut animation = Animation::new(STEP * 4).easing(Easing::Linear);
+        animation.play(Direction::Forward, start, None);
+        animation.play(Direction::Reverse, start + STEP, None);
+
+        // Reversing at 25% only needs a quarter of the duration to get back
+        assert!((animation.progress(start + STEP) - 0.25).abs() < 1e-4);
+        assert_eq!(animation.progress(start + STEP * 2), 0.0);
+        assert!(animation.tick(start + STEP * 2).is_none());
+        assert!(!animation.is_active());
+    }
+
+    #[test]
+    fn spring_run_settles_with_message() {
+        let start = Instant::now();
+        let mut animation = Animation::new(STEP * 3).curve(Curve::Spring);
+        animation.play(Direction::Forward, start, Some(Message::ConfigMenuInteraction));
+
+        let early = animation.progress(start + STEP / 2);
+        assert!(early > 0.0 && early < 1.0);
+        assert!(animation.tick(start + STEP).is_none());
+        assert!(matches!(
+            animation.tick(start + STEP * 6),
+            Some(Message::ConfigMenuInteraction)
+        ));
+        assert!(!animation.is_active());
+        assert_eq!(animation.progress(start + STEP * 10), 1.0);
+    }
+
+    #[test]
+    fn spring_reversal_keeps_its_velocity() {
+        let start = Instant::now();
+        let mut animation = Animation::new(STEP * 3).curve(Curve::Spring);
+        animation.play(Direction::Forward, start, None);
+
+        let turn = start + STEP;
+        let at_turn = animation.progress(turn);
+        animation.play(Direction::Reverse, turn, None);
+
+        // Still moving forward for a moment, then heading back
+        assert!(animation.progress(turn + STEP / 10) > at_turn);
+        assert!(animation.progress(turn + STEP * 2) < at_turn);
+        assert!(animation.tick(turn + STEP * 6).is_none());
+        assert_eq!(animation.progress(turn + STEP * 6), 0.0);
+    }
+
+    #[test]
+    fn curves_can_change_between_runs() {
+        let start = Instant::now();
+        let mut animation = Animation::new(STEP * 2).easing(Easing::Linear);
+        animation.set_curve(Curve::Eased(Easing::QuadIn));
+        animation.play(Direction::Forward, start, None);
+
+        assert_eq!(animation.current_curve(), Curve::Eased(Easing::QuadIn));
+        assert!((animation.progress(start + STEP) - 0.25).abs() < 1e-4);
+        assert!(Curve::all().contains(&Curve::Spring));
+    }
+
+    #[test]
+    fn tween_retargets_from_current_value() {
+        let start = Instant::now();
+        let mut tween = Tween::new(0.0f32, STEP * 2);
+        tween.retarget(10.0, start);
+        // Halfway along the default cubic ease-out
+        let halfway = 10.0 * Easing::CubicOut.apply(0.5);
+        assert!((tween.value(start + STEP) - halfway).abs() < 1e-4);
+
+        tween.retarget(0.0, start + STEP);
+        assert!((tween.value(start + STEP) - halfway).abs() < 1e-4);
+        assert!((tween.value(start + STEP * 3) - 0.0).abs() < 1e-4);
     }
 }
@@ -0,0 +1,21 @@
+use std::time::Instant;
+
+/// Source of "now" for everything time-based in the app
+///
+/// Live runs read the system clock; session replays and tests drive a
+/// manual clock so animations and timeouts play out identically every time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Clock {
+    #[default]
+    System,
+    Manual(Instant),
+}
+
+impl Clock {
+    pub fn now(&self) -> Instant {
+        match self {
+            Clock::System => Instant::now(),
+            Clock::Manual(now) => *now,
+        }
+    }
+}
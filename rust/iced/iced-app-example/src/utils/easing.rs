@@ -0,0 +1,287 @@
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Easing curves mapping linear progress in 0.0..=1.0 onto eased progress
+///
+/// Every curve starts at 0.0 and ends at 1.0. `Back` and `Elastic` overshoot
+/// in between and `Bounce` rebounds; all the others are monotonic.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    /// CSS-style `cubic-bezier(x1, y1, x2, y2)`
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    /// Every named curve, in menu order
+    pub const ALL: [Easing; 22] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuartIn,
+        Easing::QuartOut,
+        Easing::QuartInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    /// CSS `ease`
+    pub const EASE: Easing = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+
+    /// Map linear progress in 0.0..=1.0 onto the curve
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t.powi(2),
+            Easing::QuadOut => out(t, |t| t.powi(2)),
+            Easing::QuadInOut => in_out(t, |t| t.powi(2)),
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => out(t, |t| t.powi(3)),
+            Easing::CubicInOut => in_out(t, |t| t.powi(3)),
+            Easing::QuartIn => t.powi(4),
+            Easing::QuartOut => out(t, |t| t.powi(4)),
+            Easing::QuartInOut => in_out(t, |t| t.powi(4)),
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => out(t, expo_in),
+            Easing::ExpoInOut => in_out(t, expo_in),
+            Easing::BackIn => back_in(t),
+            Easing::BackOut => out(t, back_in),
+            Easing::BackInOut => in_out(t, back_in),
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => out(t, elastic_in),
+            Easing::ElasticInOut => in_out(t, elastic_in),
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => in_out(t, |t| 1.0 - bounce_out(1.0 - t)),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(t, x1, y1, x2, y2),
+        }
+    }
+}
+
+impl std::fmt::Display for Easing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            _ if *self == Easing::EASE => write!(f, "Ease"),
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                write!(f, "Cubic Bézier ({x1}, {y1}, {x2}, {y2})")
+            }
+            _ => write!(f, "{self:?}"),
+        }
+    }
+}
+
+/// Mirror an ease-in curve into its ease-out counterpart
+fn out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    1.0 - ease_in(1.0 - t)
+}
+
+/// Ease in over the first half and out over the second
+fn in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(t * 2.0) / 2.0
+    } else {
+        1.0 - ease_in((1.0 - t) * 2.0) / 2.0
+    }
+}
+
+fn expo_in(t: f32) -> f32 {
+    if t == 0.0 { 0.0 } else { 2f32.powf(10.0 * t - 10.0) }
+}
+
+fn back_in(t: f32) -> f32 {
+    // Standard overshoot of ~10%
+    const C1: f32 = 1.70158;
+    const C3: f32 = C1 + 1.0;
+    C3 * t.powi(3) - C1 * t.powi(2)
+}
+
+fn elastic_in(t: f32) -> f32 {
+    if t == 0.0 || t == 1.0 {
+        return t;
+    }
+    const C4: f32 = (2.0 * PI) / 3.0;
+    -(2f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * C4).sin()
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N1: f32 = 7.5625;
+    const D1: f32 = 2.75;
+
+    if t < 1.0 / D1 {
+        N1 * t * t
+    } else if t < 2.0 / D1 {
+        let t = t - 1.5 / D1;
+        N1 * t * t + 0.75
+    } else if t < 2.5 / D1 {
+        let t = t - 2.25 / D1;
+        N1 * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D1;
+        N1 * t * t + 0.984375
+    }
+}
+
+/// Evaluate a CSS cubic-bezier timing function at `x`
+fn cubic_bezier(x: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    // Bernstein form of one coordinate with P0 = 0 and P3 = 1
+    let sample = |s: f32, p1: f32, p2: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * s * p1 + 3.0 * inv * s * s * p2 + s * s * s
+    };
+    let slope = |s: f32, p1: f32, p2: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * p1 + 6.0 * inv * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+    };
+
+    // Newton-Raphson is fast for typical curves...
+    let mut s = x;
+    for _ in 0..8 {
+        let error = sample(s, x1, x2) - x;
+        if error.abs() < 1e-6 {
+            return sample(s, y1, y2);
+        }
+        let derivative = slope(s, x1, x2);
+        if derivative.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / derivative).clamp(0.0, 1.0);
+    }
+
+    // ...and bisection catches the flat ones it cannot handle
+    let (mut low, mut high) = (0.0f32, 1.0f32);
+    s = x;
+    for _ in 0..32 {
+        let value = sample(s, x1, x2);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+
+    sample(s, y1, y2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: usize = 200;
+
+    fn is_monotonic(easing: Easing) -> bool {
+        (0..SAMPLES).all(|i| {
+            let a = easing.apply(i as f32 / SAMPLES as f32);
+            let b = easing.apply((i + 1) as f32 / SAMPLES as f32);
+            b >= a - 1e-5
+        })
+    }
+
+    #[test]
+    fn every_curve_hits_both_endpoints() {
+        let curves = Easing::ALL
+            .into_iter()
+            .chain([Easing::EASE, Easing::CubicBezier(0.42, 0.0, 0.58, 1.0)]);
+
+        for easing in curves {
+            assert!(easing.apply(0.0).abs() < 1e-4, "{easing} at 0.0");
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-4, "{easing} at 1.0");
+        }
+    }
+
+    #[test]
+    fn smooth_curves_are_monotonic() {
+        let curves = Easing::ALL
+            .into_iter()
+            .filter(|easing| {
+                !matches!(
+                    easing,
+                    Easing::BackIn
+                        | Easing::BackOut
+                        | Easing::BackInOut
+                        | Easing::ElasticIn
+                        | Easing::ElasticOut
+                        | Easing::ElasticInOut
+                        | Easing::BounceIn
+                        | Easing::BounceOut
+                        | Easing::BounceInOut
+                )
+            })
+            .chain([Easing::EASE]);
+
+        for easing in curves {
+            assert!(is_monotonic(easing), "{easing} is not monotonic");
+        }
+    }
+
+    #[test]
+    fn overshooting_curves_leave_the_unit_range() {
+        assert!(Easing::BackIn.apply(0.2) < 0.0);
+        assert!(Easing::BackOut.apply(0.8) > 1.0);
+        assert!(Easing::ElasticOut.apply(0.1) > 1.0);
+    }
+
+    #[test]
+    fn bounce_stays_in_the_unit_range() {
+        for i in 0..=SAMPLES {
+            let value = Easing::BounceOut.apply(i as f32 / SAMPLES as f32);
+            assert!((-1e-5..=1.0 + 1e-5).contains(&value));
+        }
+    }
+
+    #[test]
+    fn linear_bezier_matches_linear() {
+        let easing = Easing::CubicBezier(0.0, 0.0, 1.0, 1.0);
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            assert!((easing.apply(t) - t).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn in_out_curves_are_symmetric() {
+        for easing in [Easing::QuadInOut, Easing::CubicInOut, Easing::ExpoInOut] {
+            assert!((easing.apply(0.5) - 0.5).abs() < 1e-4, "{easing}");
+            assert!((easing.apply(0.25) + easing.apply(0.75) - 1.0).abs() < 1e-4, "{easing}");
+        }
+    }
+}
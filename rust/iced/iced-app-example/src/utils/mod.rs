@@ -1 +1,4 @@
 pub mod animation;
+pub mod clock;
+pub mod easing;
+pub mod spring;
@@ -0,0 +1,136 @@
+use std::time::{Duration, Instant};
+
+/// Positions and velocities closer than this to rest count as settled
+const REST_THRESHOLD: f32 = 1e-3;
+
+/// Critically damped spring - the fastest motion towards the target that
+/// never overshoots
+///
+/// Unlike an eased [`super::animation::Animation`], retargeting keeps the
+/// current velocity, so interrupted motion stays smooth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    position: f32,
+    velocity: f32,
+    target: f32,
+    /// Natural angular frequency, higher is snappier
+    omega: f32,
+    last_step: Option<Instant>,
+}
+
+impl Spring {
+    /// Spring at rest on `position`
+    pub fn new(position: f32, stiffness: f32) -> Self {
+        Self {
+            position,
+            velocity: 0.0,
+            target: position,
+            omega: stiffness.max(0.0).sqrt(),
+            last_step: None,
+        }
+    }
+
+    /// Spring that settles in roughly `duration`
+    pub fn with_settle_time(position: f32, duration: Duration) -> Self {
+        // A critically damped spring is within ~0.2% of its target after 8/omega
+        let omega = 8.0 / duration.as_secs_f32().max(f32::EPSILON);
+        Self::new(position, omega * omega)
+    }
+
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+
+    pub fn is_active(&self) -> bool {
+        (self.position - self.target).abs() > REST_THRESHOLD || self.velocity.abs() > REST_THRESHOLD
+    }
+
+    /// Move the rest point, keeping the current position and velocity
+    pub fn set_target(&mut self, target: f32) {
+        // A spring at rest starts timing from its next step, not its last one
+        if !self.is_active() {
+            self.last_step = None;
+        }
+        self.target = target;
+    }
+
+    /// Advance the simulation to `now`
+    pub fn step_to(&mut self, now: Instant) {
+        if let Some(last) = self.last_step {
+            self.step(now.saturating_duration_since(last).as_secs_f32());
+        }
+        self.last_step = Some(now);
+    }
+
+    /// Advance the simulation by `dt` seconds using the exact solution, so
+    /// large or uneven frame times stay stable
+    pub fn step(&mut self, dt: f32) {
+        if !self.is_active() {
+            self.position = self.target;
+            self.velocity = 0.0;
+            return;
+        }
+
+        let displacement = self.position - self.target;
+        let c2 = self.velocity + self.omega * displacement;
+        let decay = (-self.omega * dt).exp();
+
+        self.position = self.target + (displacement + c2 * dt) * decay;
+        self.velocity = (c2 - self.omega * (displacement + c2 * dt)) * decay;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settles_on_target_without_overshoot() {
+        let mut spring = Spring::new(0.0, 100.0);
+        spring.set_target(1.0);
+
+        let mut previous = spring.position();
+        for _ in 0..200 {
+            spring.step(1.0 / 60.0);
+            assert!(spring.position() <= 1.0 + 1e-4);
+            assert!(spring.position() >= previous - 1e-6);
+            previous = spring.position();
+        }
+        assert!(!spring.is_active());
+    }
+
+    #[test]
+    fn retargeting_preserves_velocity() {
+        let mut spring = Spring::new(0.0, 100.0);
+        spring.set_target(1.0);
+        spring.step(0.05);
+
+        let velocity = spring.velocity;
+        assert!(velocity > 0.0);
+
+        spring.set_target(0.0);
+        assert_eq!(spring.velocity, velocity);
+    }
+
+    #[test]
+    fn step_size_does_not_change_the_result() {
+        let mut coarse = Spring::new(0.0, 50.0);
+        let mut fine = coarse;
+        coarse.set_target(10.0);
+        fine.set_target(10.0);
+
+        coarse.step(0.2);
+        for _ in 0..20 {
+            fine.step(0.01);
+        }
+        assert!((coarse.position() - fine.position()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn settle_time_is_respected() {
+        let mut spring = Spring::with_settle_time(0.0, Duration::from_millis(300));
+        spring.set_target(1.0);
+        spring.step(0.3);
+        assert!((spring.position() - 1.0).abs() < 0.01);
+    }
+}
@@ -1,20 +1,27 @@
 use iced::widget::{button, checkbox, column, container, pick_list, row, slider, text, text_input, Column};
-use iced::Theme;
+use iced::Task;
 
 use crate::app::App;
 use crate::message::{BrowserOption, Message};
-use crate::styles::plain::{plain_button_style, plain_container_style, plain_fieldset_style, plain_input_style};
-use crate::styles::web::{
-    bootstrap_button_style, bootstrap_card_style, bootstrap_danger_button_style,
-    bootstrap_input_style, bootstrap_secondary_button_style, bootstrap_success_button_style,
-    form_group_style,
+use crate::views::{Update, View, ViewId};
+
+pub const VIEW: View = View {
+    id: ViewId("browser-form"),
+    title: "Browser Form",
+    icon: "🌐",
+    order: 3,
+    sections: &[],
+    view: create_browser_form_view,
+    update,
 };
 
 pub fn create_browser_form_view(app: &App) -> Column<'_, Message> {
+    let skin = app.skin;
+
     column![
         // Header
-        text("Plain HTML Style Form").size(32),
-        text("Demonstrating unstyled HTML appearance with iced").size(14),
+        text(format!("{skin} Style Form")).size(32),
+        text("Browser-like form controls in the skin picked in the configuration menu").size(14),
         
         // Form Card
         container(
@@ -26,11 +33,11 @@ pub fn create_browser_form_view(app: &App) -> Column<'_, Message> {
                         text_input("Enter text...", &app.browser_text)
                             .on_input(Message::BrowserTextChanged)
                             .padding(5)
-                            .style(plain_input_style),
+                            .style(skin.input()),
                     ]
                     .spacing(5)
                 )
-                .style(plain_fieldset_style)
+                .style(skin.fieldset())
                 .padding(10),
                 // Password Input Group
                 container(
@@ -40,11 +47,11 @@ pub fn create_browser_form_view(app: &App) -> Column<'_, Message> {
                             .on_input(Message::BrowserPasswordChanged)
                             .secure(true)
                             .padding(5)
-                            .style(plain_input_style),
+                            .style(skin.input()),
                     ]
                     .spacing(5)
                 )
-                .style(plain_fieldset_style)
+                .style(skin.fieldset())
                 .padding(10),
                 // Slider Group
                 container(
@@ -54,7 +61,7 @@ pub fn create_browser_form_view(app: &App) -> Column<'_, Message> {
                     ]
                     .spacing(5)
                 )
-                .style(plain_fieldset_style)
+                .style(skin.fieldset())
                 .padding(10),
                 // Checkbox Group
                 container(
@@ -64,7 +71,7 @@ pub fn create_browser_form_view(app: &App) -> Column<'_, Message> {
                     ]
                     .spacing(5)
                 )
-                .style(plain_fieldset_style)
+                .style(skin.fieldset())
                 .padding(10),
                 // Dropdown Group
                 container(
@@ -80,7 +87,7 @@ pub fn create_browser_form_view(app: &App) -> Column<'_, Message> {
                     ]
                     .spacing(5)
                 )
-                .style(plain_fieldset_style)
+                .style(skin.fieldset())
                 .padding(10),
                 // Buttons
                 container(
@@ -88,14 +95,14 @@ pub fn create_browser_form_view(app: &App) -> Column<'_, Message> {
                         button("Submit")
                             .on_press(Message::BrowserFormSubmit)
                             .padding(8)
-                            .style(plain_button_style),
+                            .style(skin.success_button()),
                         button("Reset")
                             .on_press(Message::BrowserFormSubmit)
                             .padding(8)
-                            .style(plain_button_style),
+                            .style(skin.secondary_button()),
                         button("Cancel")
                             .padding(8)
-                            .style(plain_button_style),
+                            .style(skin.danger_button()),
                     ]
                     .spacing(5)
                 )
@@ -103,12 +110,41 @@ pub fn create_browser_form_view(app: &App) -> Column<'_, Message> {
             ]
             .spacing(5)
         )
-        .style(plain_container_style)
+        .style(skin.container())
         .padding(10),
         // Footer note
-        text("This view demonstrates plain HTML-style controls (no CSS), while other views use custom styling")
+        text("Switching the skin restyles these controls along with the rest of the app")
             .size(12),
     ]
     .spacing(10)
     .padding(20)
 }
+
+fn update(app: &mut App, message: Message) -> Update {
+    match message {
+        Message::BrowserTextChanged(text) => {
+            app.browser_text = text;
+        }
+        Message::BrowserPasswordChanged(password) => {
+            app.browser_password = password;
+        }
+        Message::BrowserSliderChanged(value) => {
+            app.browser_slider_value = value;
+        }
+        Message::BrowserCheckboxToggled(checked) => {
+            app.browser_checkbox = checked;
+        }
+        Message::BrowserOptionSelected(option) => {
+            app.browser_selected_option = Some(option);
+        }
+        Message::BrowserFormSubmit => {
+            // Handle form submission (e.g., log values, validate, etc.)
+            println!("Form submitted!");
+            println!("Text: {}", app.browser_text);
+            println!("Slider: {}", app.browser_slider_value);
+            println!("Checkbox: {}", app.browser_checkbox);
+        }
+        message => return Update::Ignored(message),
+    }
+    Update::Handled(Task::none())
+}
@@ -1,23 +1,268 @@
-use iced::widget::{column, container, text, Column};
+use iced::widget::{button, checkbox, column, container, pick_list, row, text, text_input, Column};
+use iced::{Center, Element, Task};
+use std::path::PathBuf;
 
 use crate::app::App;
+use crate::dataset::{self, ColumnMapping, Dataset, NO_COLUMN};
 use crate::message::Message;
+use crate::router::Route;
+use crate::views::{form, Update, View, ViewId};
+use crate::widgets::export::{self, ExportOptions};
+use crate::widgets::{chart, BarLayout, ChartKind, ExportFormat, ExportSize};
+
+pub const VIEW: View = View {
+    id: ViewId("chart"),
+    title: "Chart",
+    icon: "📊",
+    order: 1,
+    sections: &[],
+    view: create_chart_view,
+    update,
+};
 
 pub fn create_chart_view(app: &App) -> Column<'_, Message> {
+    let mut controls = row![
+        text("Data Visualization").size(24),
+        pick_list(&ChartKind::ALL[..], Some(app.chart_kind), Message::ChartKindSelected),
+    ]
+    .spacing(20)
+    .align_y(Center);
+
+    // Grouping and stacking only apply to bars
+    if app.chart_kind == ChartKind::Bar {
+        controls = controls.push(pick_list(
+            &BarLayout::ALL[..],
+            Some(app.chart.layout()),
+            Message::ChartLayoutSelected,
+        ));
+    }
+
+    let chart: Element<'_, Message> = match app.chart_kind {
+        // Click a legend entry to show or hide its series
+        ChartKind::Bar => app
+            .chart
+            .view(|bar| Message::ChartBarPressed(bar.label), Message::ChartSeriesToggled),
+        ChartKind::Line | ChartKind::Area => chart::view(&app.line_chart),
+        ChartKind::Pie | ChartKind::Donut => chart::view(&app.pie_chart),
+        ChartKind::Scatter => chart::view(&app.scatter_chart),
+    };
+
+    // Export writes the chart shown above to the working directory
+    let mut export = row![
+        text("Export").size(16),
+        pick_list(&ExportSize::ALL[..], Some(app.chart_export_size), Message::ChartExportSizeSelected),
+        checkbox("Transparent background", app.chart_export_transparent)
+            .on_toggle(Message::ChartExportTransparentToggled),
+    ]
+    .spacing(20)
+    .align_y(Center);
+    for format in ExportFormat::ALL {
+        export = export.push(
+            button(text(format!("Export {format}")))
+                .on_press(Message::ChartExported(format))
+                .style(app.skin.secondary_button()),
+        );
+    }
+
+    let mut content = column![controls, chart, export, create_import_section(app)].spacing(20);
+    if let Some(status) = &app.chart_status {
+        content = content.push(text(status).size(14));
+    }
+
     column![
         text("Chart View").size(32),
         container(
-            column![
-                text("Data Visualization").size(24),
-                app.chart.view(),
-                text(format!("Counter Value: {}", app.value)).size(16),
-                text(format!("Age: {}", app.age)).size(16),
-                text(format!("Experience: {:.1} years", app.experience)).size(16),
-            ]
-            .spacing(20)
+            content
+                .push(text(format!("Counter Value: {}", app.value)).size(16))
+                .push(text(format!("Age: {}", app.age)).size(16))
+                .push(text(format!("Experience: {:.1} years", app.experience)).size(16))
         )
-        .padding(20),
+        .padding(20)
+        .style(app.skin.container()),
     ]
     .spacing(20)
     .padding(20)
 }
+
+/// Data file picker, column mapping and the rows that could not be charted
+fn create_import_section(app: &App) -> Column<'_, Message> {
+    let dataset = app.chart_dataset.as_ref();
+    let mut section = column![row![
+        text("Data").size(16),
+        text_input("Path to a CSV or JSON file", &app.chart_import_path)
+            .on_input(Message::ChartImportPathChanged)
+            .on_submit(Message::ChartImportRequested)
+            .style(app.skin.input()),
+        button("Load").on_press(Message::ChartImportRequested).style(app.skin.button()),
+        button("Reload")
+            .on_press_maybe(dataset.map(|_| Message::ChartImportReloaded))
+            .style(app.skin.secondary_button()),
+        button("Use form data")
+            .on_press_maybe(dataset.map(|_| Message::ChartImportCleared))
+            .style(app.skin.secondary_button()),
+    ]
+    .spacing(20)
+    .align_y(Center)]
+    .spacing(10);
+
+    let Some(dataset) = dataset else {
+        return section;
+    };
+
+    let columns = &dataset.table.columns[..];
+    let color_columns: Vec<String> = std::iter::once(NO_COLUMN.to_string())
+        .chain(columns.iter().cloned())
+        .collect();
+    section = section.push(
+        row![
+            text("Label").size(14),
+            pick_list(columns, Some(dataset.mapping().label.clone()), Message::ChartImportLabelSelected),
+            text("Value").size(14),
+            pick_list(columns, Some(dataset.mapping().value.clone()), Message::ChartImportValueSelected),
+            text("Color").size(14),
+            pick_list(
+                color_columns,
+                Some(dataset.mapping().color.clone().unwrap_or_else(|| NO_COLUMN.to_string())),
+                Message::ChartImportColorSelected,
+            ),
+        ]
+        .spacing(10)
+        .align_y(Center),
+    );
+
+    // List the first few problems, then just count the rest
+    const SHOWN_ERRORS: usize = 5;
+    let errors = &dataset.bars().errors;
+    if !errors.is_empty() {
+        section = section.push(text("Problems in the data file:").size(14));
+    }
+    for error in errors.iter().take(SHOWN_ERRORS) {
+        section = section.push(text(error.to_string()).size(14));
+    }
+    if errors.len() > SHOWN_ERRORS {
+        section = section.push(
+            text(format!("…and {} more", errors.len() - SHOWN_ERRORS)).size(14),
+        );
+    }
+
+    section
+}
+
+fn update(app: &mut App, message: Message) -> Update {
+    match message {
+        Message::ChartSeriesToggled(series) => {
+            app.chart.toggle_series(series, app.now);
+        }
+        Message::ChartLayoutSelected(layout) => {
+            app.chart.set_layout(layout, app.now);
+        }
+        Message::ChartKindSelected(kind) => {
+            app.chart_kind = kind;
+            app.line_chart.set_filled(kind == ChartKind::Area);
+            app.pie_chart.set_hole(if kind == ChartKind::Donut { 0.55 } else { 0.0 });
+        }
+        Message::ChartExportSizeSelected(size) => {
+            app.chart_export_size = size;
+        }
+        Message::ChartExportTransparentToggled(transparent) => {
+            app.chart_export_transparent = transparent;
+        }
+        Message::ChartExported(format) => {
+            let theme = app.theme();
+            let (width, height) = app.chart_export_size.dimensions();
+            let options = ExportOptions {
+                width,
+                height,
+                background: (!app.chart_export_transparent)
+                    .then(|| theme.extended_palette().background.base.color),
+            };
+            let path = app.storage.unused_work_file("chart", format.extension());
+            app.chart_status = Some("Exporting…".to_string());
+            return Update::Handled(Task::perform(
+                export::export(app.current_chart_snapshot(), theme, options, format, path),
+                |result| Message::ChartExportFinished(result.map_err(|error| error.to_string())),
+            ));
+        }
+        Message::ChartExportFinished(result) => {
+            app.chart_status = Some(match result {
+                Ok(path) => format!("Exported to {}", path.display()),
+                Err(error) => format!("Export failed: {error}"),
+            });
+        }
+        Message::ChartImportPathChanged(path) => {
+            app.chart_import_path = path;
+        }
+        Message::ChartImportRequested => {
+            let path = PathBuf::from(app.chart_import_path.trim());
+            return Update::Handled(load_chart_data(app, path));
+        }
+        Message::ChartImportReloaded => {
+            if let Some(dataset) = &app.chart_dataset {
+                let path = dataset.path.clone();
+                return Update::Handled(load_chart_data(app, path));
+            }
+        }
+        Message::ChartDataLoaded(path, result) => {
+            app.chart_status = Some(match result {
+                Ok(table) => {
+                    let status = format!("Loaded {} rows from {}", table.rows.len(), path.display());
+                    match &mut app.chart_dataset {
+                        // Reloading keeps the chosen columns
+                        Some(dataset) if dataset.path == path => dataset.reload(table),
+                        _ => app.chart_dataset = Some(Dataset::new(path, table)),
+                    }
+                    status
+                }
+                Err(error) => format!("Import failed: {error}"),
+            });
+        }
+        Message::ChartImportLabelSelected(column) => {
+            if let Some(dataset) = &mut app.chart_dataset {
+                dataset.set_mapping(ColumnMapping {
+                    label: column,
+                    ..dataset.mapping().clone()
+                });
+            }
+        }
+        Message::ChartImportValueSelected(column) => {
+            if let Some(dataset) = &mut app.chart_dataset {
+                dataset.set_mapping(ColumnMapping {
+                    value: column,
+                    ..dataset.mapping().clone()
+                });
+            }
+        }
+        Message::ChartImportColorSelected(column) => {
+            if let Some(dataset) = &mut app.chart_dataset {
+                dataset.set_mapping(ColumnMapping {
+                    color: (column != NO_COLUMN).then_some(column),
+                    ..dataset.mapping().clone()
+                });
+            }
+        }
+        Message::ChartImportCleared => {
+            app.chart_dataset = None;
+            app.chart_status = None;
+        }
+        Message::ChartBarPressed(label) => {
+            // Imported bars have no form field behind them
+            if app.chart_dataset.is_none() {
+                // Jump to the form field behind the bar: the counter sits at
+                // the top, age and experience in the settings section below
+                let section = if label == 0 { form::COUNTER } else { form::SETTINGS };
+                let route = Route::section(form::VIEW.id, section);
+                return Update::Handled(Task::done(Message::Navigate(route)));
+            }
+        }
+        message => return Update::Ignored(message),
+    }
+    Update::Handled(Task::none())
+}
+
+/// Read a data file for the chart in the background
+fn load_chart_data(app: &mut App, path: PathBuf) -> Task<Message> {
+    app.chart_status = Some(format!("Loading {}…", path.display()));
+    Task::perform(dataset::load(path.clone()), move |result| {
+        Message::ChartDataLoaded(path, result)
+    })
+}
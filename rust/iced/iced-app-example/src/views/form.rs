@@ -1,10 +1,28 @@
 use iced::widget::{button, column, container, pick_list, row, slider, text, text_input, Column};
-use iced::Center;
+use iced::{Center, Task};
 
 use crate::app::App;
 use crate::components::animated_buttons;
 use crate::message::{Message, Role};
-use crate::styles::theme::ThemeType;
+use crate::styles::theme::ThemeChoice;
+use crate::utils::animation::Direction;
+use crate::views::{Section, Update, View, ViewId};
+
+pub const COUNTER: Section = Section { name: "counter" };
+pub const PERSONAL: Section = Section { name: "personal" };
+pub const SETTINGS: Section = Section { name: "settings" };
+pub const PREFERENCES: Section = Section { name: "preferences" };
+pub const CHART: Section = Section { name: "chart" };
+
+pub const VIEW: View = View {
+    id: ViewId("form"),
+    title: "Form",
+    icon: "📝",
+    order: 0,
+    sections: &[COUNTER, PERSONAL, SETTINGS, PREFERENCES, CHART],
+    view: create_form_view,
+    update,
+};
 
 pub fn create_form_view(app: &App) -> Column<'_, Message> {
     column![
@@ -14,16 +32,18 @@ pub fn create_form_view(app: &App) -> Column<'_, Message> {
             column![
                 text("Counter").size(20),
                 row![
-                    button("Decrement").on_press(Message::Decrement),
+                    button("Decrement").on_press(Message::Decrement).style(app.skin.button()),
                     text(app.value).size(30),
-                    button("Increment").on_press(Message::Increment),
+                    button("Increment").on_press(Message::Increment).style(app.skin.button()),
                 ]
                 .spacing(10)
                 .align_y(Center)
             ]
             .spacing(10)
         )
-        .padding(10),
+        .id(COUNTER.id())
+        .padding(10)
+        .style(app.skin.fieldset()),
         // Text inputs section
         container(
             column![
@@ -31,15 +51,19 @@ pub fn create_form_view(app: &App) -> Column<'_, Message> {
                 text("Name:").size(14),
                 text_input("Enter your name...", &app.name)
                     .on_input(Message::NameChanged)
-                    .padding(10),
+                    .padding(10)
+                    .style(app.skin.input()),
                 text("Email:").size(14),
                 text_input("Enter your email...", &app.email)
                     .on_input(Message::EmailChanged)
-                    .padding(10),
+                    .padding(10)
+                    .style(app.skin.input()),
             ]
             .spacing(5)
         )
-        .padding(10),
+        .id(PERSONAL.id())
+        .padding(10)
+        .style(app.skin.fieldset()),
         // Sliders section
         container(
             column![
@@ -51,7 +75,9 @@ pub fn create_form_view(app: &App) -> Column<'_, Message> {
             ]
             .spacing(10)
         )
-        .padding(10),
+        .id(SETTINGS.id())
+        .padding(10)
+        .style(app.skin.fieldset()),
         // Dropdowns section
         container(
             column![
@@ -62,30 +88,78 @@ pub fn create_form_view(app: &App) -> Column<'_, Message> {
                     .width(250),
                 text("Theme:").size(14),
                 pick_list(
-                    &ThemeType::ALL[..],
-                    ThemeType::ALL
-                        .iter()
-                        .find(|t| t.to_theme() == app.selected_theme)
-                        .copied(),
-                    Message::ThemeSelected
+                    ThemeChoice::all(&app.custom_themes),
+                    ThemeChoice::from_theme(&app.selected_theme, &app.custom_themes),
+                    |choice| match choice {
+                        ThemeChoice::BuiltIn(theme) => Message::ThemeSelected(theme),
+                        ThemeChoice::Custom(name) => Message::CustomThemeSelected(name),
+                    }
                 )
                 .placeholder("Choose a theme...")
                 .width(250),
             ]
             .spacing(10)
         )
-        .padding(10),
+        .id(PREFERENCES.id())
+        .padding(10)
+        .style(app.skin.fieldset()),
         // Animated buttons section
         container(animated_buttons::create_animated_buttons(
             app.second_button_visible,
-            app.button_animation_start,
-            app.button_animation_direction,
+            &app.second_button_animation,
+            app.now,
+            app.skin,
         ))
-        .padding(10),
+        .padding(10)
+        .style(app.skin.fieldset()),
         // Bar Chart section
-        container(column![text("Data Visualization").size(20), app.chart.view(),].spacing(10))
-            .padding(10),
+        container(column![text("Data Visualization").size(20), app.chart.view(|bar| Message::ChartBarPressed(bar.label), Message::ChartSeriesToggled),].spacing(10))
+            .id(CHART.id())
+            .padding(10)
+            .style(app.skin.fieldset()),
     ]
     .spacing(20)
     .padding(20)
 }
+
+fn update(app: &mut App, message: Message) -> Update {
+    match message {
+        Message::Increment => {
+            app.value += 1;
+        }
+        Message::Decrement => {
+            app.value -= 1;
+        }
+        Message::NameChanged(name) => {
+            app.name = name;
+        }
+        Message::EmailChanged(email) => {
+            app.email = email;
+        }
+        Message::AgeChanged(age) => {
+            app.age = age;
+        }
+        Message::ExperienceChanged(experience) => {
+            app.experience = experience;
+        }
+        Message::RoleSelected(role) => {
+            app.selected_role = Some(role);
+        }
+        Message::ShowSecondButton => {
+            app.second_button_visible = true;
+            app.second_button_animation.play(Direction::Forward, app.now, None);
+        }
+        Message::HideSecondButton => {
+            app.second_button_animation.play(
+                Direction::Reverse,
+                app.now,
+                Some(Message::SecondButtonHidden),
+            );
+        }
+        Message::SecondButtonHidden => {
+            app.second_button_visible = false;
+        }
+        message => return Update::Ignored(message),
+    }
+    Update::Handled(Task::none())
+}
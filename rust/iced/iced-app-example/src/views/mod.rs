@@ -1,4 +1,181 @@
+use iced::widget::{self, Column};
+use iced::Task;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::LazyLock;
+
+use crate::app::App;
+use crate::message::Message;
+
 pub mod form;
 pub mod chart;
 pub mod summary;
 pub mod browser_form;
+pub mod theme_editor;
+
+/// Every page of the app. The navigation menu, command palette, router and
+/// view shortcuts are built from this list.
+///
+/// Adding a view takes a module above and an entry here; the state and
+/// messages its widgets use still go on `App` and `Message`.
+static VIEWS: LazyLock<Vec<View>> = LazyLock::new(|| {
+    let mut views = vec![
+        form::VIEW,
+        chart::VIEW,
+        summary::VIEW,
+        browser_form::VIEW,
+        theme_editor::VIEW,
+    ];
+    views.sort_by_key(|view| view.order);
+    views
+});
+
+/// Identifies a registered view; also its route path, like `/chart`
+///
+/// Only the view modules can build one, for their `VIEW`, so every id names
+/// a registered view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ViewId(&'static str);
+
+impl std::fmt::Display for ViewId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for ViewId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ViewId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        // Older files stored enum variant names, like `BrowserForm`
+        all()
+            .find(|view| {
+                view.id.0.eq_ignore_ascii_case(&id)
+                    || view.id.0.replace('-', "").eq_ignore_ascii_case(&id)
+            })
+            .map(|view| view.id)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown view `{id}`")))
+    }
+}
+
+/// Named place within a view that a route can scroll to, like `#settings`
+///
+/// The view marks it by giving the widget there the section's [`id`](Section::id).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Section {
+    pub name: &'static str,
+}
+
+impl Section {
+    pub fn id(&self) -> widget::Id {
+        widget::Id::from(self.name)
+    }
+}
+
+/// Outcome of offering a message to a view's update function
+pub enum Update {
+    Handled(Task<Message>),
+    /// Not one of the view's messages, handed back to try elsewhere
+    Ignored(Message),
+}
+
+/// A page reachable from the navigation menu, command palette and router
+pub struct View {
+    pub id: ViewId,
+    pub title: &'static str,
+    pub icon: &'static str,
+    /// Position in the navigation menu
+    pub order: u8,
+    pub sections: &'static [Section],
+    pub view: fn(&App) -> Column<'_, Message>,
+    /// Handles the messages sent from the view's own widgets
+    pub update: fn(&mut App, Message) -> Update,
+}
+
+impl View {
+    pub fn section(&self, name: &str) -> Option<&'static Section> {
+        self.sections
+            .iter()
+            .find(|section| section.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Registered views, in menu order
+pub fn all() -> impl Iterator<Item = &'static View> {
+    VIEWS.iter()
+}
+
+/// The view shown at startup
+pub fn first() -> ViewId {
+    VIEWS[0].id
+}
+
+pub fn find(id: &str) -> Option<&'static View> {
+    all().find(|view| view.id.0.eq_ignore_ascii_case(id))
+}
+
+/// Registered view with `id`; ids only come from the registry
+pub fn get(id: ViewId) -> &'static View {
+    find(id.0).expect("view ids come from the registry")
+}
+
+/// Offer `message` to each view until one handles it
+pub fn update(app: &mut App, mut message: Message) -> Task<Message> {
+    for view in all() {
+        match (view.update)(app, message) {
+            Update::Handled(task) => return task,
+            Update::Ignored(ignored) => message = ignored,
+        }
+    }
+    Task::none()
+}
+
+/// Update function of views without messages of their own
+pub fn ignore(_app: &mut App, message: Message) -> Update {
+    Update::Ignored(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_and_orders_are_unique() {
+        let views: Vec<&View> = all().collect();
+        for (index, view) in views.iter().enumerate() {
+            for other in &views[index + 1..] {
+                assert_ne!(view.id, other.id);
+                assert_ne!(view.order, other.order);
+            }
+        }
+        assert!(views.windows(2).all(|pair| pair[0].order < pair[1].order));
+    }
+
+    #[test]
+    fn view_ids_round_trip_and_accept_old_names() {
+        let json = serde_json::to_string(&browser_form::VIEW.id).unwrap();
+        assert_eq!(json, r#""browser-form""#);
+        assert_eq!(serde_json::from_str::<ViewId>(&json).unwrap(), browser_form::VIEW.id);
+
+        assert_eq!(serde_json::from_str::<ViewId>(r#""BrowserForm""#).unwrap(), browser_form::VIEW.id);
+        assert_eq!(serde_json::from_str::<ViewId>(r#""Chart""#).unwrap(), chart::VIEW.id);
+        assert!(serde_json::from_str::<ViewId>(r#""Graphs""#).is_err());
+    }
+
+    #[test]
+    fn messages_reach_the_view_that_owns_them() {
+        let mut app = App::default();
+
+        let _ = update(&mut app, Message::BrowserTextChanged("hello".to_string()));
+        let _ = update(&mut app, Message::ChartImportPathChanged("data.json".to_string()));
+        let _ = update(&mut app, Message::Increment);
+
+        assert_eq!(app.browser_text, "hello");
+        assert_eq!(app.chart_import_path, "data.json");
+        assert_eq!(app.value, 1);
+    }
+}
@@ -3,7 +3,18 @@ use iced::Center;
 
 use crate::app::App;
 use crate::message::Message;
-use crate::styles::theme::ThemeType;
+use crate::styles::theme::ThemeChoice;
+use crate::views::{self, View, ViewId};
+
+pub const VIEW: View = View {
+    id: ViewId("summary"),
+    title: "Summary",
+    icon: "📋",
+    order: 2,
+    sections: &[],
+    view: create_summary_view,
+    update: views::ignore,
+};
 
 pub fn create_summary_view(app: &App) -> Column<'_, Message> {
     column![
@@ -37,17 +48,29 @@ pub fn create_summary_view(app: &App) -> Column<'_, Message> {
                 )),
                 text(format!(
                     "Theme: {}",
-                    ThemeType::ALL
-                        .iter()
-                        .find(|t| t.to_theme() == app.selected_theme)
+                    ThemeChoice::from_theme(&app.selected_theme, &app.custom_themes)
                         .map(|t| t.to_string())
                         .unwrap_or_else(|| "Unknown".to_string())
                 )),
+                text(format!("Skin: {}", app.skin)),
+                text(format!(
+                    "Saved themes: {}",
+                    if app.custom_themes.is_empty() {
+                        "None".to_string()
+                    } else {
+                        app.custom_themes
+                            .iter()
+                            .map(|theme| theme.name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    }
+                )),
                 text(format!("Counter: {}", app.value)),
             ]
             .spacing(10)
         )
-        .padding(20),
+        .padding(20)
+        .style(app.skin.container()),
         container(
             column![
                 text("Counter").size(24),
@@ -55,7 +78,8 @@ pub fn create_summary_view(app: &App) -> Column<'_, Message> {
             ]
             .spacing(10)
         )
-        .padding(20),
+        .padding(20)
+        .style(app.skin.container()),
     ]
     .spacing(20)
     .padding(20)
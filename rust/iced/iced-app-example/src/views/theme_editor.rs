@@ -0,0 +1,299 @@
+use iced::widget::{
+    button, column, container, pick_list, progress_bar, row, slider, text, text_input, themer, Column,
+};
+use iced::{Background, Border, Center, Color, Task, Theme};
+use std::path::PathBuf;
+
+use crate::app::App;
+use crate::message::Message;
+use crate::styles::skin::Skin;
+use crate::styles::theme::{CustomTheme, PaletteColor, ThemeChoice};
+use crate::theme_editor;
+use crate::theme_files::{self, ThemeFileFormat};
+use crate::views::{Update, View, ViewId};
+
+pub const VIEW: View = View {
+    id: ViewId("theme-editor"),
+    title: "Theme Editor",
+    icon: "🎨",
+    order: 4,
+    sections: &[],
+    view: create_theme_editor_view,
+    update,
+};
+
+pub fn create_theme_editor_view(app: &App) -> Column<'_, Message> {
+    let editor = &app.theme_editor;
+    let skin = app.skin;
+    let is_saved = app
+        .custom_themes
+        .iter()
+        .any(|theme| theme.name == editor.name.trim());
+
+    let header = row![
+        text_input("Theme name", &editor.name)
+            .on_input(Message::ThemeEditorNameChanged)
+            .on_submit(Message::ThemeEditorSaved)
+            .width(220)
+            .style(skin.input()),
+        pick_list(
+            ThemeChoice::all(&app.custom_themes),
+            None::<ThemeChoice>,
+            Message::ThemeEditorStartedFrom
+        )
+        .placeholder("Start from…"),
+        button("Save").on_press(Message::ThemeEditorSaved).style(skin.success_button()),
+        button("Delete")
+            .on_press_maybe(is_saved.then_some(Message::ThemeEditorDeleted))
+            .style(skin.danger_button()),
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let mut colors = column![].spacing(8);
+    for color in PaletteColor::ALL {
+        let is_selected = color == editor.selected;
+        colors = colors.push(
+            row![
+                button(swatch(editor.color(color), is_selected))
+                    .on_press(Message::ThemeEditorColorSelected(color))
+                    .padding(0)
+                    .style(button::text),
+                text(color.to_string()).width(100),
+                text_input("#rrggbb", editor.hex(color))
+                    .on_input(move |hex| Message::ThemeEditorHexChanged(color, hex))
+                    .width(110)
+                    .style(skin.input()),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        );
+    }
+
+    let channels = editor.channels();
+    colors = colors.push(text(format!("Editing: {}", editor.selected)).size(16));
+    for (channel, name) in ["Red", "Green", "Blue"].into_iter().enumerate() {
+        colors = colors.push(
+            row![
+                text(name).width(50),
+                slider(0..=255, channels[channel], move |value| {
+                    Message::ThemeEditorChannelChanged(channel, value)
+                }),
+                text(channels[channel]).width(30),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        );
+    }
+
+    let mut exports = row![text("Export as").size(14)].spacing(10).align_y(Center);
+    for format in ThemeFileFormat::ALL {
+        exports = exports.push(
+            button(text(format.to_string()))
+                .on_press(Message::ThemeExported(format))
+                .style(skin.secondary_button()),
+        );
+    }
+    let files = row![
+        text_input("base16/base24 YAML or JSON file, or a folder of them", &editor.import_path)
+            .on_input(Message::ThemeImportPathChanged)
+            .on_submit(Message::ThemeImportRequested)
+            .style(skin.input()),
+        button("Import").on_press(Message::ThemeImportRequested).style(skin.button()),
+        exports,
+    ]
+    .spacing(10)
+    .align_y(Center);
+
+    let mut content = column![text("Theme Editor").size(32), header, files].spacing(20);
+    if let Some(status) = &editor.status {
+        content = content.push(text(status).size(14));
+    }
+
+    content
+        .push(
+            row![
+                container(colors).width(320),
+                create_preview(editor.preview(), skin),
+            ]
+            .spacing(30),
+        )
+        .padding(20)
+}
+
+/// Sample widgets drawn with the palette being edited, in the current skin;
+/// the buttons pick their color for editing
+fn create_preview<'a>(theme: Theme, skin: Skin) -> iced::Element<'a, Message> {
+    let sample = column![
+        text("Preview").size(20),
+        text("Body text in the text color."),
+        row![
+            button("Primary")
+                .style(skin.button())
+                .on_press(Message::ThemeEditorColorSelected(PaletteColor::Primary)),
+            button("Success")
+                .style(skin.success_button())
+                .on_press(Message::ThemeEditorColorSelected(PaletteColor::Success)),
+        ]
+        .spacing(10),
+        row![
+            button("Warning")
+                .style(skin.warning_button())
+                .on_press(Message::ThemeEditorColorSelected(PaletteColor::Warning)),
+            button("Danger")
+                .style(skin.danger_button())
+                .on_press(Message::ThemeEditorColorSelected(PaletteColor::Danger)),
+        ]
+        .spacing(10),
+        text_input("A text input", "").style(skin.input()),
+        progress_bar(0.0..=1.0, 0.6),
+    ]
+    .spacing(12)
+    .padding(20)
+    .width(300);
+
+    themer(Some(theme), sample)
+        .background(|theme: &Theme| Background::Color(theme.palette().background))
+        .text_color(|theme: &Theme| theme.palette().text)
+        .into()
+}
+
+fn swatch<'a>(color: Color, is_selected: bool) -> container::Container<'a, Message> {
+    container(text(""))
+        .width(28)
+        .height(28)
+        .style(move |theme: &Theme| container::Style {
+            background: Some(Background::Color(color)),
+            border: Border {
+                color: if is_selected {
+                    theme.extended_palette().primary.strong.color
+                } else {
+                    Color::from_rgba(0.5, 0.5, 0.5, 0.6)
+                },
+                width: if is_selected { 3.0 } else { 1.0 },
+                radius: 4.0.into(),
+            },
+            ..container::Style::default()
+        })
+}
+
+fn update(app: &mut App, message: Message) -> Update {
+    let editor = &mut app.theme_editor;
+    match message {
+        Message::ThemeEditorNameChanged(name) => {
+            editor.name = name;
+        }
+        Message::ThemeEditorStartedFrom(choice) => {
+            if let Some(theme) = choice.to_theme(&app.custom_themes) {
+                editor.start_from(&theme);
+                // Starting from a saved theme edits it in place
+                if let ThemeChoice::Custom(name) = choice {
+                    editor.name = name;
+                }
+            }
+        }
+        Message::ThemeEditorColorSelected(color) => {
+            editor.selected = color;
+        }
+        Message::ThemeEditorHexChanged(color, hex) => {
+            editor.set_hex(color, hex);
+        }
+        Message::ThemeEditorChannelChanged(channel, value) => {
+            editor.set_channel(channel, value);
+        }
+        Message::ThemeEditorSaved => match editor.to_custom() {
+            Ok(theme) => {
+                let status = format!("Saved \"{}\"", theme.name);
+                keep_theme(app, theme);
+                return Update::Handled(save_themes(app, status));
+            }
+            Err(reason) => editor.status = Some(reason),
+        },
+        Message::ThemeEditorDeleted => {
+            let name = editor.name.trim().to_string();
+            if let Some(index) = app.custom_themes.iter().position(|theme| theme.name == name) {
+                let deleted = app.custom_themes.remove(index);
+                // The deleted theme cannot stay in use: go back to the default one
+                if app.selected_theme == deleted.to_theme() {
+                    app.selected_theme = Theme::Light;
+                }
+            }
+            return Update::Handled(save_themes(app, format!("Deleted \"{name}\"")));
+        }
+        Message::ThemesSaved(status, result) => {
+            app.theme_editor.status = Some(match result {
+                Ok(_) => status,
+                Err(error) => format!("{status}, but saving failed: {error}"),
+            });
+        }
+        Message::ThemeImportPathChanged(path) => {
+            editor.import_path = path;
+        }
+        Message::ThemeImportRequested => {
+            let path = PathBuf::from(editor.import_path.trim());
+            editor.status = Some(format!("Importing {}…", path.display()));
+            return Update::Handled(Task::perform(theme_files::import(path), |(themes, errors)| {
+                let errors = errors.iter().map(ToString::to_string).collect();
+                Message::ThemesImported(themes, errors)
+            }));
+        }
+        Message::ThemesImported(themes, errors) => {
+            let mut status = match themes.len() {
+                0 => "Nothing imported".to_string(),
+                1 => format!("Imported \"{}\"", themes[0].name),
+                count => format!("Imported {count} themes"),
+            };
+            // Name the first few bad files, then just count the rest
+            const SHOWN_ERRORS: usize = 3;
+            for error in errors.iter().take(SHOWN_ERRORS) {
+                status = format!("{status}; {error}");
+            }
+            if errors.len() > SHOWN_ERRORS {
+                status = format!("{status}; …and {} more", errors.len() - SHOWN_ERRORS);
+            }
+
+            if themes.is_empty() {
+                app.theme_editor.status = Some(status);
+            } else {
+                for theme in themes {
+                    keep_theme(app, theme);
+                }
+                return Update::Handled(save_themes(app, status));
+            }
+        }
+        Message::ThemeExported(format) => {
+            editor.status = Some(match editor.to_custom() {
+                Ok(theme) => match theme_files::export(&app.storage, &theme, format) {
+                    Ok(path) => format!("Exported to {}", path.display()),
+                    Err(error) => format!("Export failed: {error}"),
+                },
+                Err(reason) => reason,
+            });
+        }
+        message => return Update::Ignored(message),
+    }
+    Update::Handled(Task::none())
+}
+
+/// Write the custom themes in the background, showing `status` once they are
+fn save_themes(app: &mut App, status: String) -> Task<Message> {
+    app.theme_editor.status = Some("Saving…".to_string());
+    Task::perform(
+        theme_editor::save(app.storage.clone(), app.custom_themes.clone()),
+        move |result| Message::ThemesSaved(status, result.map_err(|error| error.to_string())),
+    )
+}
+
+/// Add `theme`, replacing a saved one of the same name; replacing the
+/// theme in use shows the change right away
+fn keep_theme(app: &mut App, theme: CustomTheme) {
+    match app.custom_themes.iter().position(|saved| saved.name == theme.name) {
+        Some(index) => {
+            if app.selected_theme == app.custom_themes[index].to_theme() {
+                app.selected_theme = theme.to_theme();
+            }
+            app.custom_themes[index] = theme;
+        }
+        None => app.custom_themes.push(theme),
+    }
+}
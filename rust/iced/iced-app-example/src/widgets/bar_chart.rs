@@ -1,151 +1,1062 @@
-use iced::widget::canvas::{self, Cache, Canvas, Geometry, Path, Stroke, Text};
-use iced::{Color, Point, Rectangle, Renderer, Size, Theme};
+use iced::alignment;
+use iced::keyboard::{self, key};
+use iced::widget::canvas::{self, Action, Canvas, Event, Frame, Geometry, Path, Stroke, Text};
+use iced::widget::text;
+use iced::{mouse, Color, Element, Length, Point, Rectangle, Renderer, Size, Theme, Vector};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
 
+use super::chart::{self, Axis, Chart, ChartCache, Series};
+use super::painter::{Painter, Shape};
+use crate::utils::animation::{Tween, DEFAULT_DURATION};
+
+/// Share of each label's slot taken up by its bars
+const GROUP_WIDTH: f32 = 0.7;
+
+/// How the series of one label share its slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BarLayout {
+    /// Side by side
+    #[default]
+    Grouped,
+    /// On top of each other, negative values stacking downwards
+    Stacked,
+}
+
+impl BarLayout {
+    pub const ALL: [BarLayout; 2] = [BarLayout::Grouped, BarLayout::Stacked];
+}
+
+impl std::fmt::Display for BarLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                BarLayout::Grouped => "Grouped",
+                BarLayout::Stacked => "Stacked",
+            }
+        )
+    }
+}
+
+/// Position of a bar in the data passed to [`BarChart::update_data`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarIndex {
+    pub label: usize,
+    pub series: usize,
+}
+
+#[derive(Clone)]
 pub struct BarChart {
-    cache: Cache,
-    data: Vec<BarData>,
+    cache: ChartCache,
+    /// Data as last passed in
+    labels: Vec<String>,
+    series: Vec<Series>,
+    /// Labels in data order, followed by removed ones still fading out
+    groups: Vec<AnimatedGroup>,
+    /// One per series, toggled from the legend
+    visibility: Vec<Visibility>,
+    layout: BarLayout,
+    /// Bottom and top of the Y axis scale
+    scale_min: Tween<f32>,
+    scale_max: Tween<f32>,
+    /// Time of the last update or tick, which drawing uses
+    now: Instant,
+    y_axis_title: Option<String>,
+}
+
+/// The bars of one label, easing towards the values last passed in
+#[derive(Debug, Clone)]
+struct AnimatedGroup {
+    label: String,
+    /// One per series, in series order
+    values: Vec<Tween<f32>>,
+    opacity: Tween<f32>,
+    /// Gone from the data and fading out
+    leaving: bool,
+    /// Replaces the series colors for this label's bars
+    color: Option<Color>,
 }
 
 #[derive(Debug, Clone)]
-pub struct BarData {
-    pub label: String,
-    pub value: f32,
-    pub color: Color,
+struct Visibility {
+    hidden: bool,
+    /// 1.0 when shown, 0.0 when hidden
+    amount: Tween<f32>,
 }
 
-impl Clone for BarChart {
-    fn clone(&self) -> Self {
-        Self {
-            cache: Cache::default(),
-            data: self.data.clone(),
+/// A bar as laid out on screen
+#[derive(Debug, Clone, Copy)]
+struct BarRect {
+    /// Index into `BarChart::groups`
+    group: usize,
+    series: usize,
+    bounds: Rectangle,
+}
+
+impl BarChart {
+    pub fn new(labels: Vec<String>, series: Vec<Series>) -> Self {
+        let mut chart = Self {
+            cache: ChartCache::default(),
+            labels: Vec::new(),
+            series: Vec::new(),
+            groups: Vec::new(),
+            visibility: Vec::new(),
+            layout: BarLayout::default(),
+            scale_min: Tween::new(0.0, DEFAULT_DURATION),
+            scale_max: Tween::new(1.0, DEFAULT_DURATION),
+            now: Instant::now(),
+            y_axis_title: None,
+        };
+        chart.set_data_at_rest(labels, series);
+        chart
+    }
+
+    /// Title shown above the Y axis
+    pub fn y_axis_title(mut self, title: impl Into<String>) -> Self {
+        self.y_axis_title = Some(title.into());
+        self
+    }
+
+    pub fn layout(&self) -> BarLayout {
+        self.layout
+    }
+
+    pub fn set_layout(&mut self, layout: BarLayout, now: Instant) {
+        if self.layout != layout {
+            self.layout = layout;
+            self.now = now;
+            self.retarget_scale(now);
+            self.cache.clear();
+        }
+    }
+
+    pub fn is_series_hidden(&self, series: usize) -> bool {
+        self.visibility.get(series).is_some_and(|visibility| visibility.hidden)
+    }
+
+    /// Show or hide a series, as clicking its legend entry does
+    pub fn toggle_series(&mut self, series: usize, now: Instant) {
+        let Some(visibility) = self.visibility.get_mut(series) else {
+            return;
+        };
+
+        visibility.hidden = !visibility.hidden;
+        visibility
+            .amount
+            .retarget(if visibility.hidden { 0.0 } else { 1.0 }, now);
+
+        self.now = now;
+        self.retarget_scale(now);
+        self.cache.clear();
+    }
+
+    /// Color the bars of each label, in the order last passed to
+    /// [`Chart::update_data`]; `None` and missing entries keep the series
+    /// colors
+    pub fn set_bar_colors(&mut self, colors: &[Option<Color>]) {
+        let colors = colors.iter().copied().chain(std::iter::repeat(None));
+        for (group, color) in self.groups.iter_mut().take(self.labels.len()).zip(colors) {
+            if group.color != color {
+                group.color = color;
+                self.cache.clear();
+            }
         }
     }
+
+    /// Interactive chart. `on_press` is published when a bar is clicked or
+    /// activated from the keyboard, `on_legend` when a legend entry is
+    /// clicked - usually to call [`BarChart::toggle_series`].
+    ///
+    /// The cache is keyed on the canvas size, so resizing redraws the chart.
+    pub fn view<'a, Message: 'a>(
+        &'a self,
+        on_press: impl Fn(BarIndex) -> Message + 'a,
+        on_legend: impl Fn(usize) -> Message + 'a,
+    ) -> Element<'a, Message> {
+        Canvas::new(Interactive {
+            chart: self,
+            on_press: Box::new(on_press),
+            on_legend: Box::new(on_legend),
+        })
+        .width(Length::Fill)
+        .height(Chart::height(self))
+        .into()
+    }
+
+    /// Replace the data without animating
+    fn set_data_at_rest(&mut self, labels: Vec<String>, series: Vec<Series>) {
+        self.groups = labels
+            .iter()
+            .enumerate()
+            .map(|(label_index, label)| AnimatedGroup {
+                label: label.clone(),
+                values: series
+                    .iter()
+                    .map(|series| Tween::new(series.value(label_index), DEFAULT_DURATION))
+                    .collect(),
+                opacity: Tween::new(1.0, DEFAULT_DURATION),
+                leaving: false,
+                color: None,
+            })
+            .collect();
+        self.visibility = series
+            .iter()
+            .enumerate()
+            .map(|(index, _)| {
+                let hidden = self.is_series_hidden(index);
+                Visibility {
+                    hidden,
+                    amount: Tween::new(if hidden { 0.0 } else { 1.0 }, DEFAULT_DURATION),
+                }
+            })
+            .collect();
+
+        self.labels = labels;
+        self.series = series;
+
+        let (low, high) = self.scale();
+        self.scale_min = Tween::new(low, DEFAULT_DURATION);
+        self.scale_max = Tween::new(high, DEFAULT_DURATION);
+        self.cache.clear();
+    }
+
+    /// Lowest and highest point the visible bars reach
+    fn extent(&self) -> (f32, f32) {
+        let mut low = 0.0f32;
+        let mut high = 0.0f32;
+
+        for label in 0..self.labels.len() {
+            let values = self
+                .series
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !self.is_series_hidden(*index))
+                .map(|(_, series)| series.value(label));
+
+            match self.layout {
+                BarLayout::Grouped => {
+                    for value in values {
+                        low = low.min(value);
+                        high = high.max(value);
+                    }
+                }
+                BarLayout::Stacked => {
+                    let (negative, positive) = values.fold((0.0, 0.0), |(negative, positive), value| {
+                        if value < 0.0 {
+                            (negative + value, positive)
+                        } else {
+                            (negative, positive + value)
+                        }
+                    });
+                    low = low.min(negative);
+                    high = high.max(positive);
+                }
+            }
+        }
+
+        (low, high)
+    }
+
+    /// Bottom and top of a Y axis that fits every visible bar
+    fn scale(&self) -> (f32, f32) {
+        let axis = self.axis();
+        (axis.min(), axis.max())
+    }
+
+    fn retarget_scale(&mut self, now: Instant) {
+        let (low, high) = self.scale();
+        if *self.scale_min.target() != low {
+            self.scale_min.retarget(low, now);
+        }
+        if *self.scale_max.target() != high {
+            self.scale_max.retarget(high, now);
+        }
+    }
+
+    /// Y axis of the target scale
+    fn axis(&self) -> Axis {
+        let (low, high) = self.extent();
+        Axis::fit(low, high)
+    }
+
+    /// Bottom and top of the Y axis right now
+    fn current_scale(&self) -> (f32, f32) {
+        let bottom = self.scale_min.value(self.now);
+        let top = self.scale_max.value(self.now);
+        (bottom, top.max(bottom + f32::EPSILON))
+    }
+
+    /// Position in the caller's data of a bar, if its label is not fading out
+    fn bar_index(&self, bar: &BarRect) -> Option<BarIndex> {
+        // Leaving groups come after all others, so the group is the label
+        self.groups
+            .get(bar.group)
+            .filter(|group| !group.leaving)
+            .map(|_| BarIndex {
+                label: bar.group,
+                series: bar.series,
+            })
+    }
+
+    /// Every bar with a visible share, for a chart of the given size
+    fn bar_rects(&self, size: Size) -> Vec<BarRect> {
+        if self.groups.is_empty() || self.series.is_empty() {
+            return Vec::new();
+        }
+
+        let plot = chart::plot_area(size);
+        let (bottom, top) = self.current_scale();
+        let y_of = |value: f32| chart::y_of(plot, bottom, top, value);
+
+        let visibility: Vec<f32> = self
+            .visibility
+            .iter()
+            .map(|visibility| visibility.amount.value(self.now).clamp(0.0, 1.0))
+            .collect();
+        let total_visibility: f32 = visibility.iter().sum();
+
+        let slot = plot.width / self.groups.len() as f32;
+        let group_width = slot * GROUP_WIDTH;
+        let mut rects = Vec::new();
+
+        for (group_index, group) in self.groups.iter().enumerate() {
+            let left = plot.x + group_index as f32 * slot + (slot - group_width) / 2.0;
+            let mut offset = 0.0;
+            let mut positive = 0.0;
+            let mut negative = 0.0;
+
+            for (series, value) in group.values.iter().enumerate() {
+                let shown = visibility.get(series).copied().unwrap_or(0.0);
+                if shown <= 0.0 {
+                    continue;
+                }
+                let value = value.value(self.now);
+
+                // Hidden series shrink out sideways when grouped and
+                // downwards when stacked
+                let (x, width, base, value) = match self.layout {
+                    BarLayout::Grouped => {
+                        let width = group_width * shown / total_visibility;
+                        let x = left + offset;
+                        offset += width;
+                        (x, width, 0.0, value)
+                    }
+                    BarLayout::Stacked => {
+                        let value = value * shown;
+                        let base = if value < 0.0 { negative } else { positive };
+                        if value < 0.0 {
+                            negative += value;
+                        } else {
+                            positive += value;
+                        }
+                        (left, group_width, base, value)
+                    }
+                };
+
+                let (from, to) = (y_of(base), y_of(base + value));
+                rects.push(BarRect {
+                    group: group_index,
+                    series,
+                    bounds: Rectangle::new(
+                        Point::new(x, from.min(to)),
+                        Size::new(width, (from - to).abs()),
+                    ),
+                });
+            }
+        }
+
+        rects
+    }
+
+    /// Index into `rects` of the bar under `point`, relative to the chart's
+    /// top-left
+    ///
+    /// The segment under the cursor wins; otherwise the whole column above
+    /// or below a bar counts, so short bars stay easy to hit.
+    fn bar_at(&self, rects: &[BarRect], size: Size, point: Point) -> Option<usize> {
+        let plot = chart::plot_area(size);
+        if point.y < plot.y || point.y > plot.y + plot.height {
+            return None;
+        }
+
+        let in_column =
+            |bar: &BarRect| point.x >= bar.bounds.x && point.x <= bar.bounds.x + bar.bounds.width;
+
+        rects
+            .iter()
+            .position(|bar| bar.bounds.contains(point))
+            .or_else(|| rects.iter().rposition(in_column))
+            .filter(|index| self.bar_index(&rects[*index]).is_some())
+    }
+
+    /// Clickable legend entries, right-aligned in the top margin
+    fn legend(&self, size: Size) -> Vec<Rectangle> {
+        chart::legend(size, self.series.iter().map(|series| series.name.as_str()))
+    }
+
+    fn legend_at(&self, size: Size, point: Point) -> Option<usize> {
+        self.legend(size)
+            .iter()
+            .position(|entry| entry.contains(point))
+    }
 }
 
-impl BarChart {
-    pub fn new(data: Vec<BarData>) -> Self {
-        Self {
-            cache: Cache::default(),
-            data,
+impl Chart for BarChart {
+    /// Ease bars and scale towards the new data. Labels and series are
+    /// matched by name; new labels fade in and missing ones fade out.
+    fn update_data(&mut self, labels: Vec<String>, series: Vec<Series>, now: Instant) {
+        if labels == self.labels && series == self.series {
+            return;
         }
+
+        self.now = now;
+
+        // Map each new series onto the old one of the same name
+        let previous: Vec<Option<usize>> = series
+            .iter()
+            .map(|new| self.series.iter().position(|old| old.name == new.name))
+            .collect();
+        // Per-series state in the new series order; new series grow out of
+        // the zero line
+        let remap = |values: &[Tween<f32>]| -> Vec<Tween<f32>> {
+            previous
+                .iter()
+                .map(|index| {
+                    index
+                        .and_then(|index| values.get(index).cloned())
+                        .unwrap_or_else(|| Tween::new(0.0, DEFAULT_DURATION))
+                })
+                .collect()
+        };
+
+        self.visibility = previous
+            .iter()
+            .map(|index| {
+                index
+                    .and_then(|index| self.visibility.get(index).cloned())
+                    .unwrap_or_else(|| Visibility {
+                        hidden: false,
+                        amount: Tween::new(1.0, DEFAULT_DURATION),
+                    })
+            })
+            .collect();
+
+        let mut old_groups = std::mem::take(&mut self.groups);
+        for (label_index, label) in labels.iter().enumerate() {
+            let group = match old_groups.iter().position(|group| &group.label == label) {
+                Some(index) => {
+                    let mut group = old_groups.remove(index);
+                    group.values = remap(&group.values);
+                    for (tween, series) in group.values.iter_mut().zip(&series) {
+                        if *tween.target() != series.value(label_index) {
+                            tween.retarget(series.value(label_index), now);
+                        }
+                    }
+
+                    // Coming back while fading out
+                    if group.leaving {
+                        group.leaving = false;
+                        group.opacity.retarget(1.0, now);
+                    }
+                    group
+                }
+                None => {
+                    let mut opacity = Tween::new(0.0, DEFAULT_DURATION);
+                    opacity.retarget(1.0, now);
+                    AnimatedGroup {
+                        label: label.clone(),
+                        values: series
+                            .iter()
+                            .map(|series| Tween::new(series.value(label_index), DEFAULT_DURATION))
+                            .collect(),
+                        opacity,
+                        leaving: false,
+                        color: None,
+                    }
+                }
+            };
+            self.groups.push(group);
+        }
+
+        // Removed labels keep their last values while they fade out
+        for mut group in old_groups {
+            if !group.leaving {
+                group.leaving = true;
+                group.opacity.retarget(0.0, now);
+            }
+            group.values = remap(&group.values);
+            self.groups.push(group);
+        }
+
+        self.labels = labels;
+        self.series = series;
+        self.retarget_scale(now);
+        self.cache.clear();
+    }
+
+    fn is_animating(&self) -> bool {
+        self.scale_min.is_active()
+            || self.scale_max.is_active()
+            || self.visibility.iter().any(|visibility| visibility.amount.is_active())
+            || self.groups.iter().any(|group| {
+                group.opacity.is_active() || group.values.iter().any(Tween::is_active)
+            })
     }
 
-    pub fn update_data(&mut self, data: Vec<BarData>) {
-        self.data = data;
+    /// Advance running transitions to `now`; the cache is only cleared while
+    /// something is moving
+    fn tick(&mut self, now: Instant) {
+        if !self.is_animating() {
+            return;
+        }
+
+        self.now = now;
         self.cache.clear();
+
+        self.scale_min.tick(now);
+        self.scale_max.tick(now);
+        for visibility in &mut self.visibility {
+            visibility.amount.tick(now);
+        }
+        for group in &mut self.groups {
+            group.opacity.tick(now);
+            for value in &mut group.values {
+                value.tick(now);
+            }
+        }
+        self.groups.retain(|group| !group.leaving || group.opacity.is_active());
     }
 
-    pub fn view<Message>(&self) -> Canvas<BarChart, Message, Theme, Renderer> 
-    where 
-        Message: 'static,
-    {
-        Canvas::new(self.clone())
-            .width(600)
-            .height(300)
+    fn cache(&self) -> &ChartCache {
+        &self.cache
+    }
+
+    fn draw(&self, painter: &mut dyn Painter, theme: &Theme, size: Size) {
+        let plot = chart::plot_area(size);
+        let (bottom, top) = self.current_scale();
+        let y_of = |value: f32| chart::y_of(plot, bottom, top, value);
+        let rects = self.bar_rects(size);
+        let text_color = theme.extended_palette().background.base.text;
+
+        chart::draw_grid(painter, theme, plot, &self.axis(), (bottom, top));
+        if let Some(title) = &self.y_axis_title {
+            chart::draw_title(painter, theme, title);
+        }
+
+        // Draw bars
+        for bar in &rects {
+            let group = &self.groups[bar.group];
+            let opacity = group.opacity.value(self.now);
+            let fade = |color: Color| Color { a: color.a * opacity, ..color };
+
+            let shape = Shape::Rectangle(bar.bounds);
+            painter.fill(&shape, fade(group.color.unwrap_or(self.series[bar.series].color)));
+
+            // Draw bar outline
+            painter.stroke(&shape, 1.5, fade(Color { a: 0.5, ..text_color }));
+
+            let value = group.values[bar.series].value(self.now);
+            let value_text = Text {
+                content: format!("{value:.0}"),
+                color: fade(text_color),
+                size: 12.0.into(),
+                align_x: text::Alignment::Center,
+                ..Text::default()
+            };
+
+            match self.layout {
+                // Draw value above positive and below negative bars
+                BarLayout::Grouped => painter.fill_text(if value < 0.0 {
+                    Text {
+                        position: Point::new(bar.bounds.center_x(), bar.bounds.y + bar.bounds.height + 4.0),
+                        align_y: alignment::Vertical::Top,
+                        ..value_text
+                    }
+                } else {
+                    Text {
+                        position: Point::new(bar.bounds.center_x(), bar.bounds.y - 4.0),
+                        align_y: alignment::Vertical::Bottom,
+                        ..value_text
+                    }
+                }),
+                // Draw value inside segments tall enough to hold it
+                BarLayout::Stacked if bar.bounds.height >= 16.0 => painter.fill_text(Text {
+                    position: bar.bounds.center(),
+                    align_y: alignment::Vertical::Center,
+                    ..value_text
+                }),
+                BarLayout::Stacked => {}
+            }
+        }
+
+        // Draw label below each group
+        let slot = plot.width / self.groups.len().max(1) as f32;
+        for (index, group) in self.groups.iter().enumerate() {
+            let opacity = group.opacity.value(self.now);
+            painter.fill_text(Text {
+                content: group.label.clone(),
+                position: Point::new(
+                    plot.x + (index as f32 + 0.5) * slot,
+                    plot.y + plot.height + 10.0,
+                ),
+                color: Color { a: text_color.a * opacity, ..text_color },
+                size: 12.0.into(),
+                align_x: text::Alignment::Center,
+                ..Text::default()
+            });
+        }
+
+        // Draw axes; the X axis sits on zero so negative bars hang below it
+        chart::draw_axes(painter, theme, plot, y_of(0.0));
+
+        // Draw legend
+        for (index, (series, entry)) in self.series.iter().zip(self.legend(size)).enumerate() {
+            chart::draw_legend_entry(
+                painter,
+                theme,
+                entry,
+                &series.name,
+                series.color,
+                self.is_series_hidden(index),
+            );
+        }
     }
 }
 
-impl<Message> canvas::Program<Message, Theme, Renderer> for BarChart {
-    type State = ();
+/// Move keyboard focus one bar forwards or backwards, wrapping around
+fn cycle(current: Option<usize>, len: usize, forward: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+
+    Some(match (current, forward) {
+        (None, true) => 0,
+        (None, false) => len - 1,
+        (Some(index), true) => (index + 1) % len,
+        (Some(index), false) => (index + len - 1) % len,
+    })
+}
+
+/// Cursor and keyboard state of a chart on screen
+#[derive(Debug, Default)]
+struct ChartState {
+    /// Indices into the laid out bars
+    hovered: Option<usize>,
+    /// Bar selected with the keyboard or by the last click
+    selected: Option<usize>,
+    hovered_legend: Option<usize>,
+    is_focused: bool,
+}
+
+/// The canvas program behind [`BarChart::view`]
+struct Interactive<'a, Message> {
+    chart: &'a BarChart,
+    on_press: Box<dyn Fn(BarIndex) -> Message + 'a>,
+    on_legend: Box<dyn Fn(usize) -> Message + 'a>,
+}
+
+impl<Message> canvas::Program<Message, Theme, Renderer> for Interactive<'_, Message> {
+    type State = ChartState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<Action<Message>> {
+        let chart = self.chart;
+        let rects = chart.bar_rects(bounds.size());
+
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let point = cursor.position_in(bounds);
+                let hovered = point.and_then(|point| chart.bar_at(&rects, bounds.size(), point));
+                let hovered_legend = point.and_then(|point| chart.legend_at(bounds.size(), point));
+
+                if hovered != state.hovered || hovered_legend != state.hovered_legend {
+                    state.hovered = hovered;
+                    state.hovered_legend = hovered_legend;
+                    return Some(Action::request_redraw());
+                }
+            }
+            Event::Mouse(mouse::Event::CursorLeft) => {
+                state.hovered_legend = None;
+                if state.hovered.take().is_some() {
+                    return Some(Action::request_redraw());
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let Some(point) = cursor.position_in(bounds) else {
+                    // Clicking elsewhere takes keyboard focus away
+                    if state.is_focused {
+                        state.is_focused = false;
+                        return Some(Action::request_redraw());
+                    }
+                    return None;
+                };
+
+                if let Some(series) = chart.legend_at(bounds.size(), point) {
+                    return Some(Action::publish((self.on_legend)(series)).and_capture());
+                }
+
+                state.is_focused = true;
+                let pressed = chart.bar_at(&rects, bounds.size(), point).and_then(|index| {
+                    state.selected = Some(index);
+                    chart.bar_index(&rects[index])
+                });
+
+                return Some(match pressed {
+                    Some(bar) => Action::publish((self.on_press)(bar)).and_capture(),
+                    None => Action::request_redraw().and_capture(),
+                });
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+                if state.is_focused =>
+            {
+                let len = rects.len();
+                match key.as_ref() {
+                    keyboard::Key::Named(key::Named::Tab) => {
+                        state.selected = cycle(state.selected, len, !modifiers.shift());
+                    }
+                    keyboard::Key::Named(key::Named::ArrowRight) => {
+                        state.selected = cycle(state.selected, len, true);
+                    }
+                    keyboard::Key::Named(key::Named::ArrowLeft) => {
+                        state.selected = cycle(state.selected, len, false);
+                    }
+                    keyboard::Key::Named(key::Named::Enter | key::Named::Space) => {
+                        let bar = state
+                            .selected
+                            .and_then(|index| rects.get(index))
+                            .and_then(|bar| chart.bar_index(bar))?;
+                        return Some(Action::publish((self.on_press)(bar)).and_capture());
+                    }
+                    keyboard::Key::Named(key::Named::Escape) => {
+                        state.is_focused = false;
+                    }
+                    _ => return None,
+                }
+                return Some(Action::request_redraw().and_capture());
+            }
+            _ => {}
+        }
+
+        None
+    }
 
     fn draw(
         &self,
-        _state: &Self::State,
+        state: &Self::State,
         renderer: &Renderer,
-        _theme: &Theme,
+        theme: &Theme,
         bounds: Rectangle,
-        _cursor: iced::mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<Geometry<Renderer>> {
-        let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
-            let padding = 40.0;
-            let chart_width = bounds.width - padding * 2.0;
-            let chart_height = bounds.height - padding * 2.0;
+        let chart = self.chart;
+        let rects = chart.bar_rects(bounds.size());
+        let palette = theme.extended_palette();
+        let text_color = palette.background.base.text;
 
-            // Draw background
-            let background = Path::rectangle(Point::ORIGIN, bounds.size());
-            frame.fill(&background, Color::from_rgb(0.95, 0.95, 0.95));
+        let geometry = chart.cache.draw(renderer, theme, bounds.size(), |painter, theme| {
+            Chart::draw(chart, painter, theme, bounds.size());
+        });
 
-            if self.data.is_empty() {
-                return;
-            }
+        // Hover and focus feedback changes with every cursor move, so it is
+        // drawn on top of the cached bars instead of invalidating them
+        let mut overlay = Frame::new(renderer, bounds.size());
+        let accent = palette.primary.base.color;
 
-            // Find max value for scaling
-            let max_value = self
-                .data
-                .iter()
-                .map(|d| d.value)
-                .fold(0.0f32, f32::max)
-                .max(1.0);
-
-            let bar_width = chart_width / self.data.len() as f32 * 0.7;
-            let bar_spacing = chart_width / self.data.len() as f32;
-
-            // Draw bars
-            for (i, bar_data) in self.data.iter().enumerate() {
-                let bar_height = (bar_data.value / max_value) * chart_height;
-                let x = padding + i as f32 * bar_spacing + (bar_spacing - bar_width) / 2.0;
-                let y = padding + chart_height - bar_height;
-
-                // Draw bar
-                let bar = Path::rectangle(Point::new(x, y), Size::new(bar_width, bar_height));
-                frame.fill(&bar, bar_data.color);
-
-                // Draw bar outline
-                frame.stroke(
-                    &bar,
-                    Stroke::default()
-                        .with_width(2.0)
-                        .with_color(Color::from_rgb(0.3, 0.3, 0.3)),
-                );
+        if state.is_focused {
+            overlay.stroke(
+                &Path::rectangle(Point::new(1.0, 1.0), bounds.size() - Size::new(2.0, 2.0)),
+                Stroke::default().with_width(2.0).with_color(accent),
+            );
 
-                // Draw value on top of bar
-                let value_text = Text {
-                    content: format!("{:.0}", bar_data.value),
-                    position: Point::new(x + bar_width / 2.0, y - 15.0),
-                    color: Color::BLACK,
-                    size: 14.0.into(),
-                    ..Text::default()
-                };
-                frame.fill_text(value_text);
-
-                // Draw label below bar
-                let label_text = Text {
-                    content: bar_data.label.clone(),
-                    position: Point::new(
-                        x + bar_width / 2.0,
-                        padding + chart_height + 10.0,
+            if let Some(bar) = state.selected.and_then(|index| rects.get(index)) {
+                overlay.stroke(
+                    &Path::rectangle(
+                        bar.bounds.position() - Vector::new(3.0, 3.0),
+                        bar.bounds.size() + Size::new(6.0, 6.0),
                     ),
-                    color: Color::BLACK,
-                    size: 12.0.into(),
-                    ..Text::default()
-                };
-                frame.fill_text(label_text);
+                    Stroke::default().with_width(2.0).with_color(accent),
+                );
             }
+        }
 
-            // Draw axes
-            let x_axis = Path::line(
-                Point::new(padding, padding + chart_height),
-                Point::new(padding + chart_width, padding + chart_height),
-            );
-            frame.stroke(
-                &x_axis,
-                Stroke::default()
-                    .with_width(2.0)
-                    .with_color(Color::BLACK),
+        // Blend the hovered bar towards the background, light or dark
+        if let Some(bar) = state.hovered.and_then(|index| rects.get(index)) {
+            overlay.fill(
+                &Path::rectangle(bar.bounds.position(), bar.bounds.size()),
+                Color { a: 0.35, ..palette.background.base.color },
             );
+        }
 
-            let y_axis = Path::line(
-                Point::new(padding, padding),
-                Point::new(padding, padding + chart_height),
+        // Underline the hovered legend entry
+        if let Some(entry) = state
+            .hovered_legend
+            .and_then(|index| chart.legend(bounds.size()).get(index).copied())
+        {
+            overlay.stroke(
+                &Path::line(
+                    Point::new(entry.x, entry.y + entry.height),
+                    Point::new(entry.x + entry.width, entry.y + entry.height),
+                ),
+                Stroke::default().with_width(1.0).with_color(text_color),
             );
-            frame.stroke(
-                &y_axis,
-                Stroke::default()
-                    .with_width(2.0)
-                    .with_color(Color::BLACK),
+        }
+
+        // Tooltip follows the cursor, or sits above the keyboard selection
+        let tooltip = match (state.hovered, cursor.position_in(bounds)) {
+            (Some(index), Some(point)) => Some((index, point)),
+            _ if state.is_focused => state
+                .selected
+                .and_then(|index| Some((index, rects.get(index)?.bounds.position()))),
+            _ => None,
+        };
+
+        if let Some((bar, anchor)) = tooltip.and_then(|(index, anchor)| {
+            Some((chart.bar_index(rects.get(index)?)?, anchor))
+        }) {
+            let series = &chart.series[bar.series];
+            let content = format!(
+                "{} · {}: {}",
+                chart.labels[bar.label],
+                series.name,
+                series.values.get(bar.label).copied().unwrap_or(0.0)
             );
-        });
+            chart::draw_tooltip(&mut overlay, theme, bounds.size(), anchor, content);
+        }
+
+        vec![geometry, overlay.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        _bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if state.hovered.is_some() || state.hovered_legend.is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::chart::{MARGIN_BOTTOM, MARGIN_TOP};
+
+    const SIZE: Size = Size::new(600.0, 300.0);
+
+    fn labels(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("Bar {i}")).collect()
+    }
+
+    fn series(name: &str, values: &[f32]) -> Series {
+        Series {
+            name: name.to_string(),
+            color: Color::BLACK,
+            values: values.to_vec(),
+        }
+    }
+
+    fn chart(values: &[f32]) -> BarChart {
+        BarChart::new(labels(values.len()), vec![series("A", values)])
+    }
+
+    fn hit(chart: &BarChart, point: Point) -> Option<BarIndex> {
+        let rects = chart.bar_rects(SIZE);
+        let index = chart.bar_at(&rects, SIZE, point)?;
+        chart.bar_index(&rects[index])
+    }
+
+    fn bounds(chart: &BarChart, label: usize, series: usize) -> Rectangle {
+        chart
+            .bar_rects(SIZE)
+            .into_iter()
+            .find(|bar| bar.group == label && bar.series == series)
+            .unwrap()
+            .bounds
+    }
+
+    #[test]
+    fn hits_the_bar_under_the_cursor() {
+        let chart = BarChart::new(
+            labels(2),
+            vec![series("A", &[10.0, 20.0]), series("B", &[30.0, 5.0])],
+        );
+
+        for label in 0..2 {
+            for series in 0..2 {
+                let center = bounds(&chart, label, series).center();
+                assert_eq!(hit(&chart, center), Some(BarIndex { label, series }));
+            }
+        }
+    }
+
+    #[test]
+    fn short_bars_are_hit_across_their_column() {
+        let chart = chart(&[100.0, 1.0]);
+        let short = bounds(&chart, 1, 0);
+
+        // Well above the top of the short bar but inside the plot
+        let point = Point::new(short.center_x(), MARGIN_TOP + 5.0);
+        assert_eq!(hit(&chart, point), Some(BarIndex { label: 1, series: 0 }));
+    }
+
+    #[test]
+    fn gaps_and_margins_miss() {
+        let chart = chart(&[10.0, 20.0]);
+        let (first, second) = (bounds(&chart, 0, 0), bounds(&chart, 1, 0));
+
+        let gap = Point::new((first.x + first.width + second.x) / 2.0, SIZE.height / 2.0);
+        assert_eq!(hit(&chart, gap), None);
+
+        // Value labels above the plot and the axis labels below it
+        assert_eq!(hit(&chart, Point::new(first.center_x(), MARGIN_TOP / 2.0)), None);
+        assert_eq!(
+            hit(&chart, Point::new(first.center_x(), SIZE.height - MARGIN_BOTTOM / 2.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn empty_chart_has_nothing_to_hit() {
+        let chart = chart(&[]);
+        assert_eq!(hit(&chart, Point::new(300.0, 150.0)), None);
+    }
+
+    #[test]
+    fn stacked_segments_are_hit_separately() {
+        let mut chart = BarChart::new(
+            labels(1),
+            vec![series("A", &[10.0]), series("B", &[10.0])],
+        );
+        chart.set_layout(BarLayout::Stacked, Instant::now());
+        chart.tick(Instant::now() + DEFAULT_DURATION);
+
+        let (lower, upper) = (bounds(&chart, 0, 0), bounds(&chart, 0, 1));
+        assert!((lower.y - (upper.y + upper.height)).abs() < 1e-3);
+        assert_eq!(hit(&chart, lower.center()), Some(BarIndex { label: 0, series: 0 }));
+        assert_eq!(hit(&chart, upper.center()), Some(BarIndex { label: 0, series: 1 }));
+    }
+
+    #[test]
+    fn negative_bars_hang_below_zero() {
+        let chart = chart(&[20.0, -10.0]);
+        let (positive, negative) = (bounds(&chart, 0, 0), bounds(&chart, 1, 0));
+
+        // Both start on the zero line, one going up and one going down
+        assert!((positive.y + positive.height - negative.y).abs() < 1e-3);
+        assert!((positive.height - 2.0 * negative.height).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bars_scale_to_the_top_tick() {
+        let chart = chart(&[37.0]);
+        let plot = chart::plot_area(SIZE);
+
+        // 37 of a 0-40 scale
+        assert!((bounds(&chart, 0, 0).height - plot.height * 37.0 / 40.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bars_ease_to_new_values() {
+        let start = Instant::now();
+        let mut chart = chart(&[10.0, 10.0]);
+        let plot = chart::plot_area(SIZE);
+
+        chart.update_data(labels(2), vec![series("A", &[10.0, 5.0])], start);
+        assert!(chart.is_animating());
+
+        chart.tick(start + DEFAULT_DURATION / 2);
+        let halfway = bounds(&chart, 1, 0).height;
+        assert!(halfway < plot.height && halfway > plot.height / 2.0);
+
+        chart.tick(start + DEFAULT_DURATION);
+        assert!(!chart.is_animating());
+        assert!((bounds(&chart, 1, 0).height - plot.height / 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn removed_labels_fade_out_then_go() {
+        let start = Instant::now();
+        let mut chart = chart(&[10.0, 20.0]);
+
+        chart.update_data(labels(1), vec![series("A", &[10.0])], start);
+        assert_eq!(chart.groups.len(), 2);
+        // The leaving bar can no longer be clicked
+        assert_eq!(hit(&chart, bounds(&chart, 1, 0).center()), None);
+
+        chart.tick(start + DEFAULT_DURATION);
+        assert_eq!(chart.groups.len(), 1);
+    }
+
+    #[test]
+    fn bar_colors_follow_the_labels() {
+        let red = Color::from_rgb(1.0, 0.0, 0.0);
+        let mut chart = chart(&[10.0, 20.0, 30.0]);
+
+        chart.set_bar_colors(&[None, Some(red)]);
+        let colors: Vec<_> = chart.groups.iter().map(|group| group.color).collect();
+        assert_eq!(colors, [None, Some(red), None]);
+
+        // Fading out labels keep their color
+        chart.update_data(labels(1), vec![series("A", &[10.0])], Instant::now());
+        chart.set_bar_colors(&[]);
+        let colors: Vec<_> = chart.groups.iter().map(|group| group.color).collect();
+        assert_eq!(colors, [None, Some(red), None]);
+    }
+
+    #[test]
+    fn unchanged_data_does_not_animate() {
+        let mut chart = chart(&[10.0, 20.0]);
+        chart.update_data(labels(2), vec![series("A", &[10.0, 20.0])], Instant::now());
+        assert!(!chart.is_animating());
+    }
+
+    #[test]
+    fn hidden_series_leave_the_scale() {
+        let start = Instant::now();
+        let mut chart = BarChart::new(
+            labels(1),
+            vec![series("A", &[10.0]), series("B", &[100.0])],
+        );
+        assert_eq!(chart.scale(), (0.0, 100.0));
+
+        chart.toggle_series(1, start);
+        assert!(chart.is_series_hidden(1));
+        assert_eq!(chart.scale(), (0.0, 10.0));
+
+        // Once faded out, the hidden series has no bar left to hit
+        chart.tick(start + DEFAULT_DURATION);
+        assert!(chart.bar_rects(SIZE).iter().all(|bar| bar.series == 0));
+    }
+
+    #[test]
+    fn legend_clicks_find_their_series() {
+        let chart = BarChart::new(
+            labels(1),
+            vec![series("First", &[1.0]), series("Second", &[2.0])],
+        );
+        let legend = chart.legend(SIZE);
+
+        assert_eq!(chart.legend_at(SIZE, legend[1].center()), Some(1));
+        assert_eq!(chart.legend_at(SIZE, Point::new(0.0, 0.0)), None);
+    }
 
-        vec![geometry]
+    #[test]
+    fn keyboard_focus_wraps_around() {
+        assert_eq!(cycle(None, 3, true), Some(0));
+        assert_eq!(cycle(None, 3, false), Some(2));
+        assert_eq!(cycle(Some(2), 3, true), Some(0));
+        assert_eq!(cycle(Some(0), 3, false), Some(2));
+        assert_eq!(cycle(Some(0), 0, true), None);
     }
 }
@@ -0,0 +1,474 @@
+use iced::alignment;
+use iced::theme::Palette;
+use iced::widget::canvas::{self, Cache, Canvas, Geometry, Text};
+use iced::widget::text;
+use iced::{mouse, Color, Element, Length, Point, Rectangle, Renderer, Size, Theme, Vector};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::time::Instant;
+
+use super::painter::{Painter, Shape, Translucent};
+use crate::styles::theme::{faded, opacity};
+
+/// Room left of the plot area for the Y axis tick labels
+pub(crate) const MARGIN_LEFT: f32 = 56.0;
+/// Room right of the plot area
+pub(crate) const MARGIN_RIGHT: f32 = 20.0;
+/// Room above the plot area for the axis title, legend and values
+pub(crate) const MARGIN_TOP: f32 = 40.0;
+/// Room below the plot area for the labels
+pub(crate) const MARGIN_BOTTOM: f32 = 40.0;
+
+/// Number of axis intervals the tick spacing aims for
+pub(crate) const TARGET_TICKS: usize = 5;
+
+/// Size of the colored square in front of each legend entry
+pub(crate) const LEGEND_SWATCH: f32 = 10.0;
+/// Gap between legend entries
+const LEGEND_SPACING: f32 = 16.0;
+/// Rough width of one character - the canvas cannot measure text
+pub(crate) const CHAR_WIDTH: f32 = 7.0;
+
+/// Height of a chart unless set otherwise
+pub(crate) const DEFAULT_HEIGHT: f32 = 300.0;
+
+/// One named series with a value for every label
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub name: String,
+    pub color: Color,
+    /// Values in label order; missing ones count as zero
+    pub values: Vec<f32>,
+}
+
+impl Series {
+    pub fn value(&self, label: usize) -> f32 {
+        self.values.get(label).copied().unwrap_or(0.0)
+    }
+}
+
+/// Chart types the Chart view can switch between for the same data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ChartKind {
+    #[default]
+    Bar,
+    Line,
+    Area,
+    Pie,
+    Donut,
+    Scatter,
+}
+
+impl ChartKind {
+    pub const ALL: [ChartKind; 6] = [
+        ChartKind::Bar,
+        ChartKind::Line,
+        ChartKind::Area,
+        ChartKind::Pie,
+        ChartKind::Donut,
+        ChartKind::Scatter,
+    ];
+}
+
+impl std::fmt::Display for ChartKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ChartKind::Bar => "Bar",
+                ChartKind::Line => "Line",
+                ChartKind::Area => "Area",
+                ChartKind::Pie => "Pie",
+                ChartKind::Donut => "Donut",
+                ChartKind::Scatter => "Scatter",
+            }
+        )
+    }
+}
+
+/// Common surface of the chart widgets: data binding, axes and theming
+///
+/// On screen, charts draw into a [`ChartCache`], so they are only redrawn
+/// when their data, size or theme changes. Drawing goes through a
+/// [`Painter`], so the same commands also produce exported files.
+pub trait Chart {
+    /// Show `series` over `labels`, transitioning from the current data
+    /// where the chart animates
+    fn update_data(&mut self, labels: Vec<String>, series: Vec<Series>, now: Instant);
+
+    /// Advance running transitions to `now`
+    fn tick(&mut self, _now: Instant) {}
+
+    fn is_animating(&self) -> bool {
+        false
+    }
+
+    fn cache(&self) -> &ChartCache;
+
+    /// Draw the whole chart over a background filled by the caller
+    fn draw(&self, painter: &mut dyn Painter, theme: &Theme, size: Size);
+
+    fn height(&self) -> Length {
+        Length::Fixed(DEFAULT_HEIGHT)
+    }
+}
+
+/// Read-only canvas for any [`Chart`], filling the available width
+pub fn view<'a, Message: 'a>(chart: &'a dyn Chart) -> Element<'a, Message> {
+    Canvas::new(Static { chart })
+        .width(Length::Fill)
+        .height(chart.height())
+        .into()
+}
+
+struct Static<'a> {
+    chart: &'a dyn Chart,
+}
+
+impl<Message> canvas::Program<Message, Theme, Renderer> for Static<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry<Renderer>> {
+        let chart = self.chart;
+        vec![chart
+            .cache()
+            .draw(renderer, theme, bounds.size(), |painter, theme| chart.draw(painter, theme, bounds.size()))]
+    }
+}
+
+/// Canvas cache that also redraws when the theme changes
+#[derive(Debug, Default)]
+pub struct ChartCache {
+    cache: Cache,
+    /// Palette the cache was drawn with
+    palette: Cell<Option<Palette>>,
+}
+
+impl Clone for ChartCache {
+    /// Clones start empty; geometry is cheap to redraw
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl ChartCache {
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+
+    /// Cached geometry of `draw_fn` over the theme's background
+    ///
+    /// Under a [`faded`] theme, `draw_fn` gets the theme at full opacity and
+    /// everything it paints is faded afterwards, so fixed series colors fade
+    /// along with the palette ones.
+    pub fn draw(
+        &self,
+        renderer: &Renderer,
+        theme: &Theme,
+        size: Size,
+        draw_fn: impl Fn(&mut dyn Painter, &Theme),
+    ) -> Geometry<Renderer> {
+        // The cache only tracks the size, so drop it when the theme changes
+        if self.palette.get() != Some(theme.palette()) {
+            self.cache.clear();
+            self.palette.set(Some(theme.palette()));
+        }
+
+        self.cache.draw(renderer, size, |frame| {
+            frame.fill_rectangle(Point::ORIGIN, size, theme.extended_palette().background.base.color);
+
+            let opacity = opacity(theme);
+            if opacity >= 1.0 {
+                draw_fn(frame, theme);
+            } else if opacity > 0.0 {
+                let solid = faded(theme, opacity.recip());
+                draw_fn(&mut Translucent { painter: frame, opacity }, &solid);
+            }
+        })
+    }
+}
+
+/// Evenly spaced ticks of a value axis, always including zero
+#[derive(Debug, Clone, PartialEq)]
+pub struct Axis {
+    pub ticks: Vec<f32>,
+}
+
+impl Axis {
+    /// Axis with round ticks covering `low..=high`
+    pub fn fit(low: f32, high: f32) -> Self {
+        // An empty or all-zero chart still gets a 0-1 axis
+        let high = if high - low < 1.0 { low + 1.0 } else { high };
+        Self {
+            ticks: nice_ticks(low, high, TARGET_TICKS),
+        }
+    }
+
+    pub fn min(&self) -> f32 {
+        self.ticks.first().copied().unwrap_or(0.0)
+    }
+
+    pub fn max(&self) -> f32 {
+        self.ticks.last().copied().unwrap_or(1.0)
+    }
+
+    pub fn step(&self) -> f32 {
+        self.ticks.windows(2).next().map_or(1.0, |pair| pair[1] - pair[0])
+    }
+}
+
+/// Area between the axes
+pub(crate) fn plot_area(size: Size) -> Rectangle {
+    Rectangle::new(
+        Point::new(MARGIN_LEFT, MARGIN_TOP),
+        Size::new(
+            (size.width - MARGIN_LEFT - MARGIN_RIGHT).max(0.0),
+            (size.height - MARGIN_TOP - MARGIN_BOTTOM).max(0.0),
+        ),
+    )
+}
+
+/// Screen Y of `value` on a `bottom..top` scale filling `plot`
+pub(crate) fn y_of(plot: Rectangle, bottom: f32, top: f32, value: f32) -> f32 {
+    plot.y + (top - value) / (top - bottom).max(f32::EPSILON) * plot.height
+}
+
+/// Tick values covering `min..=max` and zero, spaced by 1, 2 or 5 times a
+/// power of ten so they read well as labels
+pub(crate) fn nice_ticks(min: f32, max: f32, target: usize) -> Vec<f32> {
+    let (min, max) = (min.min(0.0), max.max(0.0));
+    if max - min <= 0.0 || !min.is_finite() || !max.is_finite() || target == 0 {
+        return vec![0.0, 1.0];
+    }
+
+    let rough_step = (max - min) / target as f32;
+    let magnitude = 10f32.powf(rough_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough_step * (1.0 - 1e-4))
+        .unwrap_or(10.0 * magnitude);
+
+    // Tolerance keeps float noise from adding an extra empty interval
+    let first = (min / step + 1e-4).floor() as i32;
+    let last = ((max / step - 1e-4).ceil() as i32).max(first + 1);
+    (first..=last).map(|i| i as f32 * step).collect()
+}
+
+/// Format a tick with just enough decimals for its spacing
+pub(crate) fn tick_label(value: f32, step: f32) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    format!("{value:.decimals$}")
+}
+
+/// Gridlines and tick labels of a value axis showing `bottom..top`; ticks
+/// outside a still-changing scale are left out
+pub(crate) fn draw_grid(
+    painter: &mut dyn Painter,
+    theme: &Theme,
+    plot: Rectangle,
+    axis: &Axis,
+    (bottom, top): (f32, f32),
+) {
+    let palette = theme.extended_palette();
+
+    let tolerance = (top - bottom) * 1e-4;
+    for tick in axis
+        .ticks
+        .iter()
+        .filter(|tick| **tick >= bottom - tolerance && **tick <= top + tolerance)
+    {
+        let y = y_of(plot, bottom, top, *tick);
+
+        painter.stroke(
+            &Shape::Line(Point::new(plot.x, y), Point::new(plot.x + plot.width, y)),
+            1.0,
+            palette.background.strong.color,
+        );
+        painter.fill_text(Text {
+            content: tick_label(*tick, axis.step()),
+            position: Point::new(plot.x - 8.0, y),
+            color: palette.background.base.text,
+            size: 12.0.into(),
+            align_x: text::Alignment::Right,
+            align_y: alignment::Vertical::Center,
+            ..Text::default()
+        });
+    }
+}
+
+/// X axis on the zero line and the Y axis along the left of `plot`
+pub(crate) fn draw_axes(painter: &mut dyn Painter, theme: &Theme, plot: Rectangle, zero: f32) {
+    let color = theme.extended_palette().background.base.text;
+
+    painter.stroke(
+        &Shape::Line(Point::new(plot.x, zero), Point::new(plot.x + plot.width, zero)),
+        2.0,
+        color,
+    );
+    painter.stroke(
+        &Shape::Line(Point::new(plot.x, plot.y), Point::new(plot.x, plot.y + plot.height)),
+        2.0,
+        color,
+    );
+}
+
+/// Axis title in the top-left corner
+pub(crate) fn draw_title(painter: &mut dyn Painter, theme: &Theme, title: &str) {
+    painter.fill_text(Text {
+        content: title.to_string(),
+        position: Point::new(8.0, 8.0),
+        color: theme.extended_palette().background.base.text,
+        size: 13.0.into(),
+        ..Text::default()
+    });
+}
+
+/// Legend entries for `names`, right-aligned in the top margin
+pub(crate) fn legend<'a>(size: Size, names: impl DoubleEndedIterator<Item = &'a str>) -> Vec<Rectangle> {
+    let mut right = size.width - MARGIN_RIGHT;
+
+    let mut entries: Vec<Rectangle> = names
+        .rev()
+        .map(|name| {
+            let width = LEGEND_SWATCH + 6.0 + name.chars().count() as f32 * CHAR_WIDTH;
+            right -= width;
+            let bounds = Rectangle::new(Point::new(right, 8.0), Size::new(width, 16.0));
+            right -= LEGEND_SPACING;
+            bounds
+        })
+        .collect();
+
+    entries.reverse();
+    entries
+}
+
+/// One legend entry; hidden entries get an empty swatch and a dimmed name
+pub(crate) fn draw_legend_entry(
+    painter: &mut dyn Painter,
+    theme: &Theme,
+    entry: Rectangle,
+    name: &str,
+    color: Color,
+    hidden: bool,
+) {
+    let text_color = theme.extended_palette().background.base.text;
+    let swatch = Shape::Rectangle(Rectangle::new(
+        Point::new(entry.x, entry.center_y() - LEGEND_SWATCH / 2.0),
+        Size::new(LEGEND_SWATCH, LEGEND_SWATCH),
+    ));
+
+    if hidden {
+        painter.stroke(&swatch, 1.5, color);
+    } else {
+        painter.fill(&swatch, color);
+    }
+    painter.fill_text(Text {
+        content: name.to_string(),
+        position: Point::new(entry.x + LEGEND_SWATCH + 6.0, entry.center_y()),
+        color: if hidden { Color { a: 0.4, ..text_color } } else { text_color },
+        size: 12.0.into(),
+        align_y: alignment::Vertical::Center,
+        ..Text::default()
+    });
+}
+
+/// `content` in a small box next to `anchor`, in the theme's inverted colors
+pub(crate) fn draw_tooltip(
+    painter: &mut dyn Painter,
+    theme: &Theme,
+    size: Size,
+    anchor: Point,
+    content: String,
+) {
+    let palette = theme.extended_palette();
+    let box_size = Size::new(content.chars().count() as f32 * CHAR_WIDTH + 16.0, 24.0);
+
+    // Keep the box inside the chart
+    let position = Point::new(
+        (anchor.x + 12.0).min(size.width - box_size.width).max(0.0),
+        (anchor.y - box_size.height - 8.0).max(0.0),
+    );
+
+    painter.fill(
+        &Shape::RoundedRectangle(Rectangle::new(position, box_size), 4.0),
+        Color { a: 0.9, ..palette.background.base.text },
+    );
+    painter.fill_text(Text {
+        content,
+        position: position + Vector::new(8.0, 5.0),
+        color: palette.background.base.color,
+        size: 13.0.into(),
+        ..Text::default()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_use_round_steps() {
+        assert_eq!(nice_ticks(0.0, 100.0, 5), vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+        assert_eq!(nice_ticks(0.0, 37.0, 5), vec![0.0, 10.0, 20.0, 30.0, 40.0]);
+        assert_eq!(nice_ticks(0.0, 7.0, 5), vec![0.0, 2.0, 4.0, 6.0, 8.0]);
+    }
+
+    #[test]
+    fn ticks_cover_negative_values_and_zero() {
+        assert_eq!(
+            nice_ticks(-30.0, 70.0, 5),
+            vec![-40.0, -20.0, 0.0, 20.0, 40.0, 60.0, 80.0]
+        );
+        assert_eq!(nice_ticks(-7.0, -3.0, 5), vec![-8.0, -6.0, -4.0, -2.0, 0.0]);
+    }
+
+    #[test]
+    fn ticks_always_cover_the_range() {
+        for (min, max) in [(0.0, 0.3), (0.0, 1.0), (-4.5, 0.0), (0.0, 19.9), (-99.0, 101.0), (0.0, 1234.0)] {
+            let ticks = nice_ticks(min, max, 5);
+            assert!(ticks[0] <= min + 1e-3, "{min}..{max}: {ticks:?}");
+            assert!(*ticks.last().unwrap() >= max - 1e-3, "{min}..{max}: {ticks:?}");
+            assert!(ticks.len() <= 12, "{min}..{max}: {ticks:?}");
+        }
+    }
+
+    #[test]
+    fn fractional_ticks_get_decimals() {
+        assert_eq!(tick_label(0.5, 0.1), "0.5");
+        assert_eq!(tick_label(40.0, 20.0), "40");
+    }
+
+    #[test]
+    fn empty_axis_still_spans_one() {
+        let axis = Axis::fit(0.0, 0.0);
+        assert_eq!((axis.min(), axis.max()), (0.0, 1.0));
+        assert!((axis.step() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn values_map_into_the_plot() {
+        let plot = Rectangle::new(Point::new(0.0, 10.0), Size::new(100.0, 100.0));
+        assert_eq!(y_of(plot, -50.0, 50.0, 50.0), 10.0);
+        assert_eq!(y_of(plot, -50.0, 50.0, 0.0), 60.0);
+        assert_eq!(y_of(plot, -50.0, 50.0, -50.0), 110.0);
+    }
+
+    #[test]
+    fn legend_entries_line_up_on_the_right() {
+        let size = Size::new(600.0, 300.0);
+        let legend = legend(size, ["First", "Second"].into_iter());
+
+        assert_eq!(legend.len(), 2);
+        assert!(legend[0].x + legend[0].width < legend[1].x);
+        assert!((legend[1].x + legend[1].width - (size.width - MARGIN_RIGHT)).abs() < 1e-3);
+    }
+}
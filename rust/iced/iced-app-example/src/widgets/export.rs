@@ -0,0 +1,452 @@
+use iced::advanced::graphics::geometry::Renderer as _;
+use iced::advanced::renderer::Headless;
+use iced::alignment;
+use iced::futures::executor::block_on;
+use iced::widget::canvas::{Frame, Text};
+use iced::widget::text;
+use iced::{Color, Font, Pixels, Rectangle, Renderer, Size, Theme};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use super::chart::Chart;
+use super::painter::{Painter, Shape};
+
+/// File types a chart can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Svg,
+    Png,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 2] = [ExportFormat::Svg, ExportFormat::Png];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Svg => "svg",
+            ExportFormat::Png => "png",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ExportFormat::Svg => "SVG",
+                ExportFormat::Png => "PNG",
+            }
+        )
+    }
+}
+
+/// Output sizes offered by the Chart view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExportSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl ExportSize {
+    pub const ALL: [ExportSize; 3] = [ExportSize::Small, ExportSize::Medium, ExportSize::Large];
+
+    /// Width and height in pixels
+    pub fn dimensions(self) -> (u32, u32) {
+        match self {
+            ExportSize::Small => (800, 400),
+            ExportSize::Medium => (1200, 600),
+            ExportSize::Large => (1920, 1080),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (width, height) = self.dimensions();
+        write!(f, "{width} × {height}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportOptions {
+    pub width: u32,
+    pub height: u32,
+    /// Color behind the chart, `None` for a transparent background
+    pub background: Option<Color>,
+}
+
+impl ExportOptions {
+    fn size(&self) -> Size {
+        Size::new(self.width as f32, self.height as f32)
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(std::io::Error),
+    /// No software renderer could be created for PNG output
+    Renderer,
+    Png(png::EncodingError),
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Io(error) => write!(f, "cannot write chart file: {error}"),
+            ExportError::Renderer => write!(f, "software renderer unavailable"),
+            ExportError::Png(error) => write!(f, "cannot encode PNG: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<std::io::Error> for ExportError {
+    fn from(error: std::io::Error) -> Self {
+        ExportError::Io(error)
+    }
+}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(error: png::EncodingError) -> Self {
+        ExportError::Png(error)
+    }
+}
+
+/// SVG document of `chart`, drawn by the same code as on screen
+pub fn to_svg(chart: &dyn Chart, theme: &Theme, options: &ExportOptions) -> String {
+    let mut svg = SvgPainter::default();
+    let (width, height) = (options.width, options.height);
+
+    if let Some(background) = options.background {
+        svg.fill(&Shape::Rectangle(Rectangle::with_size(options.size())), background);
+    }
+    chart.draw(&mut svg, theme, options.size());
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\">\n{}</svg>\n",
+        svg.body
+    )
+}
+
+/// PNG file of `chart`, rasterized on the CPU so no GPU is needed
+pub fn to_png(chart: &dyn Chart, theme: &Theme, options: &ExportOptions) -> Result<Vec<u8>, ExportError> {
+    let mut renderer = block_on(<Renderer as Headless>::new(
+        Font::DEFAULT,
+        Pixels(16.0),
+        Some("tiny-skia"),
+    ))
+    .ok_or(ExportError::Renderer)?;
+
+    let mut frame = Frame::new(&renderer, options.size());
+    chart.draw(&mut frame, theme, options.size());
+    renderer.draw_geometry(frame.into_geometry());
+
+    let mut rgba = renderer.screenshot(
+        Size::new(options.width, options.height),
+        1.0,
+        options.background.unwrap_or(Color::TRANSPARENT),
+    );
+    unpremultiply(&mut rgba);
+
+    let mut file = Vec::new();
+    let mut encoder = png::Encoder::new(&mut file, options.width, options.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    // Charts are mostly flat color, which compresses very well
+    encoder.set_compression(png::Compression::Best);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&rgba)?;
+    writer.finish()?;
+
+    Ok(file)
+}
+
+/// Write `chart` to `path` as `format`
+pub async fn export(
+    chart: Box<dyn Chart + Send>,
+    theme: Theme,
+    options: ExportOptions,
+    format: ExportFormat,
+    path: PathBuf,
+) -> Result<PathBuf, ExportError> {
+    let contents = match format {
+        ExportFormat::Svg => to_svg(chart.as_ref(), &theme, &options).into_bytes(),
+        ExportFormat::Png => to_png(chart.as_ref(), &theme, &options)?,
+    };
+    tokio::fs::write(&path, contents).await?;
+    Ok(path)
+}
+
+/// Turn the renderer's premultiplied pixels into the straight alpha PNG
+/// expects
+fn unpremultiply(rgba: &mut [u8]) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        let alpha = u32::from(pixel[3]);
+        if alpha == 0 || alpha == 255 {
+            continue;
+        }
+        for channel in &mut pixel[..3] {
+            *channel = ((u32::from(*channel) * 255 + alpha / 2) / alpha).min(255) as u8;
+        }
+    }
+}
+
+/// Painter collecting SVG elements
+#[derive(Default)]
+struct SvgPainter {
+    body: String,
+}
+
+impl SvgPainter {
+    /// Opening of the element for `shape`, up to its paint attributes
+    fn open(&mut self, shape: &Shape) {
+        let body = &mut self.body;
+        let _ = match shape {
+            Shape::Rectangle(bounds) => write!(
+                body,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+                number(bounds.x),
+                number(bounds.y),
+                number(bounds.width),
+                number(bounds.height)
+            ),
+            Shape::RoundedRectangle(bounds, radius) => write!(
+                body,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"",
+                number(bounds.x),
+                number(bounds.y),
+                number(bounds.width),
+                number(bounds.height),
+                number(*radius)
+            ),
+            Shape::Line(from, to) => write!(
+                body,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"",
+                number(from.x),
+                number(from.y),
+                number(to.x),
+                number(to.y)
+            ),
+            Shape::Circle(center, radius) => write!(
+                body,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"",
+                number(center.x),
+                number(center.y),
+                number(*radius)
+            ),
+            Shape::Polyline { points, closed } => {
+                let points: Vec<String> = points
+                    .iter()
+                    .map(|point| format!("{},{}", number(point.x), number(point.y)))
+                    .collect();
+                write!(
+                    body,
+                    "<{} points=\"{}\"",
+                    if *closed { "polygon" } else { "polyline" },
+                    points.join(" ")
+                )
+            }
+        };
+    }
+}
+
+impl Painter for SvgPainter {
+    fn fill(&mut self, shape: &Shape, color: Color) {
+        // A line has no inside to fill
+        if matches!(shape, Shape::Line(..)) {
+            return;
+        }
+        self.open(shape);
+        let _ = writeln!(self.body, " {}/>", paint("fill", color));
+    }
+
+    fn stroke(&mut self, shape: &Shape, width: f32, color: Color) {
+        self.open(shape);
+        let _ = writeln!(
+            self.body,
+            " fill=\"none\" {} stroke-width=\"{}\"/>",
+            paint("stroke", color),
+            number(width)
+        );
+    }
+
+    fn fill_text(&mut self, text: Text) {
+        let anchor = match text.align_x {
+            text::Alignment::Center => "middle",
+            text::Alignment::Right => "end",
+            _ => "start",
+        };
+        let baseline = match text.align_y {
+            alignment::Vertical::Top => "text-before-edge",
+            alignment::Vertical::Center => "central",
+            alignment::Vertical::Bottom => "text-after-edge",
+        };
+
+        let _ = writeln!(
+            self.body,
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"{anchor}\" \
+             dominant-baseline=\"{baseline}\" {}>{}</text>",
+            number(text.position.x),
+            number(text.position.y),
+            number(text.size.0),
+            paint("fill", text.color),
+            escape(&text.content)
+        );
+    }
+}
+
+/// `attribute` set to the color, plus its opacity unless opaque
+fn paint(attribute: &str, color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+    if a == 255 {
+        format!("{attribute}=\"rgb({r},{g},{b})\"")
+    } else {
+        format!(
+            "{attribute}=\"rgb({r},{g},{b})\" {attribute}-opacity=\"{}\"",
+            number(color.a)
+        )
+    }
+}
+
+/// Coordinate with at most two decimals and no trailing zeros
+fn number(value: f32) -> String {
+    let text = format!("{value:.2}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+fn escape(content: &str) -> String {
+    content
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::{LineChart, PieChart, Series};
+    use iced::Point;
+
+    fn options(background: Option<Color>) -> ExportOptions {
+        ExportOptions {
+            width: 400,
+            height: 200,
+            background,
+        }
+    }
+
+    fn series() -> Vec<Series> {
+        vec![Series {
+            name: "Values".to_string(),
+            color: Color::from_rgb(1.0, 0.0, 0.0),
+            values: vec![1.0, 3.0],
+        }]
+    }
+
+    fn line_chart() -> LineChart {
+        LineChart::new(vec!["A & B".to_string(), "<C>".to_string()], series())
+    }
+
+    #[test]
+    fn svg_has_requested_size_and_background() {
+        let svg = to_svg(&line_chart(), &Theme::Light, &options(Some(Color::WHITE)));
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"400\" height=\"200\""));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"400\" height=\"200\" fill=\"rgb(255,255,255)\"/>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn transparent_svg_has_no_background() {
+        let svg = to_svg(&line_chart(), &Theme::Light, &options(None));
+
+        assert!(!svg.contains("width=\"400\" height=\"200\" fill="));
+    }
+
+    #[test]
+    fn svg_replays_chart_drawing() {
+        let svg = to_svg(&line_chart(), &Theme::Light, &options(None));
+
+        // Line, points and escaped labels
+        assert!(svg.contains("<polyline points="));
+        assert!(svg.contains("<circle "));
+        assert!(svg.contains(">A &amp; B</text>"));
+        assert!(svg.contains(">&lt;C&gt;</text>"));
+
+        let pie = PieChart::new(vec!["A".to_string(), "B".to_string()], series());
+        assert!(to_svg(&pie, &Theme::Light, &options(None)).contains("<polygon points="));
+    }
+
+    #[test]
+    fn png_is_rendered_at_the_requested_size() {
+        let png = to_png(&line_chart(), &Theme::Light, &options(None)).expect("software renderer");
+
+        assert_eq!(png[16..20], 400u32.to_be_bytes());
+        assert_eq!(png[20..24], 200u32.to_be_bytes());
+    }
+
+    #[test]
+    fn export_writes_the_file_it_returns() {
+        let dir = std::env::temp_dir().join(format!("icedapp-chart-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("chart.svg");
+
+        let export = export(Box::new(line_chart()), Theme::Light, options(None), ExportFormat::Svg, path.clone());
+        let written = iced::executor::Default::new().unwrap().block_on(export).unwrap();
+        let svg = std::fs::read_to_string(&written).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(written, path);
+        assert!(svg.starts_with("<svg "));
+    }
+
+    #[test]
+    fn colors_carry_their_opacity() {
+        assert_eq!(paint("fill", Color::BLACK), "fill=\"rgb(0,0,0)\"");
+        assert_eq!(
+            paint("stroke", Color { a: 0.25, ..Color::WHITE }),
+            "stroke=\"rgb(255,255,255)\" stroke-opacity=\"0.25\""
+        );
+    }
+
+    #[test]
+    fn numbers_are_short() {
+        assert_eq!(number(56.0), "56");
+        assert_eq!(number(12.5), "12.5");
+        assert_eq!(number(1.0 / 3.0), "0.33");
+        assert_eq!(number(-0.001), "0");
+    }
+
+    #[test]
+    fn pixels_are_unpremultiplied() {
+        let mut rgba = [64, 32, 0, 128, 10, 20, 30, 255, 5, 5, 5, 0];
+        unpremultiply(&mut rgba);
+
+        assert_eq!(rgba, [128, 64, 0, 128, 10, 20, 30, 255, 5, 5, 5, 0]);
+    }
+
+    #[test]
+    fn shapes_map_to_svg_elements() {
+        let mut svg = SvgPainter::default();
+        svg.stroke(&Shape::Line(Point::ORIGIN, Point::new(10.0, 5.0)), 1.0, Color::BLACK);
+        svg.fill(&Shape::Line(Point::ORIGIN, Point::new(10.0, 5.0)), Color::BLACK);
+
+        assert_eq!(
+            svg.body,
+            "<line x1=\"0\" y1=\"0\" x2=\"10\" y2=\"5\" fill=\"none\" stroke=\"rgb(0,0,0)\" stroke-width=\"1\"/>\n"
+        );
+    }
+}
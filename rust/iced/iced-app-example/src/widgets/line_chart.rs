@@ -0,0 +1,213 @@
+use iced::widget::canvas::Text;
+use iced::widget::text;
+use iced::{Color, Point, Size, Theme};
+use std::time::Instant;
+
+use super::chart::{self, Axis, Chart, ChartCache, Series};
+use super::painter::{Painter, Shape};
+
+/// Radius of the dot marking each value
+const POINT_RADIUS: f32 = 3.5;
+
+/// Opacity of the area under each line
+const AREA_ALPHA: f32 = 0.25;
+
+/// One line per series across the labels, optionally filled down to zero
+#[derive(Debug, Clone, Default)]
+pub struct LineChart {
+    cache: ChartCache,
+    labels: Vec<String>,
+    series: Vec<Series>,
+    filled: bool,
+    y_axis_title: Option<String>,
+}
+
+impl LineChart {
+    pub fn new(labels: Vec<String>, series: Vec<Series>) -> Self {
+        Self {
+            labels,
+            series,
+            ..Self::default()
+        }
+    }
+
+    /// Title shown above the Y axis
+    pub fn y_axis_title(mut self, title: impl Into<String>) -> Self {
+        self.y_axis_title = Some(title.into());
+        self
+    }
+
+    /// Fill the area between each line and zero, making an area chart
+    pub fn set_filled(&mut self, filled: bool) {
+        if self.filled != filled {
+            self.filled = filled;
+            self.cache.clear();
+        }
+    }
+
+    fn axis(&self) -> Axis {
+        let values = self.series.iter().flat_map(|series| {
+            (0..self.labels.len()).map(|label| series.value(label))
+        });
+        let (low, high) = values.fold((0.0f32, 0.0f32), |(low, high), value| {
+            (low.min(value), high.max(value))
+        });
+        Axis::fit(low, high)
+    }
+
+    /// Points of every series, for a chart of the given size
+    ///
+    /// Labels sit in the middle of equal slots, as bars do, so switching
+    /// chart type keeps them in place.
+    fn points(&self, size: Size) -> Vec<Vec<Point>> {
+        let plot = chart::plot_area(size);
+        let axis = self.axis();
+        let slot = plot.width / self.labels.len().max(1) as f32;
+
+        self.series
+            .iter()
+            .map(|series| {
+                (0..self.labels.len())
+                    .map(|label| {
+                        Point::new(
+                            plot.x + (label as f32 + 0.5) * slot,
+                            chart::y_of(plot, axis.min(), axis.max(), series.value(label)),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Chart for LineChart {
+    fn update_data(&mut self, labels: Vec<String>, series: Vec<Series>, _now: Instant) {
+        if labels != self.labels || series != self.series {
+            self.labels = labels;
+            self.series = series;
+            self.cache.clear();
+        }
+    }
+
+    fn cache(&self) -> &ChartCache {
+        &self.cache
+    }
+
+    fn draw(&self, painter: &mut dyn Painter, theme: &Theme, size: Size) {
+        let plot = chart::plot_area(size);
+        let axis = self.axis();
+        let zero = chart::y_of(plot, axis.min(), axis.max(), 0.0);
+        let text_color = theme.extended_palette().background.base.text;
+
+        chart::draw_grid(painter, theme, plot, &axis, (axis.min(), axis.max()));
+        if let Some(title) = &self.y_axis_title {
+            chart::draw_title(painter, theme, title);
+        }
+
+        for (series, points) in self.series.iter().zip(self.points(size)) {
+            let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                continue;
+            };
+
+            // Draw area down to the zero line
+            if self.filled {
+                let area = std::iter::once(Point::new(first.x, zero))
+                    .chain(points.iter().copied())
+                    .chain([Point::new(last.x, zero)])
+                    .collect();
+                painter.fill(
+                    &Shape::Polyline { points: area, closed: true },
+                    Color { a: AREA_ALPHA, ..series.color },
+                );
+            }
+
+            // Draw line
+            painter.stroke(
+                &Shape::Polyline { points: points.clone(), closed: false },
+                2.5,
+                series.color,
+            );
+
+            // Draw points
+            for point in &points {
+                painter.fill(&Shape::Circle(*point, POINT_RADIUS), series.color);
+            }
+        }
+
+        // Draw label below each point
+        let slot = plot.width / self.labels.len().max(1) as f32;
+        for (index, label) in self.labels.iter().enumerate() {
+            painter.fill_text(Text {
+                content: label.clone(),
+                position: Point::new(plot.x + (index as f32 + 0.5) * slot, plot.y + plot.height + 10.0),
+                color: text_color,
+                size: 12.0.into(),
+                align_x: text::Alignment::Center,
+                ..Text::default()
+            });
+        }
+
+        chart::draw_axes(painter, theme, plot, zero);
+
+        // Draw legend
+        let legend = chart::legend(size, self.series.iter().map(|series| series.name.as_str()));
+        for (series, entry) in self.series.iter().zip(legend) {
+            chart::draw_legend_entry(painter, theme, entry, &series.name, series.color, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: Size = Size::new(600.0, 300.0);
+
+    fn line_chart(values: &[f32]) -> LineChart {
+        LineChart::new(
+            (0..values.len()).map(|i| format!("Point {i}")).collect(),
+            vec![Series {
+                name: "A".to_string(),
+                color: Color::BLACK,
+                values: values.to_vec(),
+            }],
+        )
+    }
+
+    #[test]
+    fn points_sit_in_the_middle_of_their_slots() {
+        let points = &line_chart(&[10.0, 20.0, 30.0]).points(SIZE)[0];
+        let plot = chart::plot_area(SIZE);
+        let slot = plot.width / 3.0;
+
+        for (index, point) in points.iter().enumerate() {
+            assert!((point.x - (plot.x + (index as f32 + 0.5) * slot)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn points_follow_the_axis() {
+        let points = &line_chart(&[0.0, 40.0, -20.0]).points(SIZE)[0];
+        let plot = chart::plot_area(SIZE);
+
+        // -20..40 in steps of 10
+        let zero = plot.y + plot.height * 40.0 / 60.0;
+        assert!((points[0].y - zero).abs() < 1e-3);
+        assert!((points[1].y - plot.y).abs() < 1e-3);
+        assert!((points[2].y - (plot.y + plot.height)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn missing_values_count_as_zero() {
+        let mut chart = line_chart(&[10.0]);
+        chart.update_data(
+            vec!["A".to_string(), "B".to_string()],
+            chart.series.clone(),
+            Instant::now(),
+        );
+
+        let points = &chart.points(SIZE)[0];
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].y, chart::y_of(chart::plot_area(SIZE), 0.0, 10.0, 0.0));
+    }
+}
@@ -1,3 +1,16 @@
 pub mod bar_chart;
+pub mod chart;
+pub mod export;
+pub mod inert;
+pub mod line_chart;
+pub mod painter;
+pub mod pie_chart;
+pub mod scatter_chart;
 
-pub use bar_chart::{BarChart, BarData};
+pub use bar_chart::{BarChart, BarLayout};
+pub use chart::{Chart, ChartKind, Series};
+pub use export::{ExportFormat, ExportSize};
+pub use inert::inert;
+pub use line_chart::LineChart;
+pub use pie_chart::PieChart;
+pub use scatter_chart::ScatterChart;
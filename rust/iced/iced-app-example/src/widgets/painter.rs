@@ -0,0 +1,100 @@
+use iced::widget::canvas::{Frame, Path, Stroke, Text};
+use iced::{Color, Point, Rectangle};
+
+use crate::styles::color::with_alpha;
+
+/// Outline that charts fill or stroke
+///
+/// Kept as plain geometry rather than a canvas [`Path`] so that backends
+/// other than the canvas, like SVG export, can read it back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Rectangle(Rectangle),
+    RoundedRectangle(Rectangle, f32),
+    Line(Point, Point),
+    Circle(Point, f32),
+    /// Straight segments through `points`, back to the first one if `closed`
+    Polyline { points: Vec<Point>, closed: bool },
+}
+
+impl Shape {
+    pub fn to_path(&self) -> Path {
+        match self {
+            Shape::Rectangle(bounds) => Path::rectangle(bounds.position(), bounds.size()),
+            Shape::RoundedRectangle(bounds, radius) => {
+                Path::rounded_rectangle(bounds.position(), bounds.size(), (*radius).into())
+            }
+            Shape::Line(from, to) => Path::line(*from, *to),
+            Shape::Circle(center, radius) => Path::circle(*center, *radius),
+            Shape::Polyline { points, closed } => Path::new(|builder| {
+                let Some((first, rest)) = points.split_first() else {
+                    return;
+                };
+                builder.move_to(*first);
+                for point in rest {
+                    builder.line_to(*point);
+                }
+                if *closed {
+                    builder.close();
+                }
+            }),
+        }
+    }
+}
+
+/// Drawing commands shared by every chart, so the same drawing code can
+/// target the screen or an exported file
+pub trait Painter {
+    fn fill(&mut self, shape: &Shape, color: Color);
+
+    fn stroke(&mut self, shape: &Shape, width: f32, color: Color);
+
+    fn fill_text(&mut self, text: Text);
+}
+
+impl Painter for Frame {
+    fn fill(&mut self, shape: &Shape, color: Color) {
+        Frame::fill(self, &shape.to_path(), color);
+    }
+
+    fn stroke(&mut self, shape: &Shape, width: f32, color: Color) {
+        Frame::stroke(
+            self,
+            &shape.to_path(),
+            Stroke::default().with_width(width).with_color(color),
+        );
+    }
+
+    fn fill_text(&mut self, text: Text) {
+        Frame::fill_text(self, text);
+    }
+}
+
+/// Draws through another painter with every color faded to `opacity`
+pub struct Translucent<'a> {
+    pub painter: &'a mut dyn Painter,
+    pub opacity: f32,
+}
+
+impl Translucent<'_> {
+    fn fade(&self, color: Color) -> Color {
+        with_alpha(color, color.a * self.opacity)
+    }
+}
+
+impl Painter for Translucent<'_> {
+    fn fill(&mut self, shape: &Shape, color: Color) {
+        let color = self.fade(color);
+        self.painter.fill(shape, color);
+    }
+
+    fn stroke(&mut self, shape: &Shape, width: f32, color: Color) {
+        let color = self.fade(color);
+        self.painter.stroke(shape, width, color);
+    }
+
+    fn fill_text(&mut self, text: Text) {
+        let color = self.fade(text.color);
+        self.painter.fill_text(Text { color, ..text });
+    }
+}
@@ -0,0 +1,232 @@
+use iced::alignment;
+use iced::widget::canvas::Text;
+use iced::widget::text;
+use iced::{Color, Point, Size, Theme, Vector};
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::time::Instant;
+
+use super::chart::{self, Chart, ChartCache, Series, MARGIN_BOTTOM, MARGIN_TOP};
+use super::painter::{Painter, Shape};
+
+/// Straight segments per full turn when tracing slice edges
+const ARC_SEGMENTS: f32 = 96.0;
+
+/// Slices narrower than this get no percentage label
+const LABEL_MIN_ANGLE: f32 = 0.3;
+
+/// One slice per label, sized by a single series; with a hole it becomes a
+/// donut
+///
+/// Of several series, the last one is shown. Negative values cannot be
+/// drawn as slices and are left out.
+#[derive(Debug, Clone, Default)]
+pub struct PieChart {
+    cache: ChartCache,
+    labels: Vec<String>,
+    series: Vec<Series>,
+    /// Radius of the hole as a share of the outer radius
+    hole: f32,
+}
+
+impl PieChart {
+    pub fn new(labels: Vec<String>, series: Vec<Series>) -> Self {
+        Self {
+            labels,
+            series,
+            ..Self::default()
+        }
+    }
+
+    /// Cut out the middle, `0.0` for a full pie up to `0.9`
+    pub fn set_hole(&mut self, hole: f32) {
+        let hole = hole.clamp(0.0, 0.9);
+        if self.hole != hole {
+            self.hole = hole;
+            self.cache.clear();
+        }
+    }
+
+    fn values(&self) -> Vec<f32> {
+        let series = self.series.last();
+        (0..self.labels.len())
+            .map(|label| series.map_or(0.0, |series| series.value(label)))
+            .collect()
+    }
+
+    /// Center and outer radius, leaving the margins free for the legend
+    fn circle(size: Size) -> (Point, f32) {
+        let radius = ((size.height - MARGIN_TOP - MARGIN_BOTTOM).min(size.width) / 2.0).max(0.0);
+        let center = Point::new(size.width / 2.0, MARGIN_TOP + (size.height - MARGIN_TOP - MARGIN_BOTTOM) / 2.0);
+        (center, radius)
+    }
+}
+
+/// Start and end angle of each slice, clockwise from twelve o'clock
+///
+/// Zero and negative values get empty slices, so indices still match the
+/// labels.
+fn slice_angles(values: &[f32]) -> Vec<(f32, f32)> {
+    let total: f32 = values.iter().map(|value| value.max(0.0)).sum();
+    let mut angle = 0.0;
+
+    values
+        .iter()
+        .map(|value| {
+            let start = angle;
+            if total > 0.0 {
+                angle += value.max(0.0) / total * TAU;
+            }
+            (start, angle)
+        })
+        .collect()
+}
+
+/// Point at `angle` clockwise from twelve o'clock
+fn polar(center: Point, radius: f32, angle: f32) -> Point {
+    let angle = angle - FRAC_PI_2;
+    center + Vector::new(radius * angle.cos(), radius * angle.sin())
+}
+
+/// Colors of the slices, cycling through the theme's accents
+fn slice_colors(theme: &Theme) -> [Color; 5] {
+    let palette = theme.extended_palette();
+    [
+        palette.primary.base.color,
+        palette.success.base.color,
+        palette.danger.base.color,
+        palette.warning.base.color,
+        palette.secondary.strong.color,
+    ]
+}
+
+impl Chart for PieChart {
+    fn update_data(&mut self, labels: Vec<String>, series: Vec<Series>, _now: Instant) {
+        if labels != self.labels || series != self.series {
+            self.labels = labels;
+            self.series = series;
+            self.cache.clear();
+        }
+    }
+
+    fn cache(&self) -> &ChartCache {
+        &self.cache
+    }
+
+    fn draw(&self, painter: &mut dyn Painter, theme: &Theme, size: Size) {
+        let palette = theme.extended_palette();
+        let colors = slice_colors(theme);
+        let (center, radius) = Self::circle(size);
+        let inner = radius * self.hole;
+        let values = self.values();
+        let total: f32 = values.iter().map(|value| value.max(0.0)).sum();
+
+        if let Some(series) = self.series.last() {
+            chart::draw_title(painter, theme, &series.name);
+        }
+
+        for (index, (start, end)) in slice_angles(&values).into_iter().enumerate() {
+            if end <= start {
+                continue;
+            }
+
+            // Outer edge forwards, inner edge (or the center) backwards
+            let steps = ((end - start) / TAU * ARC_SEGMENTS).ceil().max(1.0) as usize;
+            let angle = |step: usize| start + (end - start) * step as f32 / steps as f32;
+            let slice = Shape::Polyline {
+                points: (0..=steps)
+                    .map(|step| polar(center, radius, angle(step)))
+                    .chain((0..=steps).rev().map(|step| polar(center, inner, angle(step))))
+                    .collect(),
+                closed: true,
+            };
+
+            painter.fill(&slice, colors[index % colors.len()]);
+            painter.stroke(&slice, 1.5, palette.background.base.color);
+
+            // Draw share in the middle of the slice or ring
+            if end - start >= LABEL_MIN_ANGLE {
+                painter.fill_text(Text {
+                    content: format!("{:.0}%", values[index] / total * 100.0),
+                    position: polar(center, (radius + inner) / 2.0, (start + end) / 2.0),
+                    color: Color::WHITE,
+                    size: 12.0.into(),
+                    align_x: text::Alignment::Center,
+                    align_y: alignment::Vertical::Center,
+                    ..Text::default()
+                });
+            }
+        }
+
+        // Draw legend of labels, dimming the ones without a slice
+        let legend = chart::legend(size, self.labels.iter().map(String::as_str));
+        for (index, (label, entry)) in self.labels.iter().zip(legend).enumerate() {
+            chart::draw_legend_entry(
+                painter,
+                theme,
+                entry,
+                label,
+                colors[index % colors.len()],
+                values[index] <= 0.0,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn slices_fill_the_circle_in_proportion() {
+        let angles = slice_angles(&[1.0, 2.0, 1.0]);
+
+        assert_close(angles[0].0, 0.0);
+        assert_close(angles[0].1, TAU / 4.0);
+        assert_close(angles[1].1, TAU * 3.0 / 4.0);
+        assert_close(angles[2].1, TAU);
+        // Each slice starts where the previous one ended
+        assert!(angles.windows(2).all(|pair| pair[0].1 == pair[1].0));
+    }
+
+    #[test]
+    fn negative_and_zero_values_get_empty_slices() {
+        let angles = slice_angles(&[-5.0, 3.0, 0.0, 1.0]);
+
+        assert_eq!(angles.len(), 4);
+        assert_eq!(angles[0].0, angles[0].1);
+        assert_eq!(angles[2].0, angles[2].1);
+        assert_close(angles[1].1 - angles[1].0, TAU * 0.75);
+        assert_close(angles[3].1, TAU);
+    }
+
+    #[test]
+    fn nothing_to_show_gives_no_slices() {
+        assert!(slice_angles(&[0.0, -1.0]).iter().all(|(start, end)| start == end));
+        assert!(slice_angles(&[]).is_empty());
+    }
+
+    #[test]
+    fn angles_run_clockwise_from_the_top() {
+        let center = Point::new(100.0, 100.0);
+
+        let top = polar(center, 10.0, 0.0);
+        let right = polar(center, 10.0, TAU / 4.0);
+        assert_close(top.x, 100.0);
+        assert_close(top.y, 90.0);
+        assert_close(right.x, 110.0);
+        assert_close(right.y, 100.0);
+    }
+
+    #[test]
+    fn circle_fits_between_the_margins() {
+        let (center, radius) = PieChart::circle(Size::new(600.0, 300.0));
+
+        assert_close(radius, (300.0 - MARGIN_TOP - MARGIN_BOTTOM) / 2.0);
+        assert!(center.y - radius >= MARGIN_TOP - 1e-3);
+        assert!(center.y + radius <= 300.0 - MARGIN_BOTTOM + 1e-3);
+    }
+}
@@ -0,0 +1,238 @@
+use iced::alignment;
+use iced::widget::canvas::Text;
+use iced::widget::text;
+use iced::{Point, Rectangle, Size, Theme};
+use std::time::Instant;
+
+use super::chart::{self, Axis, Chart, ChartCache, Series};
+use super::painter::{Painter, Shape};
+
+/// Radius of each dot
+const POINT_RADIUS: f32 = 5.0;
+
+/// Every series after the first plotted against the first one, one point
+/// per label
+///
+/// With only one series there is nothing to pair it with, so just the axes
+/// are drawn.
+#[derive(Debug, Clone, Default)]
+pub struct ScatterChart {
+    cache: ChartCache,
+    labels: Vec<String>,
+    series: Vec<Series>,
+}
+
+impl ScatterChart {
+    pub fn new(labels: Vec<String>, series: Vec<Series>) -> Self {
+        Self {
+            labels,
+            series,
+            ..Self::default()
+        }
+    }
+
+    fn fit(values: impl Iterator<Item = f32>) -> Axis {
+        let (low, high) = values.fold((0.0f32, 0.0f32), |(low, high), value| {
+            (low.min(value), high.max(value))
+        });
+        Axis::fit(low, high)
+    }
+
+    /// Axis of the first series, along the bottom
+    fn x_axis(&self) -> Axis {
+        let first = self.series.first();
+        Self::fit((0..self.labels.len()).filter_map(|label| Some(first?.value(label))))
+    }
+
+    /// Axis of the remaining series, up the left
+    fn axis(&self) -> Axis {
+        Self::fit(
+            self.series
+                .iter()
+                .skip(1)
+                .flat_map(|series| (0..self.labels.len()).map(|label| series.value(label))),
+        )
+    }
+
+    /// Points of every series after the first, for a chart of the given size
+    fn points(&self, size: Size) -> Vec<Vec<Point>> {
+        let Some(first) = self.series.first() else {
+            return Vec::new();
+        };
+
+        let plot = chart::plot_area(size);
+        let (x_axis, y_axis) = (self.x_axis(), self.axis());
+
+        self.series[1..]
+            .iter()
+            .map(|series| {
+                (0..self.labels.len())
+                    .map(|label| {
+                        Point::new(
+                            x_of(plot, &x_axis, first.value(label)),
+                            chart::y_of(plot, y_axis.min(), y_axis.max(), series.value(label)),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Screen X of `value` on the horizontal `axis` filling `plot`
+fn x_of(plot: Rectangle, axis: &Axis, value: f32) -> f32 {
+    plot.x + (value - axis.min()) / (axis.max() - axis.min()).max(f32::EPSILON) * plot.width
+}
+
+impl Chart for ScatterChart {
+    fn update_data(&mut self, labels: Vec<String>, series: Vec<Series>, _now: Instant) {
+        if labels != self.labels || series != self.series {
+            self.labels = labels;
+            self.series = series;
+            self.cache.clear();
+        }
+    }
+
+    fn cache(&self) -> &ChartCache {
+        &self.cache
+    }
+
+    fn draw(&self, painter: &mut dyn Painter, theme: &Theme, size: Size) {
+        let palette = theme.extended_palette();
+        let text_color = palette.background.base.text;
+        let plot = chart::plot_area(size);
+        let (x_axis, y_axis) = (self.x_axis(), self.axis());
+
+        chart::draw_grid(painter, theme, plot, &y_axis, (y_axis.min(), y_axis.max()));
+
+        // Draw vertical gridlines and X tick labels
+        for tick in &x_axis.ticks {
+            let x = x_of(plot, &x_axis, *tick);
+
+            painter.stroke(
+                &Shape::Line(Point::new(x, plot.y), Point::new(x, plot.y + plot.height)),
+                1.0,
+                palette.background.strong.color,
+            );
+            painter.fill_text(Text {
+                content: chart::tick_label(*tick, x_axis.step()),
+                position: Point::new(x, plot.y + plot.height + 8.0),
+                color: text_color,
+                size: 12.0.into(),
+                align_x: text::Alignment::Center,
+                ..Text::default()
+            });
+        }
+
+        // Name the axes after the series on them
+        if let Some(first) = self.series.first() {
+            painter.fill_text(Text {
+                content: first.name.clone(),
+                position: Point::new(plot.x + plot.width, size.height - 4.0),
+                color: text_color,
+                size: 13.0.into(),
+                align_x: text::Alignment::Right,
+                align_y: alignment::Vertical::Bottom,
+                ..Text::default()
+            });
+        }
+
+        for (series, points) in self.series.iter().skip(1).zip(self.points(size)) {
+            for (label, point) in self.labels.iter().zip(points) {
+                painter.fill(&Shape::Circle(point, POINT_RADIUS), series.color);
+                painter.fill_text(Text {
+                    content: label.clone(),
+                    position: Point::new(point.x + POINT_RADIUS + 4.0, point.y),
+                    color: text_color,
+                    size: 11.0.into(),
+                    align_y: alignment::Vertical::Center,
+                    ..Text::default()
+                });
+            }
+        }
+
+        chart::draw_axes(
+            painter,
+            theme,
+            plot,
+            chart::y_of(plot, y_axis.min(), y_axis.max(), 0.0),
+        );
+
+        // Draw legend of the plotted series
+        let plotted = self.series.iter().skip(1);
+        let legend = chart::legend(size, plotted.clone().map(|series| series.name.as_str()));
+        for (series, entry) in plotted.zip(legend) {
+            chart::draw_legend_entry(painter, theme, entry, &series.name, series.color, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::Color;
+
+    const SIZE: Size = Size::new(600.0, 300.0);
+
+    fn series(name: &str, values: &[f32]) -> Series {
+        Series {
+            name: name.to_string(),
+            color: Color::BLACK,
+            values: values.to_vec(),
+        }
+    }
+
+    fn labels(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("Point {i}")).collect()
+    }
+
+    #[test]
+    fn first_series_runs_along_x() {
+        let chart = ScatterChart::new(
+            labels(2),
+            vec![series("X", &[0.0, 10.0]), series("Y", &[5.0, 5.0])],
+        );
+        let plot = chart::plot_area(SIZE);
+        let points = &chart.points(SIZE)[0];
+
+        assert!((points[0].x - plot.x).abs() < 1e-3);
+        assert!((points[1].x - (plot.x + plot.width)).abs() < 1e-3);
+        assert_eq!(points[0].y, points[1].y);
+    }
+
+    #[test]
+    fn each_later_series_gets_its_own_points() {
+        let chart = ScatterChart::new(
+            labels(3),
+            vec![
+                series("X", &[1.0, 2.0, 3.0]),
+                series("Y", &[1.0, 2.0, 3.0]),
+                series("Z", &[-3.0, -2.0, -1.0]),
+            ],
+        );
+        let points = chart.points(SIZE);
+
+        assert_eq!(points.len(), 2);
+        assert!(points.iter().all(|points| points.len() == 3));
+        // Negative values land below the zero line
+        let zero = chart::y_of(chart::plot_area(SIZE), chart.axis().min(), chart.axis().max(), 0.0);
+        assert!(points[1].iter().all(|point| point.y > zero));
+    }
+
+    #[test]
+    fn axes_cover_their_own_series() {
+        let chart = ScatterChart::new(
+            labels(2),
+            vec![series("X", &[0.0, 95.0]), series("Y", &[-4.0, 3.0])],
+        );
+
+        assert_eq!((chart.x_axis().min(), chart.x_axis().max()), (0.0, 100.0));
+        assert_eq!((chart.axis().min(), chart.axis().max()), (-4.0, 4.0));
+    }
+
+    #[test]
+    fn a_single_series_has_nothing_to_plot() {
+        let chart = ScatterChart::new(labels(2), vec![series("X", &[1.0, 2.0])]);
+        assert!(chart.points(SIZE).iter().all(Vec::is_empty));
+    }
+}